@@ -0,0 +1,114 @@
+#[macro_use]
+extern crate criterion;
+extern crate nom;
+extern crate nom_span;
+
+use criterion::{black_box, Criterion};
+use nom::{
+    character::complete::{alpha1, anychar, multispace0},
+    multi::many0_count,
+    sequence::preceded,
+    IResult, Slice,
+};
+use nom_span::Spanned;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn input() -> String {
+    "let héllo = \"wörld 🙌\";\n\tprint(héllo)\u{2028}\n".repeat(2_000)
+}
+
+fn slice_every_char(c: &mut Criterion) {
+    let input = input();
+    let mut group = c.benchmark_group("slice every char");
+
+    group.bench_function("default", |b| {
+        b.iter(|| many0_count(anychar::<_, ()>)(black_box(Span::new(&input, true))))
+    });
+    group.bench_function("unicode newlines", |b| {
+        b.iter(|| {
+            let span = Spanned::builder(input.as_str())
+                .unicode_newlines(true)
+                .build();
+
+            many0_count(anychar::<_, ()>)(black_box(span))
+        })
+    });
+    group.bench_function("unicode newlines and crlf", |b| {
+        b.iter(|| {
+            let span = Spanned::builder(input.as_str())
+                .unicode_newlines(true)
+                .crlf(true)
+                .build();
+
+            many0_count(anychar::<_, ()>)(black_box(span))
+        })
+    });
+
+    group.finish();
+}
+
+fn slice_whole_input(c: &mut Criterion) {
+    let input = input();
+    let mut group = c.benchmark_group("slice whole input");
+
+    group.bench_function("default", |b| {
+        b.iter(|| black_box(Span::new(&input, true)).slice(input.len()..))
+    });
+    group.bench_function("unicode newlines", |b| {
+        b.iter(|| {
+            let span = Spanned::builder(input.as_str())
+                .unicode_newlines(true)
+                .build();
+
+            black_box(span).slice(input.len()..)
+        })
+    });
+    group.bench_function("unicode newlines and crlf", |b| {
+        b.iter(|| {
+            let span = Spanned::builder(input.as_str())
+                .unicode_newlines(true)
+                .crlf(true)
+                .build();
+
+            black_box(span).slice(input.len()..)
+        })
+    });
+
+    group.finish();
+}
+
+fn skip_whitespace(c: &mut Criterion) {
+    fn ascii_word(span: Span<'_>) -> IResult<Span<'_>, Span<'_>, ()> {
+        alpha1(span.skip_ascii_whitespace())
+    }
+
+    fn unicode_word(span: Span<'_>) -> IResult<Span<'_>, Span<'_>, ()> {
+        alpha1(span.skip_whitespace())
+    }
+
+    let input = "\n  word\r\n\t\n\n  other \n".repeat(2_000);
+    let mut group = c.benchmark_group("skip whitespace");
+
+    group.bench_function("multispace0", |b| {
+        b.iter(|| {
+            many0_count(preceded(multispace0::<_, ()>, alpha1))(black_box(Span::new(&input, true)))
+        })
+    });
+    group.bench_function("skip_ascii_whitespace", |b| {
+        b.iter(|| many0_count(ascii_word)(black_box(Span::new(&input, true))))
+    });
+    group.bench_function("skip_whitespace", |b| {
+        b.iter(|| many0_count(unicode_word)(black_box(Span::new(&input, true))))
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    slice_every_char,
+    slice_whole_input,
+    skip_whitespace
+);
+criterion_main!(benches);
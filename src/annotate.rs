@@ -0,0 +1,80 @@
+use annotate_snippets::{Annotation, AnnotationKind, Group, Level, Snippet};
+use memchr::memchr;
+use nom::AsBytes;
+use span::Span;
+use tracker::PositionTracker;
+use Spanned;
+
+impl Span {
+    /// Get the snippet of the lines of `source` covered by the span, the span being annotated
+    /// with `label`
+    ///
+    /// `source` is the input the span comes from. Only the lines of the span are in the snippet,
+    /// numbered from the line of its start, and a span over several lines gets a multi-line
+    /// annotation
+    #[must_use]
+    pub fn to_annotated_snippet<'a>(
+        &self,
+        source: &'a str,
+        label: &'a str,
+    ) -> Snippet<'a, Annotation<'a>> {
+        let (snippet, annotation) = self.snippet(source);
+
+        snippet.annotation(annotation.label(label))
+    }
+
+    /// Get an error titled `message`, with the snippet of the lines of `source` covered by the
+    /// span like [`Span::to_annotated_snippet`], the span being annotated without label
+    #[must_use]
+    pub fn to_error_group<'a>(&self, source: &'a str, message: &'a str) -> Group<'a> {
+        let (snippet, annotation) = self.snippet(source);
+
+        Level::ERROR
+            .primary_title(message)
+            .element(snippet.annotation(annotation))
+    }
+
+    /// Get the snippet of the lines covered by the span and the annotation of the span in it
+    fn snippet<'a>(&self, source: &'a str) -> (Snippet<'a, Annotation<'a>>, Annotation<'a>) {
+        let line_start = source[..self.start.offset]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        // The last byte covered, to not take the line after a span ending with a newline
+        let last = if self.end.offset > self.start.offset {
+            self.end.offset - 1
+        } else {
+            self.start.offset
+        };
+        let line_end = memchr(b'\n', &source.as_bytes()[last..])
+            .map_or(source.len(), |newline| last + newline + 1);
+
+        let snippet = Snippet::source(&source[line_start..line_end]).line_start(self.start.line);
+        let annotation = AnnotationKind::Primary
+            .span(self.start.offset - line_start..self.end.offset - line_start);
+
+        (snippet, annotation)
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: AsBytes,
+    C: PositionTracker,
+{
+    /// Get the snippet of the lines of `source` covered by the data, annotated with `label`, like
+    /// [`Span::to_annotated_snippet`]
+    #[must_use]
+    pub fn to_annotated_snippet<'a>(
+        &self,
+        source: &'a str,
+        label: &'a str,
+    ) -> Snippet<'a, Annotation<'a>> {
+        Span::of(self).to_annotated_snippet(source, label)
+    }
+
+    /// Get an error titled `message` annotating the data, like [`Span::to_error_group`]
+    #[must_use]
+    pub fn to_error_group<'a>(&self, source: &'a str, message: &'a str) -> Group<'a> {
+        Span::of(self).to_error_group(source, message)
+    }
+}
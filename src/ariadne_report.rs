@@ -0,0 +1,80 @@
+use ariadne::Source;
+use nom::AsBytes;
+use span::Span;
+use tracker::PositionTracker;
+use Spanned;
+
+/// A range of bytes in the source `source`, to be the span of the ariadne labels and reports
+///
+/// The offsets are bytes, so the reports must be configured with `IndexType::Byte`, as ariadne
+/// counts chars by default
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub struct ReportSpan<Id> {
+    /// The id of the source, given to the cache to get the source
+    pub source: Id,
+    /// The offset of the first byte
+    pub start: usize,
+    /// The offset right after the last byte
+    pub end: usize,
+}
+
+impl<Id> ReportSpan<Id> {
+    #[must_use]
+    pub fn new(source: Id, start: usize, end: usize) -> Self {
+        debug_assert!(start <= end, "the span ends before it starts");
+
+        Self { source, start, end }
+    }
+}
+
+impl<Id> ariadne::Span for ReportSpan<Id>
+where
+    Id: PartialEq + ToOwned,
+{
+    type SourceId = Id;
+
+    fn source(&self) -> &Id {
+        &self.source
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn end(&self) -> usize {
+        self.end
+    }
+}
+
+impl Span {
+    /// Get the span of the bytes of the span in the source `source`, for ariadne
+    #[must_use]
+    pub fn report_span<Id>(&self, source: Id) -> ReportSpan<Id> {
+        ReportSpan::new(source, self.start.offset, self.end.offset)
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: AsBytes,
+    C: PositionTracker,
+{
+    /// Get the span of the data in the source `source`, for ariadne
+    #[must_use]
+    pub fn report_span<Id>(&self, source: Id) -> ReportSpan<Id> {
+        Span::of(self).report_span(source)
+    }
+}
+
+impl<'a, X, C> Spanned<&'a str, X, C> {
+    /// Get the ariadne cache of the source `id` over the data, borrowing it instead of copying it
+    /// in a `Source`. The reports made from the spans parsed from this one with the same id are
+    /// written with it
+    ///
+    /// In debug builds, panics if the span isn't the one over the whole input
+    pub fn to_ariadne_cache<Id>(&self, id: Id) -> (Id, Source<&'a str>) {
+        self.debug_assert_whole_input("the cache");
+
+        (id, Source::from(self.data))
+    }
+}
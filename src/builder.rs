@@ -0,0 +1,149 @@
+use encoding::{ColumnUnit, Dynamic};
+use position::SourceId;
+use tracker::{KeepCounters, KeptCounters};
+use Spanned;
+
+/// Configure a [`Spanned`] before creating it, see [`Spanned::builder`]
+///
+/// ```ignore
+/// let span = Spanned::builder("input").utf8(false).start_line(10).build();
+/// ```
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct SpannedBuilder<T, X = ()> {
+    data: T,
+    line: usize,
+    col: usize,
+    offset: usize,
+    extra: X,
+    encoding: Dynamic,
+    source: SourceId,
+}
+
+impl<T> SpannedBuilder<T> {
+    pub(crate) fn new(data: T) -> Self {
+        Self {
+            data,
+            line: 1,
+            col: 1,
+            offset: 0,
+            extra: (),
+            encoding: Dynamic::default(),
+            source: SourceId::default(),
+        }
+    }
+}
+
+impl<T, X> SpannedBuilder<T, X> {
+    /// Count the columns in UTF-8 chars or in bytes, UTF-8 is handled by default
+    #[must_use]
+    pub fn utf8(mut self, handle_utf8: bool) -> Self {
+        self.encoding.set_utf8(handle_utf8);
+        self
+    }
+
+    /// Choose the unit the columns are counted in, see [`ColumnUnit`]. Any unit but
+    /// [`ColumnUnit::Bytes`] handles UTF-8
+    #[must_use]
+    pub fn column_unit(mut self, column_unit: ColumnUnit) -> Self {
+        self.encoding.set_column_unit(column_unit);
+        self
+    }
+
+    /// Expand `\t` to the next multiple of `tab_width` when counting the columns, like editors
+    /// display them. Tabs are counted as a single column by default, and a width of 0 disables the
+    /// expansion
+    #[must_use]
+    pub fn tab_width(mut self, tab_width: u8) -> Self {
+        self.encoding.set_tab_width(tab_width);
+        self
+    }
+
+    /// Treat `\r\n` as a single line ending and a lone `\r` as a line ending too. The `\r` of a
+    /// `\r\n` doesn't take a column, even when the input is sliced between the `\r` and the `\n`
+    ///
+    /// Whether a `\r` is followed by a `\n` is decided with the rest of the input. When nothing is
+    /// known after it, like at the end of what `take_until("\n")` recognized, the `\r` is taken as
+    /// the start of a `\r\n`, so the end of the span is on the same line as the `\n`
+    #[must_use]
+    pub fn crlf(mut self, crlf: bool) -> Self {
+        self.encoding.set_crlf(crlf);
+        self
+    }
+
+    /// Treat U+2028 LINE SEPARATOR, U+2029 PARAGRAPH SEPARATOR and the form feed `\x0C` as line
+    /// endings, like JavaScript does. It's only taken into account when UTF-8 is handled
+    #[must_use]
+    pub fn unicode_newlines(mut self, unicode_newlines: bool) -> Self {
+        self.encoding.set_unicode_newlines(unicode_newlines);
+        self
+    }
+
+    /// End the lines with `line_separator` instead of `\n`, so the lines can be records separated
+    /// by NUL or `\x1e`. With [`SpannedBuilder::crlf`], a `\r` followed by the separator is a
+    /// single line ending
+    #[must_use]
+    pub fn line_separator(mut self, line_separator: u8) -> Self {
+        self.encoding.set_line_separator(line_separator);
+        self
+    }
+
+    /// Line the span starts at, 1 by default
+    #[must_use]
+    pub fn start_line(mut self, line: usize) -> Self {
+        debug_assert!(line >= 1, "line numbers start at 1");
+
+        self.line = line;
+        self
+    }
+
+    /// Column the span starts at, 1 by default
+    #[must_use]
+    pub fn start_col(mut self, col: usize) -> Self {
+        debug_assert!(col >= 1, "column numbers start at 1");
+
+        self.col = col;
+        self
+    }
+
+    /// Byte offset the span starts at, 0 by default. The char offset starts at the same value
+    #[must_use]
+    pub fn start_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The id of the input, see [`Spanned::with_source_id`]
+    #[must_use]
+    pub fn source_id(mut self, source: SourceId) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Extra data carried by the span, see [`Spanned::new_extra`]
+    pub fn extra<U>(self, extra: U) -> SpannedBuilder<T, U> {
+        SpannedBuilder {
+            data: self.data,
+            line: self.line,
+            col: self.col,
+            offset: self.offset,
+            extra,
+            encoding: self.encoding,
+            source: self.source,
+        }
+    }
+
+    /// Create the span
+    pub fn build(self) -> Spanned<T, X> {
+        let mut tracker = self.encoding;
+        *tracker.kept_mut() = KeptCounters::new(self.col, self.offset, self.source);
+
+        Spanned {
+            data: self.data,
+            offset: self.offset,
+            line: self.line,
+            col: self.col,
+            extra: self.extra,
+            tracker,
+        }
+    }
+}
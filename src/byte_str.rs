@@ -0,0 +1,408 @@
+use bstr::BStr;
+#[cfg(feature = "nom8")]
+use nom::Input;
+#[cfg(feature = "nom7")]
+use nom::{
+    error::{ErrorKind, ParseError},
+    Err, IResult, InputIter, InputLength, InputTake, InputTakeAtPosition, Slice,
+};
+use nom::{AsBytes, Compare, CompareResult, ExtendInto, FindSubstring, Needed, Offset, ParseTo};
+#[cfg(feature = "nom7")]
+use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use std::{
+    fmt,
+    iter::{Copied, Enumerate},
+    ops::Deref,
+    slice, str,
+    str::FromStr,
+};
+use tracker::Items;
+use Spanned;
+
+/// A `&BStr` that nom can parse, as the nom traits can't be implemented for `&BStr` itself
+///
+/// It's parsed like a `&[u8]`, and the columns are the chars bstr decodes, every invalid sequence
+/// being a single replacement char
+///
+/// ```ignore
+/// use nom_span::{BStrInput, Spanned};
+///
+/// let span = Spanned::new(BStrInput::new(b"caf\xc3\xa9 \xff!"), true);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BStrInput<'a> {
+    data: &'a BStr,
+}
+
+impl<'a> BStrInput<'a> {
+    /// Wrap the bytes `data`
+    pub fn new<B>(data: &'a B) -> Self
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        Self {
+            data: BStr::new(data),
+        }
+    }
+
+    /// Get the wrapped `BStr`, borrowing the input rather than the wrapper
+    #[must_use]
+    pub fn as_bstr(&self) -> &'a BStr {
+        self.data
+    }
+
+    fn bytes(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl<'a> From<&'a BStr> for BStrInput<'a> {
+    fn from(data: &'a BStr) -> Self {
+        Self { data }
+    }
+}
+
+impl<'a> From<&'a [u8]> for BStrInput<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        Self::new(data)
+    }
+}
+
+impl Deref for BStrInput<'_> {
+    type Target = BStr;
+
+    fn deref(&self) -> &BStr {
+        self.data
+    }
+}
+
+impl fmt::Debug for BStrInput<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.data, f)
+    }
+}
+
+impl fmt::Display for BStrInput<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.data, f)
+    }
+}
+
+impl PartialEq<[u8]> for BStrInput<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.bytes() == other
+    }
+}
+
+impl PartialEq<&[u8]> for BStrInput<'_> {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.bytes() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<&[u8; N]> for BStrInput<'_> {
+    fn eq(&self, other: &&[u8; N]) -> bool {
+        self.bytes() == &other[..]
+    }
+}
+
+impl PartialEq<&str> for BStrInput<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.bytes() == other.as_bytes()
+    }
+}
+
+impl Items for BStrInput<'_> {
+    type Item = u8;
+
+    fn items(&self) -> &[u8] {
+        self.bytes()
+    }
+}
+
+/// The bytes are parsed as a `Spanned<&[u8]>`, that counts invalid UTF-8 the way bstr decodes it,
+/// every invalid sequence being a single replacement char
+impl<'a, X, C> From<Spanned<&'a [u8], X, C>> for Spanned<&'a BStr, X, C> {
+    fn from(span: Spanned<&'a [u8], X, C>) -> Self {
+        span.map_data(BStr::new)
+    }
+}
+
+impl<'a, X, C> From<Spanned<&'a BStr, X, C>> for Spanned<&'a [u8], X, C> {
+    fn from(span: Spanned<&'a BStr, X, C>) -> Self {
+        span.map_data(|data| &**data)
+    }
+}
+
+impl<'a, X, C> From<Spanned<&'a BStr, X, C>> for Spanned<BStrInput<'a>, X, C> {
+    fn from(span: Spanned<&'a BStr, X, C>) -> Self {
+        span.map_data(BStrInput::from)
+    }
+}
+
+impl<'a, X, C> From<Spanned<BStrInput<'a>, X, C>> for Spanned<&'a BStr, X, C> {
+    fn from(span: Spanned<BStrInput<'a>, X, C>) -> Self {
+        span.map_data(|data| data.as_bstr())
+    }
+}
+
+impl<'a, X, C> From<Spanned<&'a [u8], X, C>> for Spanned<BStrInput<'a>, X, C> {
+    fn from(span: Spanned<&'a [u8], X, C>) -> Self {
+        span.map_data(BStrInput::from)
+    }
+}
+
+impl<'a, X, C> From<Spanned<BStrInput<'a>, X, C>> for Spanned<&'a [u8], X, C> {
+    fn from(span: Spanned<BStrInput<'a>, X, C>) -> Self {
+        span.map_data(|data| data.bytes())
+    }
+}
+
+impl AsBytes for BStrInput<'_> {
+    fn as_bytes(&self) -> &[u8] {
+        self.bytes()
+    }
+}
+
+#[cfg(feature = "nom7")]
+impl InputLength for BStrInput<'_> {
+    fn input_len(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// The iterators are the ones of `&[u8]`, so they borrow the input and not the wrapper
+#[cfg(feature = "nom7")]
+impl<'a> InputIter for BStrInput<'a> {
+    type Item = u8;
+
+    type Iter = Enumerate<Copied<slice::Iter<'a, u8>>>;
+
+    type IterElem = Copied<slice::Iter<'a, u8>>;
+
+    fn iter_indices(&self) -> Self::Iter {
+        self.bytes().iter_indices()
+    }
+
+    fn iter_elements(&self) -> Self::IterElem {
+        self.bytes().iter_elements()
+    }
+
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(u8) -> bool,
+    {
+        self.bytes().position(predicate)
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.bytes().slice_index(count)
+    }
+}
+
+#[cfg(feature = "nom7")]
+impl InputTake for BStrInput<'_> {
+    fn take(&self, count: usize) -> Self {
+        Self::new(&self.bytes()[..count])
+    }
+
+    fn take_split(&self, count: usize) -> (Self, Self) {
+        let (prefix, suffix) = self.bytes().split_at(count);
+
+        (Self::new(suffix), Self::new(prefix))
+    }
+}
+
+/// Behaves like the impl of nom for `&[u8]`, the blanket impl for the other inputs coming with a
+/// `Compare` that can't take a `&str` tag
+#[cfg(feature = "nom7")]
+impl InputTakeAtPosition for BStrInput<'_> {
+    type Item = u8;
+
+    fn split_at_position<P, E: ParseError<Self>>(&self, predicate: P) -> IResult<Self, Self, E>
+    where
+        P: Fn(u8) -> bool,
+    {
+        match self.position(predicate) {
+            Some(n) => Ok(self.take_split(n)),
+            None => Err(Err::Incomplete(Needed::new(1))),
+        }
+    }
+
+    fn split_at_position1<P, E: ParseError<Self>>(
+        &self,
+        predicate: P,
+        e: ErrorKind,
+    ) -> IResult<Self, Self, E>
+    where
+        P: Fn(u8) -> bool,
+    {
+        match self.position(predicate) {
+            Some(0) => Err(Err::Error(E::from_error_kind(*self, e))),
+            Some(n) => Ok(self.take_split(n)),
+            None => Err(Err::Incomplete(Needed::new(1))),
+        }
+    }
+
+    fn split_at_position_complete<P, E: ParseError<Self>>(
+        &self,
+        predicate: P,
+    ) -> IResult<Self, Self, E>
+    where
+        P: Fn(u8) -> bool,
+    {
+        match self.position(predicate) {
+            Some(n) => Ok(self.take_split(n)),
+            None => Ok(self.take_split(self.input_len())),
+        }
+    }
+
+    fn split_at_position1_complete<P, E: ParseError<Self>>(
+        &self,
+        predicate: P,
+        e: ErrorKind,
+    ) -> IResult<Self, Self, E>
+    where
+        P: Fn(u8) -> bool,
+    {
+        match self.position(predicate) {
+            Some(0) => Err(Err::Error(E::from_error_kind(*self, e))),
+            Some(n) => Ok(self.take_split(n)),
+            None if self.data.is_empty() => Err(Err::Error(E::from_error_kind(*self, e))),
+            None => Ok(self.take_split(self.input_len())),
+        }
+    }
+}
+
+#[cfg(feature = "nom7")]
+macro_rules! impl_slice {
+    ($($range:ty),*) => {$(
+        impl Slice<$range> for BStrInput<'_> {
+            fn slice(&self, range: $range) -> Self {
+                Self::new(&self.bytes()[range])
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "nom7")]
+impl_slice!(Range<usize>, RangeTo<usize>, RangeFrom<usize>, RangeFull);
+
+#[cfg(feature = "nom8")]
+impl<'a> Input for BStrInput<'a> {
+    type Item = u8;
+
+    type Iter = Copied<slice::Iter<'a, u8>>;
+
+    type IterIndices = Enumerate<Self::Iter>;
+
+    fn input_len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn take(&self, index: usize) -> Self {
+        Self::new(&self.bytes()[..index])
+    }
+
+    fn take_from(&self, index: usize) -> Self {
+        Self::new(&self.bytes()[index..])
+    }
+
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        let (prefix, suffix) = self.bytes().split_at(index);
+
+        (Self::new(suffix), Self::new(prefix))
+    }
+
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(u8) -> bool,
+    {
+        self.bytes().position(predicate)
+    }
+
+    fn iter_elements(&self) -> Self::Iter {
+        self.bytes().iter_elements()
+    }
+
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.bytes().iter_indices()
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.bytes().slice_index(count)
+    }
+}
+
+impl<'b> Compare<&'b str> for BStrInput<'_> {
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.bytes().compare(t)
+    }
+
+    fn compare_no_case(&self, t: &'b str) -> CompareResult {
+        self.bytes().compare_no_case(t)
+    }
+}
+
+impl<'b> Compare<&'b [u8]> for BStrInput<'_> {
+    fn compare(&self, t: &'b [u8]) -> CompareResult {
+        self.bytes().compare(t)
+    }
+
+    fn compare_no_case(&self, t: &'b [u8]) -> CompareResult {
+        self.bytes().compare_no_case(t)
+    }
+}
+
+impl Compare<BStrInput<'_>> for BStrInput<'_> {
+    fn compare(&self, t: BStrInput<'_>) -> CompareResult {
+        self.bytes().compare(t.bytes())
+    }
+
+    fn compare_no_case(&self, t: BStrInput<'_>) -> CompareResult {
+        self.bytes().compare_no_case(t.bytes())
+    }
+}
+
+impl<'b> FindSubstring<&'b str> for BStrInput<'_> {
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.bytes().find_substring(substr)
+    }
+}
+
+impl<'b> FindSubstring<&'b [u8]> for BStrInput<'_> {
+    fn find_substring(&self, substr: &'b [u8]) -> Option<usize> {
+        self.bytes().find_substring(substr)
+    }
+}
+
+impl ExtendInto for BStrInput<'_> {
+    type Item = u8;
+
+    type Extender = Vec<u8>;
+
+    fn new_builder(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn extend_into(&self, acc: &mut Vec<u8>) {
+        acc.extend_from_slice(self.bytes());
+    }
+}
+
+/// The offset is counted in bytes, `second` having to be a part of `self`
+impl Offset for BStrInput<'_> {
+    fn offset(&self, second: &Self) -> usize {
+        self.bytes().offset(second.bytes())
+    }
+}
+
+/// The bytes are parsed when they are valid UTF-8
+impl<R> ParseTo<R> for BStrInput<'_>
+where
+    R: FromStr,
+{
+    fn parse_to(&self) -> Option<R> {
+        str::from_utf8(self.bytes()).ok()?.parse().ok()
+    }
+}
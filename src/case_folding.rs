@@ -0,0 +1,61 @@
+use caseless::Caseless;
+use nom::{
+    error::{ErrorKind, ParseError},
+    Err, IResult,
+};
+use std::iter::once;
+use tracker::PositionTracker;
+use Spanned;
+
+type StrSpan<'a, X, C> = Spanned<&'a str, X, C>;
+type TagResult<'a, X, C, E> = IResult<StrSpan<'a, X, C>, StrSpan<'a, X, C>, E>;
+
+/// Recognize `tag` ignoring the case with the Unicode case folding, where nom's `tag_no_case`
+/// lowercases the chars one by one and can't match an input of another length
+///
+/// The recognized input can have another length than `tag`, for example `"straße"` matches
+/// `"STRASSE"`. A char is never partially matched
+///
+/// # Errors
+///
+/// Returns an [`ErrorKind::Tag`] error at the start of the input if it doesn't start with `tag`
+pub fn tag_no_case_unicode<'a, X, C, E>(
+    tag: &str,
+) -> impl Fn(StrSpan<'a, X, C>) -> TagResult<'a, X, C, E> + '_
+where
+    X: Clone,
+    C: PositionTracker,
+    E: ParseError<StrSpan<'a, X, C>>,
+{
+    move |input| {
+        let Some(len) = matched_len(input.data, tag) else {
+            return Err(Err::Error(E::from_error_kind(input, ErrorKind::Tag)));
+        };
+
+        let (matched, rest) = input.data.split_at(len);
+
+        Ok((
+            input.advance_to(matched.as_bytes(), rest),
+            input.respan(matched),
+        ))
+    }
+}
+
+/// Get the length in bytes of the start of `data` matching `tag` once both are case folded
+fn matched_len(data: &str, tag: &str) -> Option<usize> {
+    let mut tag = tag.chars().default_case_fold().peekable();
+
+    for (i, c) in data.char_indices() {
+        if tag.peek().is_none() {
+            return Some(i);
+        }
+
+        for folded in once(c).default_case_fold() {
+            if tag.next() != Some(folded) {
+                return None;
+            }
+        }
+    }
+
+    tag.peek().is_none().then_some(data.len())
+}
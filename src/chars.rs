@@ -0,0 +1,69 @@
+use encoding::Dynamic;
+use position::Position;
+use std::iter::FusedIterator;
+use tracker::{CounterStorage, PositionTracker};
+use Spanned;
+
+impl<'a, X, C> Spanned<&'a str, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+    /// Iterate over the chars of the data with their position, counted like the spans sliced at
+    /// each char
+    #[must_use]
+    pub fn positioned_chars(&self) -> PositionedChars<'a, X, C> {
+        PositionedChars { rest: self.clone() }
+    }
+}
+
+/// The iterator of the chars of a span with their position, given by
+/// [`Spanned::positioned_chars`]
+#[derive(Clone, Debug)]
+pub struct PositionedChars<'a, X = (), C = Dynamic> {
+    rest: Spanned<&'a str, X, C>,
+}
+
+impl<'a, X, C> PositionedChars<'a, X, C>
+where
+    C: CounterStorage,
+{
+    /// Get the position of the next char, or the end of the span once they are all iterated
+    pub fn current_position(&self) -> Position {
+        self.rest.pos()
+    }
+
+    /// Get the span of the chars not iterated yet
+    pub fn rest(&self) -> &Spanned<&'a str, X, C> {
+        &self.rest
+    }
+}
+
+impl<X, C> Iterator for PositionedChars<'_, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+    type Item = (Position, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.rest.data.chars().next()?;
+        let (consumed, rest) = self.rest.data.split_at(c.len_utf8());
+
+        let pos = self.rest.pos();
+        self.rest = self.rest.advance_to(consumed.as_bytes(), rest);
+
+        Some((pos, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rest.data.chars().size_hint()
+    }
+}
+
+impl<X, C> FusedIterator for PositionedChars<'_, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+}
@@ -0,0 +1,19 @@
+use codespan_reporting::files::SimpleFile;
+use std::fmt::Display;
+use Spanned;
+
+impl<'a, X, C> Spanned<&'a str, X, C> {
+    /// Get a codespan file named `name` over the data, to which the labels made from the spans
+    /// parsed from this one refer, their ranges being given by `Range::from` or
+    /// [`Spanned::byte_range_to`]
+    ///
+    /// In debug builds, panics if the span isn't the one over the whole input
+    pub fn to_simple_file<N>(&self, name: N) -> SimpleFile<N, &'a str>
+    where
+        N: Display + Clone,
+    {
+        self.debug_assert_whole_input("the file");
+
+        SimpleFile::new(name, self.data)
+    }
+}
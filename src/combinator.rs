@@ -0,0 +1,200 @@
+use encoding::Encoding;
+#[cfg(feature = "nom8")]
+use nom::Input as Take;
+#[cfg(feature = "nom8")]
+use nom::Input as SliceIndex;
+#[cfg(feature = "nom7")]
+use nom::InputIter as SliceIndex;
+#[cfg(feature = "nom7")]
+use nom::InputTake as Take;
+use nom::{
+    error::{ErrorKind, ParseError},
+    AsBytes, Err, IResult, Needed, Parser,
+};
+use position::Position;
+use tracker::CounterStorage;
+use Spanned;
+
+type SpanResult<T, X, C, O, E> = IResult<Spanned<T, X, C>, (O, Position, Position), E>;
+type TakeResult<T, X, C, E> = IResult<Spanned<T, X, C>, Spanned<T, X, C>, E>;
+type ConsumedResult<T, X, C, O, E> = IResult<Spanned<T, X, C>, (Spanned<T, X, C>, O), E>;
+
+/// Get the current position as an empty span, without consuming the input
+///
+/// Like the `position` of `nom_locate`, to know where a token starts in a sequence of parsers
+///
+/// # Errors
+///
+/// Never fails, the error type is only there to fit with the other parsers
+pub fn position<T, X, C, E>(
+    input: Spanned<T, X, C>,
+) -> IResult<Spanned<T, X, C>, Spanned<T, X, C>, E>
+where
+    Spanned<T, X, C>: Take,
+    E: ParseError<Spanned<T, X, C>>,
+{
+    let empty = input.take(0);
+
+    Ok((input, empty))
+}
+
+/// Run `parser` and give its output with the position where it started and where it stopped
+///
+/// A parser consuming nothing gives the same position twice
+#[cfg(feature = "nom7")]
+pub fn with_span<T, X, C, O, E, F>(
+    mut parser: F,
+) -> impl FnMut(Spanned<T, X, C>) -> SpanResult<T, X, C, O, E>
+where
+    C: CounterStorage,
+    F: Parser<Spanned<T, X, C>, O, E>,
+{
+    move |input| {
+        let start = input.pos();
+        let (rest, output) = parser.parse(input)?;
+        let end = rest.pos();
+
+        Ok((rest, (output, start, end)))
+    }
+}
+
+/// Run `parser` and give its output with the position where it started and where it stopped
+///
+/// A parser consuming nothing gives the same position twice
+#[cfg(feature = "nom8")]
+pub fn with_span<T, X, C, O, E, F>(
+    mut parser: F,
+) -> impl FnMut(Spanned<T, X, C>) -> SpanResult<T, X, C, O, E>
+where
+    C: CounterStorage,
+    F: Parser<Spanned<T, X, C>, Output = O, Error = E>,
+{
+    move |input| {
+        let start = input.pos();
+        let (rest, output) = parser.parse(input)?;
+        let end = rest.pos();
+
+        Ok((rest, (output, start, end)))
+    }
+}
+
+/// Run `parser` and give the span of what it consumed, at the position where it started, with
+/// its output
+#[cfg(feature = "nom7")]
+pub fn consumed_span<T, X, C, O, E, F>(
+    mut parser: F,
+) -> impl FnMut(Spanned<T, X, C>) -> ConsumedResult<T, X, C, O, E>
+where
+    Spanned<T, X, C>: Take + Clone,
+    F: Parser<Spanned<T, X, C>, O, E>,
+{
+    move |input| {
+        let (rest, output) = parser.parse(input.clone())?;
+        let consumed = input.take(rest.offset - input.offset);
+
+        Ok((rest, (consumed, output)))
+    }
+}
+
+/// Run `parser` and give the span of what it consumed, at the position where it started, with
+/// its output
+#[cfg(feature = "nom8")]
+pub fn consumed_span<T, X, C, O, E, F>(
+    mut parser: F,
+) -> impl FnMut(Spanned<T, X, C>) -> ConsumedResult<T, X, C, O, E>
+where
+    Spanned<T, X, C>: Take + Clone,
+    F: Parser<Spanned<T, X, C>, Output = O, Error = E>,
+{
+    move |input| {
+        let (rest, output) = parser.parse(input.clone())?;
+        let consumed = input.take(rest.offset - input.offset);
+
+        Ok((rest, (consumed, output)))
+    }
+}
+
+/// Take `count` chars, where nom's `take` counts the bytes of a `&[u8]`
+///
+/// When UTF-8 isn't handled, the count is the one of nom's `take`: the chars of a `&str` and the
+/// bytes of a `&[u8]`
+///
+/// The invalid UTF-8 sequences count as the single replacement char they are decoded to, like
+/// for the columns
+///
+/// # Errors
+///
+/// Returns an [`ErrorKind::Eof`] error if there are less than `count` chars
+pub fn take_chars<T, X, C, E>(count: usize) -> impl Fn(Spanned<T, X, C>) -> TakeResult<T, X, C, E>
+where
+    T: AsBytes,
+    C: Encoding,
+    Spanned<T, X, C>: Take + SliceIndex,
+    E: ParseError<Spanned<T, X, C>>,
+{
+    move |input| match chars_end(&input, count) {
+        Some(end) => Ok(input.take_split(end)),
+        None => Err(Err::Error(E::from_error_kind(input, ErrorKind::Eof))),
+    }
+}
+
+/// Take `count` chars like [`take_chars`], asking for more input if there are less
+///
+/// # Errors
+///
+/// Returns [`Err::Incomplete`] if there are less than `count` chars
+pub fn take_chars_streaming<T, X, C, E>(
+    count: usize,
+) -> impl Fn(Spanned<T, X, C>) -> TakeResult<T, X, C, E>
+where
+    T: AsBytes,
+    C: Encoding,
+    Spanned<T, X, C>: Take + SliceIndex,
+    E: ParseError<Spanned<T, X, C>>,
+{
+    move |input| match chars_end(&input, count) {
+        Some(end) => Ok(input.take_split(end)),
+        None => Err(Err::Incomplete(Needed::Unknown)),
+    }
+}
+
+/// Get the end of the first `count` chars of `input`, counted by nom if UTF-8 isn't handled so a
+/// `&str` is never split inside a char
+fn chars_end<T, X, C>(input: &Spanned<T, X, C>, count: usize) -> Option<usize>
+where
+    T: AsBytes,
+    C: Encoding,
+    Spanned<T, X, C>: SliceIndex,
+{
+    if input.handle_utf8() {
+        utf8_chars_end(input.data.as_bytes(), count)
+    } else {
+        input.slice_index(count).ok()
+    }
+}
+
+/// Get the end of the first `count` chars of `bytes`, decoded as UTF-8
+fn utf8_chars_end(bytes: &[u8], count: usize) -> Option<usize> {
+    let mut remaining = count;
+    let mut end = 0;
+
+    for chunk in bytes.utf8_chunks() {
+        let valid = chunk.valid();
+
+        if let Some((i, _)) = valid.char_indices().nth(remaining) {
+            return Some(end + i);
+        }
+        remaining -= valid.chars().count();
+        end += valid.len();
+
+        if remaining == 0 {
+            return Some(end);
+        }
+        if !chunk.invalid().is_empty() {
+            remaining -= 1;
+            end += chunk.invalid().len();
+        }
+    }
+
+    (remaining == 0).then_some(end)
+}
@@ -0,0 +1,125 @@
+use encoding::Dynamic;
+use position::Position;
+use tracker::PositionTracker;
+use Spanned;
+
+/// A cursor advancing over a span in place, for hand-written lexers
+///
+/// The cursor is located like the spans sliced at each step, and [`SpannedCursor::rest`] gives the
+/// span to go on with nom parsers, the result going back in the cursor with
+/// [`SpannedCursor::set_rest`]
+#[derive(Clone, Debug)]
+pub struct SpannedCursor<'a, X = (), C = Dynamic> {
+    rest: Spanned<&'a str, X, C>,
+}
+
+impl<'a, X, C> SpannedCursor<'a, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+    #[must_use]
+    pub fn new(span: Spanned<&'a str, X, C>) -> Self {
+        Self { rest: span }
+    }
+
+    /// Get the span of what wasn't consumed yet
+    #[must_use]
+    pub fn rest(&self) -> Spanned<&'a str, X, C> {
+        self.rest.clone()
+    }
+
+    /// Get the span of what wasn't consumed yet, dropping the cursor
+    #[must_use]
+    pub fn into_rest(self) -> Spanned<&'a str, X, C> {
+        self.rest
+    }
+
+    /// Move the cursor to `rest`, like the rest given by a parser
+    pub fn set_rest(&mut self, rest: Spanned<&'a str, X, C>) {
+        self.rest = rest;
+    }
+
+    /// Get the position of the cursor
+    #[must_use]
+    pub fn pos(&self) -> Position {
+        self.rest.pos()
+    }
+
+    /// Check if everything is consumed
+    #[must_use]
+    pub fn is_eof(&self) -> bool {
+        self.rest.data.is_empty()
+    }
+
+    /// Get the next char without consuming it
+    #[must_use]
+    pub fn peek(&self) -> Option<char> {
+        self.rest.data.chars().next()
+    }
+
+    /// Get the char after the next one without consuming anything
+    #[must_use]
+    pub fn peek_second(&self) -> Option<char> {
+        self.rest.data.chars().nth(1)
+    }
+
+    /// Consume the next char
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.consume(c.len_utf8());
+
+        Some(c)
+    }
+
+    /// Consume the next char if it's `c`
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.consume(c.len_utf8());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume `tag` if the rest starts with it
+    pub fn eat_str(&mut self, tag: &str) -> bool {
+        if self.rest.data.starts_with(tag) {
+            self.consume(tag.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the chars matching `predicate`, giving the span of what was consumed
+    pub fn eat_while<P>(&mut self, mut predicate: P) -> Spanned<&'a str, X, C>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let data = self.rest.data;
+        let end = data.find(|c| !predicate(c)).unwrap_or(data.len());
+
+        self.consume(end)
+    }
+
+    /// Consume the first `len` bytes, giving the span of what was consumed
+    fn consume(&mut self, len: usize) -> Spanned<&'a str, X, C> {
+        let (consumed, rest) = self.rest.data.split_at(len);
+        let consumed_span = self.rest.respan(consumed);
+
+        self.rest = self.rest.advance_to(consumed.as_bytes(), rest);
+
+        consumed_span
+    }
+}
+
+impl<'a, X, C> From<Spanned<&'a str, X, C>> for SpannedCursor<'a, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+    fn from(span: Spanned<&'a str, X, C>) -> Self {
+        Self::new(span)
+    }
+}
@@ -0,0 +1,210 @@
+use std::num::NonZeroU8;
+use tracker::{CounterStorage, KeepCounters, KeptCounters};
+
+/// Strategy used by [`Spanned`](crate::Spanned) to count columns
+///
+/// [`Utf8`] and [`Ascii`] fix the strategy at compile time so the branch in `slice` is optimized
+/// away and the struct doesn't need to store anything, while [`Dynamic`] keeps the flag at
+/// runtime for [`Spanned::new`](crate::Spanned::new)
+///
+/// Every encoding is a [`PositionTracker`](crate::PositionTracker) counting lines of text
+pub trait Encoding: Copy + CounterStorage {
+    /// Whether the input is decoded as UTF-8 rather than counted in bytes
+    ///
+    /// Invalid UTF-8 is decoded like [`String::from_utf8_lossy`] does, every invalid sequence
+    /// counting as the single replacement char it becomes. A char cut by a slice counts where it
+    /// starts, so the position is the same whichever way the input is sliced
+    fn handle_utf8(&self) -> bool;
+
+    /// The unit the columns are counted in. By default it's [`ColumnUnit::Chars`] when UTF-8 is
+    /// handled and [`ColumnUnit::Bytes`] otherwise
+    fn column_unit(&self) -> ColumnUnit {
+        if self.handle_utf8() {
+            ColumnUnit::Chars
+        } else {
+            ColumnUnit::Bytes
+        }
+    }
+
+    /// The tab stop used to expand `\t` when counting the columns. No expansion is done by
+    /// default, so a tab is counted as a single column
+    fn tab_width(&self) -> Option<NonZeroU8> {
+        None
+    }
+
+    /// Whether `\r\n` and a lone `\r` are line endings. By default only `\n` is, so the `\r` of
+    /// `\r\n` is counted as a column
+    fn crlf(&self) -> bool {
+        false
+    }
+
+    /// Whether U+2028 LINE SEPARATOR, U+2029 PARAGRAPH SEPARATOR and the form feed `\x0C` are line
+    /// endings too. It's only taken into account when UTF-8 is handled
+    fn unicode_newlines(&self) -> bool {
+        false
+    }
+
+    /// The byte ending the lines in place of `\n`, which is useful to count records separated by
+    /// something else, like NUL or the `\x1e` of JSON text sequences
+    fn line_separator(&self) -> u8 {
+        b'\n'
+    }
+}
+
+/// What a column is made of
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ColumnUnit {
+    /// Every byte is a column
+    Bytes,
+    /// Every UTF-8 char is a column
+    Chars,
+    /// Every UTF-16 code unit is a column, so chars outside of the BMP take 2 columns. This is
+    /// what the Language Server Protocol uses by default
+    Utf16,
+    /// Every extended grapheme cluster, which is what a user perceives as a char, is a column
+    ///
+    /// A cluster is counted once its end is consumed, knowing the char coming after it. The rules
+    /// looking further behind, for emoji ZWJ sequences and flags, are only applied to what is
+    /// consumed at once, so consuming such a cluster one char at a time may count more columns
+    #[cfg(feature = "unicode-segmentation")]
+    Graphemes,
+    /// Every char takes as many columns as the cells it's displayed on in a terminal, so wide
+    /// chars like CJK ones take 2 columns and zero width chars take none. Control chars take 1
+    /// column, pair it with a tab width to expand the tabs
+    #[cfg(feature = "unicode-width")]
+    Width,
+}
+
+/// Count the columns in UTF-8 chars, decided at compile time
+///
+/// Nothing but the line and the column is kept, so the spans have no char offset, no byte column
+/// and no [`SourceId`](crate::SourceId). Without knowing whether the previous slice cut a char,
+/// the continuation bytes starting a slice of bytes are taken as the end of a cut char
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub struct Utf8;
+
+/// Count the columns in bytes, decided at compile time
+///
+/// Nothing but the line and the column is kept, so the spans have no char offset, no byte column
+/// and no [`SourceId`](crate::SourceId)
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub struct Ascii;
+
+/// Count the columns in UTF-8 chars or in bytes depending on a flag given at runtime
+///
+/// This is also where the options of [`SpannedBuilder`](crate::SpannedBuilder) are stored
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct Dynamic {
+    column_unit: ColumnUnit,
+    tab_width: Option<NonZeroU8>,
+    crlf: bool,
+    unicode_newlines: bool,
+    line_separator: u8,
+    kept: KeptCounters,
+}
+
+/// UTF-8 is handled by default
+impl Default for Dynamic {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl Dynamic {
+    pub(crate) fn new(handle_utf8: bool) -> Self {
+        Self {
+            column_unit: if handle_utf8 {
+                ColumnUnit::Chars
+            } else {
+                ColumnUnit::Bytes
+            },
+            tab_width: None,
+            crlf: false,
+            unicode_newlines: false,
+            line_separator: b'\n',
+            kept: KeptCounters::default(),
+        }
+    }
+
+    /// Keep the column unit if it already requires UTF-8
+    pub(crate) fn set_utf8(&mut self, handle_utf8: bool) {
+        if !handle_utf8 {
+            self.column_unit = ColumnUnit::Bytes;
+        } else if self.column_unit == ColumnUnit::Bytes {
+            self.column_unit = ColumnUnit::Chars;
+        }
+    }
+
+    pub(crate) fn set_column_unit(&mut self, column_unit: ColumnUnit) {
+        self.column_unit = column_unit;
+    }
+
+    pub(crate) fn set_tab_width(&mut self, tab_width: u8) {
+        self.tab_width = NonZeroU8::new(tab_width);
+    }
+
+    pub(crate) fn set_crlf(&mut self, crlf: bool) {
+        self.crlf = crlf;
+    }
+
+    pub(crate) fn set_unicode_newlines(&mut self, unicode_newlines: bool) {
+        self.unicode_newlines = unicode_newlines;
+    }
+
+    pub(crate) fn set_line_separator(&mut self, line_separator: u8) {
+        self.line_separator = line_separator;
+    }
+}
+
+impl KeepCounters for Dynamic {
+    fn kept(&self) -> &KeptCounters {
+        &self.kept
+    }
+
+    fn kept_mut(&mut self) -> &mut KeptCounters {
+        &mut self.kept
+    }
+}
+
+impl CounterStorage for Utf8 {}
+
+impl CounterStorage for Ascii {}
+
+impl Encoding for Utf8 {
+    fn handle_utf8(&self) -> bool {
+        true
+    }
+}
+
+impl Encoding for Ascii {
+    fn handle_utf8(&self) -> bool {
+        false
+    }
+}
+
+impl Encoding for Dynamic {
+    fn handle_utf8(&self) -> bool {
+        self.column_unit != ColumnUnit::Bytes
+    }
+
+    fn column_unit(&self) -> ColumnUnit {
+        self.column_unit
+    }
+
+    fn tab_width(&self) -> Option<NonZeroU8> {
+        self.tab_width
+    }
+
+    fn crlf(&self) -> bool {
+        self.crlf
+    }
+
+    fn unicode_newlines(&self) -> bool {
+        self.unicode_newlines
+    }
+
+    fn line_separator(&self) -> u8 {
+        self.line_separator
+    }
+}
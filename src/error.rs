@@ -0,0 +1,212 @@
+use nom::{
+    error::{ContextError, Error as NomError, ErrorKind, FromExternalError, ParseError},
+    AsBytes,
+};
+use position::Position;
+use std::{error::Error, fmt, sync::Arc};
+use tracker::CounterStorage;
+use Spanned;
+
+/// The number of chars kept of the rest by the [`Excerpt`] of a `String`
+const EXCERPT_CHARS: usize = 40;
+
+/// An error keeping the position where the parser failed instead of the rest of the input, so it
+/// doesn't borrow the input and can be returned up the stack cheaply
+///
+/// `E` is what's kept of the rest, nothing by default, or its first 40 chars with a `String`. The
+/// contexts of `nom::error::context` are kept with their position, without allocating when there's
+/// none, and so is the error of the function given to `map_res`
+#[derive(Clone, Debug)]
+pub struct SpannedError<E = ()> {
+    /// The position where the parser failed
+    pub position: Position,
+    /// The parser that failed
+    pub kind: ErrorKind,
+    /// What's kept of the rest where the parser failed
+    pub excerpt: E,
+    /// The contexts the parser was in, given by `nom::error::context`, the innermost first, each
+    /// one at the position where it started
+    pub contexts: Vec<(Position, &'static str)>,
+    /// The error of the function given to `map_res`, which is also the source of this error
+    pub external: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl<E> SpannedError<E> {
+    #[must_use]
+    pub fn new(position: Position, kind: ErrorKind, excerpt: E) -> Self {
+        Self {
+            position,
+            kind,
+            excerpt,
+            contexts: Vec::new(),
+            external: None,
+        }
+    }
+}
+
+/// Compare the errors, the errors of `map_res` being compared on their message
+impl<E> PartialEq for SpannedError<E>
+where
+    E: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position
+            && self.kind == other.kind
+            && self.excerpt == other.excerpt
+            && self.contexts == other.contexts
+            && self.external.as_ref().map(ToString::to_string)
+                == other.external.as_ref().map(ToString::to_string)
+    }
+}
+
+impl<E> Eq for SpannedError<E> where E: Eq {}
+
+impl SpannedError<String> {
+    /// Make an error not borrowing the input from a nom error, keeping the first `excerpt_chars`
+    /// chars of its rest
+    #[must_use]
+    pub fn from_nom_error<T, X, C>(error: &NomError<Spanned<T, X, C>>, excerpt_chars: usize) -> Self
+    where
+        T: AsBytes,
+        C: CounterStorage,
+    {
+        Self::new(
+            error.input.pos(),
+            error.code,
+            excerpt(error.input.data.as_bytes(), excerpt_chars),
+        )
+    }
+}
+
+/// Detach a nom error from the input, the excerpt being made from its rest
+impl<T, X, C, E> From<NomError<Spanned<T, X, C>>> for SpannedError<E>
+where
+    T: AsBytes,
+    C: CounterStorage,
+    E: Excerpt,
+{
+    fn from(error: NomError<Spanned<T, X, C>>) -> Self {
+        Self::from_error_kind(error.input, error.code)
+    }
+}
+
+/// What a [`SpannedError`] keeps of the rest where the parser failed
+pub trait Excerpt {
+    /// Make the excerpt of `rest`, the bytes of the data of the span where the parser failed
+    fn excerpt(rest: &[u8]) -> Self;
+
+    /// Get the text of the excerpt shown by the errors, if there's one
+    fn text(&self) -> Option<&str>;
+}
+
+/// Keep nothing of the rest
+impl Excerpt for () {
+    fn excerpt(_: &[u8]) -> Self {}
+
+    fn text(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Keep the first 40 chars of the rest, the invalid UTF-8 being replaced
+impl Excerpt for String {
+    fn excerpt(rest: &[u8]) -> Self {
+        excerpt(rest, EXCERPT_CHARS)
+    }
+
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+/// Get the first `chars` chars of `rest`, the invalid UTF-8 being replaced
+fn excerpt(rest: &[u8], chars: usize) -> String {
+    // A char takes at most 4 bytes
+    let start = &rest[..rest.len().min(chars.saturating_mul(4))];
+
+    String::from_utf8_lossy(start).chars().take(chars).collect()
+}
+
+impl<T, X, C, E> ParseError<Spanned<T, X, C>> for SpannedError<E>
+where
+    T: AsBytes,
+    C: CounterStorage,
+    E: Excerpt,
+{
+    fn from_error_kind(input: Spanned<T, X, C>, kind: ErrorKind) -> Self {
+        Self::new(input.pos(), kind, E::excerpt(input.data.as_bytes()))
+    }
+
+    fn append(_: Spanned<T, X, C>, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<T, X, C, E> ContextError<Spanned<T, X, C>> for SpannedError<E>
+where
+    C: CounterStorage,
+{
+    fn add_context(input: Spanned<T, X, C>, context: &'static str, mut other: Self) -> Self {
+        other.contexts.push((input.pos(), context));
+        other
+    }
+}
+
+/// Keep the error of the function given to `map_res`, which must be a `std::error::Error`
+impl<T, X, C, E, F> FromExternalError<Spanned<T, X, C>, F> for SpannedError<E>
+where
+    T: AsBytes,
+    C: CounterStorage,
+    E: Excerpt,
+    F: Error + Send + Sync + 'static,
+{
+    fn from_external_error(input: Spanned<T, X, C>, kind: ErrorKind, error: F) -> Self {
+        Self {
+            external: Some(Arc::new(error)),
+            ..Self::from_error_kind(input, kind)
+        }
+    }
+}
+
+/// Display the error as `expected Tag at line 3, column 14`, followed by the excerpt when there's
+/// one, then a line `in <context> at line 3, column 10` for every context, the innermost first.
+/// The error of `map_res` replaces the `expected Tag`
+impl<E> fmt::Display for SpannedError<E>
+where
+    E: Excerpt,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.external {
+            Some(external) => write!(f, "{} at {:#}", external, self.position)?,
+            None => write!(
+                f,
+                "expected {} at {:#}",
+                self.kind.description(),
+                self.position
+            )?,
+        }
+
+        match self.excerpt.text() {
+            Some("") => write!(f, ", found the end of the input")?,
+            Some(text) => write!(f, ", found {text:?}")?,
+            None => {}
+        }
+
+        for (position, context) in &self.contexts {
+            write!(f, "\n    in {context} at {position:#}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E> Error for SpannedError<E>
+where
+    E: Excerpt + fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.external
+            .as_ref()
+            .map(|external| &**external as &(dyn Error + 'static))
+    }
+}
@@ -0,0 +1,125 @@
+use encoding::Encoding;
+use memchr::{memchr, memmem, Memchr};
+use nom::AsBytes;
+use position::Position;
+use tracker::PositionTracker;
+use Spanned;
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    C: PositionTracker,
+{
+    /// Get the position `len` bytes after the current one, `data` being the bytes of the data
+    fn position_at_byte(&self, data: &[u8], len: usize) -> Position {
+        let (consumed, rest) = data.split_at(len);
+        let (counters, _) = self.advance(consumed, rest);
+
+        Position::new(counters.line, counters.col, self.offset + len).with_source(self.source_id())
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: AsBytes,
+    C: PositionTracker,
+{
+    /// Get the position `rel_offset` bytes after the current one without slicing, counting only
+    /// what comes before it. The offset is clamped to the end of the data
+    #[must_use]
+    pub fn position_at(&self, rel_offset: usize) -> Position {
+        let data = self.data.as_bytes();
+
+        self.position_at_byte(data, rel_offset.min(data.len()))
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: AsBytes,
+    C: Encoding,
+{
+    /// Get the offset, relative to the current position, of the column `col` of the line `line`,
+    /// which are counted like in [`Spanned::pos`]
+    ///
+    /// The lines are found by their separator, `\n` by default, so a lone `\r` or a Unicode
+    /// newline doesn't start one. It's `None` for a position that isn't in the data, like a
+    /// line before the current one or after the last one, or a column past the end of the line
+    #[must_use]
+    pub fn offset_of(&self, line: usize, col: usize) -> Option<usize> {
+        let data = self.data.as_bytes();
+        let separator = self.tracker.line_separator();
+
+        let line_start = match line.checked_sub(self.line())? {
+            0 => 0,
+            lines => Memchr::new(separator, data).nth(lines - 1)? + 1,
+        };
+        let line_end =
+            memchr(separator, &data[line_start..]).map_or(data.len(), |end| line_start + end);
+
+        let (mut counters, mut tracker) = self.advance(&data[..line_start], &data[line_start..]);
+        let mut offset = line_start;
+
+        while counters.col < col && offset < line_end {
+            let mut next = offset + 1;
+            if tracker.handle_utf8() {
+                // Step over whole chars, a cut char being counted where it starts
+                while next < line_end && data[next] & 0xC0 == 0x80 {
+                    next += 1;
+                }
+            }
+
+            tracker.advance(&mut counters, &data[offset..next], &data[next..]);
+            offset = next;
+        }
+
+        if counters.col == col {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+}
+
+impl<X, C> Spanned<&str, X, C>
+where
+    C: PositionTracker,
+{
+    /// Get the position of the first occurrence of `needle` in the data, counting only what comes
+    /// before it
+    #[must_use]
+    pub fn find_substring_position(&self, needle: &str) -> Option<Position> {
+        let index = self.data.find(needle)?;
+
+        Some(self.position_at_byte(self.data.as_bytes(), index))
+    }
+
+    /// Get the position of the first `c` in the data, counting only what comes before it
+    #[must_use]
+    pub fn find_char_position(&self, c: char) -> Option<Position> {
+        let index = self.data.find(c)?;
+
+        Some(self.position_at_byte(self.data.as_bytes(), index))
+    }
+}
+
+impl<X, C> Spanned<&[u8], X, C>
+where
+    C: PositionTracker,
+{
+    /// Get the position of the first occurrence of `needle` in the data, counting only what comes
+    /// before it
+    #[must_use]
+    pub fn find_substring_position(&self, needle: &[u8]) -> Option<Position> {
+        let index = memmem::find(self.data, needle)?;
+
+        Some(self.position_at_byte(self.data, index))
+    }
+
+    /// Get the position of the first `byte` in the data, counting only what comes before it
+    #[must_use]
+    pub fn find_byte_position(&self, byte: u8) -> Option<Position> {
+        let index = memchr(byte, self.data)?;
+
+        Some(self.position_at_byte(self.data, index))
+    }
+}
@@ -0,0 +1,181 @@
+use error::SpannedError;
+use nom::{
+    error::{Error as NomError, ErrorKind},
+    AsBytes, Err, IResult, Needed,
+};
+use position::Position;
+use std::{error::Error, fmt};
+use tracker::CounterStorage;
+use Spanned;
+
+/// An error knowing the position where the parser failed, for [`FinishWithPosition`]
+pub trait ErrorPosition {
+    /// Get the position where the parser failed
+    fn error_position(&self) -> Position;
+}
+
+impl<T, X, C> ErrorPosition for NomError<Spanned<T, X, C>>
+where
+    C: CounterStorage,
+{
+    fn error_position(&self) -> Position {
+        self.input.pos()
+    }
+}
+
+impl<T, X, C> ErrorPosition for (Spanned<T, X, C>, ErrorKind)
+where
+    C: CounterStorage,
+{
+    fn error_position(&self) -> Position {
+        self.0.pos()
+    }
+}
+
+impl<E> ErrorPosition for SpannedError<E> {
+    fn error_position(&self) -> Position {
+        self.position
+    }
+}
+
+/// Why a parser didn't give a result, with the position where it failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LocatedFailure<E> {
+    /// A recoverable error, from `Err::Error`
+    Error { position: Position, error: E },
+    /// An unrecoverable error, from `Err::Failure`
+    Failure { position: Position, error: E },
+    /// More data is needed, from `Err::Incomplete`
+    Incomplete(Needed),
+}
+
+impl<E> LocatedFailure<E> {
+    /// Get the position where the parser failed, which is unknown when more data is needed
+    #[must_use]
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Error { position, .. } | Self::Failure { position, .. } => Some(*position),
+            Self::Incomplete(_) => None,
+        }
+    }
+
+    /// Get the error of the parser, if it failed
+    #[must_use]
+    pub fn error(&self) -> Option<&E> {
+        match self {
+            Self::Error { error, .. } | Self::Failure { error, .. } => Some(error),
+            Self::Incomplete(_) => None,
+        }
+    }
+
+    /// Get the error of the parser, if it failed
+    #[must_use]
+    pub fn into_error(self) -> Option<E> {
+        match self {
+            Self::Error { error, .. } | Self::Failure { error, .. } => Some(error),
+            Self::Incomplete(_) => None,
+        }
+    }
+
+    /// Check if the error is recoverable, like the errors `alt` tries the next parser after
+    #[must_use]
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Self::Error { .. })
+    }
+
+    /// Check if more data is needed
+    #[must_use]
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, Self::Incomplete(_))
+    }
+
+    /// Map the error of the parser, keeping its position
+    pub fn map_error<F, M>(self, f: M) -> LocatedFailure<F>
+    where
+        M: FnOnce(E) -> F,
+    {
+        match self {
+            Self::Error { position, error } => LocatedFailure::Error {
+                position,
+                error: f(error),
+            },
+            Self::Failure { position, error } => LocatedFailure::Failure {
+                position,
+                error: f(error),
+            },
+            Self::Incomplete(needed) => LocatedFailure::Incomplete(needed),
+        }
+    }
+}
+
+impl<T, X, C> LocatedFailure<NomError<Spanned<T, X, C>>>
+where
+    T: AsBytes,
+    C: CounterStorage,
+{
+    /// Detach the error from the input, the rest where the parser failed being replaced by its
+    /// first `excerpt_chars` chars, like with [`SpannedError::from_nom_error`]
+    #[must_use]
+    pub fn into_owned(self, excerpt_chars: usize) -> LocatedFailure<SpannedError<String>> {
+        self.map_error(|error| SpannedError::from_nom_error(&error, excerpt_chars))
+    }
+}
+
+/// Display the error as `line:col: <error>`, or say how much data is needed
+impl<E> fmt::Display for LocatedFailure<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error { position, error } | Self::Failure { position, error } => {
+                write!(f, "{position}: {error}")
+            }
+            Self::Incomplete(Needed::Size(size)) => {
+                write!(f, "incomplete input, {size} more bytes needed")
+            }
+            Self::Incomplete(Needed::Unknown) => write!(f, "incomplete input"),
+        }
+    }
+}
+
+impl<E> Error for LocatedFailure<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error().map(|error| error as &(dyn Error + 'static))
+    }
+}
+
+/// Turn the result of a parser into a `Result`, the error being located
+pub trait FinishWithPosition<O, E> {
+    /// Get the output of the parser, dropping the rest, or why it didn't give one with the
+    /// position where it failed
+    ///
+    /// # Errors
+    ///
+    /// The error of the parser, with its position and whether it was recoverable, or
+    /// [`LocatedFailure::Incomplete`] if more data is needed
+    fn finish_with_position(self) -> Result<O, LocatedFailure<E>>;
+}
+
+impl<I, O, E> FinishWithPosition<O, E> for IResult<I, O, E>
+where
+    E: ErrorPosition,
+{
+    fn finish_with_position(self) -> Result<O, LocatedFailure<E>> {
+        match self {
+            Ok((_, output)) => Ok(output),
+            Err(Err::Error(error)) => Err(LocatedFailure::Error {
+                position: error.error_position(),
+                error,
+            }),
+            Err(Err::Failure(error)) => Err(LocatedFailure::Failure {
+                position: error.error_position(),
+                error,
+            }),
+            Err(Err::Incomplete(needed)) => Err(LocatedFailure::Incomplete(needed)),
+        }
+    }
+}
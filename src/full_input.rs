@@ -0,0 +1,77 @@
+use encoding::Dynamic;
+use tracker::{KeepCounters, PositionTracker};
+use Spanned;
+
+/// The whole input, kept as the extra data of the spans made by [`Spanned::with_full_input`] to
+/// look behind the current position
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub struct FullInput<T>(T);
+
+impl<T> FullInput<T> {
+    /// Get the whole input
+    #[must_use]
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+}
+
+/// A span keeping the whole input, see [`Spanned::with_full_input`]
+pub type FullSpanned<T, C = Dynamic> = Spanned<T, FullInput<T>, C>;
+
+impl<T> Spanned<T, FullInput<T>>
+where
+    T: Clone,
+{
+    /// Create a span keeping the whole input, copied in every span obtained by slicing this one,
+    /// so what was consumed can be looked at. For a `&str` it costs the size of a `&str`
+    ///
+    /// The whole input is the extra data of the span, so it's only meaningful for the spans
+    /// obtained by slicing this one, and not after a `respan` or a `map_extra`
+    pub fn with_full_input(data: T, handle_utf8: bool) -> Self {
+        Self::new_extra(data.clone(), handle_utf8, FullInput(data))
+    }
+}
+
+impl<T, C> Spanned<T, FullInput<T>, C> {
+    /// Get the whole input, before any slicing
+    pub fn full_input(&self) -> &T {
+        self.extra().get()
+    }
+}
+
+macro_rules! impl_consumed {
+    ($($fragment:ty),*) => {
+        $(
+            impl<'a, C> Spanned<$fragment, FullInput<$fragment>, C> {
+                /// Get everything before the current position, which is `byte_offset()` bytes
+                /// long
+                pub fn consumed(&self) -> $fragment {
+                    &self.full_input()[..self.offset]
+                }
+            }
+        )*
+    };
+}
+
+impl_consumed!(&'a str, &'a [u8]);
+
+impl<'a, C> Spanned<&'a str, FullInput<&'a str>, C>
+where
+    C: PositionTracker + KeepCounters,
+{
+    /// Get the whole text of the current line, like [`Spanned::current_line_text`] with the
+    /// whole input
+    pub fn line_text(&self) -> &'a str {
+        self.current_line_text(self.full_input())
+    }
+
+    /// Get the text of the `count` lines before the current one, each one without its `\n` or
+    /// `\r\n`, the closest last. There are less lines when the current line is one of the first
+    pub fn lines_before(&self, count: usize) -> Vec<&'a str> {
+        let before = &self.full_input()[..self.line_start_offset()];
+        let mut lines: Vec<&'a str> = before.lines().rev().take(count).collect();
+        lines.reverse();
+
+        lines
+    }
+}
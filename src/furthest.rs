@@ -0,0 +1,91 @@
+use finish::ErrorPosition;
+use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
+use position::Position;
+use std::{error::Error, fmt};
+
+/// An error keeping, when all the branches of an `alt` failed, the error of the branch that went
+/// the furthest in the input, and not the one of the last branch
+///
+/// The errors are compared on the byte offset of their [`ErrorPosition`], the last one being kept
+/// when they failed at the same place, like without `Furthest`. It works on any error knowing
+/// its position, like `Furthest<SpannedError>` or `Furthest<nom::error::Error<Spanned<&str>>>`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Furthest<E>(pub E);
+
+impl<E> Furthest<E> {
+    /// Get the error
+    #[must_use]
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<I, E> ParseError<I> for Furthest<E>
+where
+    E: ParseError<I> + ErrorPosition,
+{
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self(E::from_error_kind(input, kind))
+    }
+
+    fn append(input: I, kind: ErrorKind, other: Self) -> Self {
+        Self(E::append(input, kind, other.0))
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Self(E::from_char(input, c))
+    }
+
+    fn or(self, other: Self) -> Self {
+        if self.0.error_position().offset > other.0.error_position().offset {
+            self
+        } else {
+            Self(self.0.or(other.0))
+        }
+    }
+}
+
+impl<I, E> ContextError<I> for Furthest<E>
+where
+    E: ContextError<I>,
+{
+    fn add_context(input: I, context: &'static str, other: Self) -> Self {
+        Self(E::add_context(input, context, other.0))
+    }
+}
+
+impl<I, E, F> FromExternalError<I, F> for Furthest<E>
+where
+    E: FromExternalError<I, F>,
+{
+    fn from_external_error(input: I, kind: ErrorKind, error: F) -> Self {
+        Self(E::from_external_error(input, kind, error))
+    }
+}
+
+impl<E> ErrorPosition for Furthest<E>
+where
+    E: ErrorPosition,
+{
+    fn error_position(&self) -> Position {
+        self.0.error_position()
+    }
+}
+
+impl<E> fmt::Display for Furthest<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E> Error for Furthest<E>
+where
+    E: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
@@ -0,0 +1,42 @@
+use bytecount::num_chars;
+use std::str;
+use tracker::count_utf8;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Count the grapheme clusters ending in `bytes`, `next` being what comes after them
+///
+/// Invalid UTF-8 is counted in chars, as it can't be segmented
+pub(crate) fn count(bytes: &[u8], next: &[u8]) -> usize {
+    let Ok(text) = str::from_utf8(bytes) else {
+        return count_utf8(bytes, num_chars);
+    };
+
+    let mut graphemes = text.graphemes(true);
+    let count = graphemes.clone().count();
+
+    let (Some(last), Some(next)) = (graphemes.next_back(), first_char(next)) else {
+        return count;
+    };
+
+    // The last cluster is only counted if it doesn't go on after `bytes`
+    let mut joined = String::with_capacity(last.len() + next.len_utf8());
+    joined.push_str(last);
+    joined.push(next);
+
+    if joined.graphemes(true).count() == 1 {
+        count - 1
+    } else {
+        count
+    }
+}
+
+fn first_char(bytes: &[u8]) -> Option<char> {
+    let bytes = &bytes[..bytes.len().min(4)];
+
+    let text = match str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => str::from_utf8(&bytes[..e.valid_up_to()]).ok()?,
+    };
+
+    text.chars().next()
+}
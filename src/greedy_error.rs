@@ -0,0 +1,19 @@
+use nom_greedyerror::{AsStr, Position};
+use Spanned;
+
+/// `GreedyError` keeps the error that went the furthest in the input, compared on the byte offset
+impl<T, X, C> Position for Spanned<T, X, C> {
+    fn position(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Lets `nom_greedyerror::convert_error` locate the errors in the input
+impl<T, X, C> AsStr for Spanned<T, X, C>
+where
+    T: AsStr,
+{
+    fn as_str(&self) -> &str {
+        self.data.as_str()
+    }
+}
@@ -51,6 +51,494 @@
 //!
 //! ```
 //!
+//! ### Choosing the encoding at compile time
+//!
+//! If you always know which encoding you're dealing with, you can pick it at the type level with `Spanned::utf8` or `Spanned::ascii`. The column counting strategy is then resolved at compile time and the span is a bit smaller:
+//!
+//! ```ignore
+//! use nom_span::{Ascii, Spanned, Utf8};
+//!
+//! type Utf8Span<'a> = Spanned<&'a str, (), Utf8>;
+//! type AsciiSpan<'a> = Spanned<&'a str, (), Ascii>;
+//!
+//! fn main() {
+//!     let utf8: Utf8Span = Spanned::utf8("🙌");
+//!     let ascii: AsciiSpan = Spanned::ascii("🙌");
+//! }
+//! ```
+//!
+//! ### Counting something else than lines
+//!
+//! The way lines and columns are counted can be replaced by implementing `PositionTracker`. For example `RecordTracker` counts length prefixed records, the line being the number of the record and the column the byte in it:
+//!
+//! ```ignore
+//! use nom_span::{RecordTracker, Spanned};
+//!
+//! type Records<'a> = Spanned<&'a [u8], (), RecordTracker>;
+//!
+//! fn main() {
+//!     // Records prefixed with their length on 2 bytes
+//!     let records: Records = Spanned::with_tracker(&b"\x00\x03abc\x00\x01d"[..], RecordTracker::new(2));
+//! }
+//! ```
+//!
+//! ### Parsing tokens
+//!
+//! A span can also wrap the tokens of a lexer, so the parser errors are still located in the source. The tokens give their position with `TokenPosition`, and the span is at the position of the next token:
+//!
+//! ```ignore
+//! use nom_span::{Spanned, TokenPosition, TokenTracker};
+//!
+//! struct Token {
+//!     line: usize,
+//!     col: usize,
+//! }
+//!
+//! impl TokenPosition for Token {
+//!     fn line(&self) -> usize {
+//!         self.line
+//!     }
+//!
+//!     fn col(&self) -> usize {
+//!         self.col
+//!     }
+//! }
+//!
+//! type Tokens<'a> = Spanned<&'a [Token], (), TokenTracker>;
+//!
+//! fn main() {
+//!     let tokens = [Token { line: 1, col: 1 }, Token { line: 2, col: 5 }];
+//!     let span: Tokens = Spanned::from_tokens(&tokens);
+//! }
+//! ```
+//!
+//! ### Getting the position in a sequence
+//!
+//! Like with [nom_locate](https://github.com/fflorent/nom_locate), `position` gives the current position as an empty span without consuming anything, so the start of a token can be kept in a tuple of parsers:
+//!
+//! ```ignore
+//! use nom::{bytes::complete::tag, sequence::tuple, IResult};
+//! use nom_span::{position, Spanned};
+//!
+//! type Span<'a> = Spanned<&'a str>;
+//!
+//! fn keyword(input: Span) -> IResult<Span, (Span, Span)> {
+//!     let (input, (_, pos, keyword)) = tuple((tag(" "), position, tag("let")))(input)?;
+//!
+//!     Ok((input, (pos, keyword)))
+//! }
+//! ```
+//!
+//! To get both ends of what a parser recognized, `with_span(parser)` gives its output with the position where it started and the position where it stopped, and `consumed_span(parser)` gives the span of what it consumed with its output.
+//!
+//! With nom 7, `SpannedParserExt` gives them as methods of the parsers: `parser.spanned()` gives the output in a `WithSpan` with both positions, and `parser.map_with_span(f)` maps the output with the span of what was consumed.
+//!
+//! To keep the location of the nodes of an AST without the input, `Span` holds a start and an end `Position`. It's made with `Span::of(&fragment)` or `Span::between(&before, &after)`, and is serializable with the `serde` feature.
+//!
+//! ### Keeping spans after parsing
+//!
+//! A `Spanned<&str>` borrows the input, so it can't outlive it. The input can be shared with an `Arc<str>` instead: a `Spanned<ArcStr>` owns a pointer to it and the range it covers, so the results and the errors can be kept or sent to another thread once the input is gone. `RcStr` does the same with an `Rc<str>`:
+//!
+//! ```ignore
+//! use nom_span::{ArcStr, Spanned};
+//! use std::sync::Arc;
+//!
+//! type Span = Spanned<ArcStr>;
+//!
+//! fn main() {
+//!     let span = Span::new(ArcStr::from(Arc::from("hello world")), true);
+//! }
+//! ```
+//!
+//! An input normalized into a `Cow<str>` is parsed the same way as a `Spanned<CowStr>`, converted from a `Spanned<Cow<str>>` keeping its position. A borrowed `Cow` stays borrowed, and an owned one is moved behind an `Arc` once so that slicing it doesn't copy it.
+//!
+//! ### Looking behind the position
+//!
+//! A span only holds what wasn't consumed yet. `Spanned::with_full_input` makes a `FullSpanned`, which also keeps the whole input as its extra data, so `consumed()` gives everything before the current position, and `line_text()` and `lines_before(n)` give the current line and the ones before it:
+//!
+//! ```ignore
+//! use nom_span::FullSpanned;
+//!
+//! fn main() {
+//!     let span = FullSpanned::with_full_input("let a = 1;\nlet b = ?;", true);
+//! }
+//! ```
+//! ### Parsing several files
+//!
+//! A span can be given the `SourceId` of its input, copied in every span obtained by slicing it and in their positions. The positions and the spans of different sources are never equal, they are ordered by source first, and the source is shown when they're displayed:
+//!
+//! ```ignore
+//! use nom_span::{SourceId, Spanned};
+//!
+//! fn main() {
+//!     let span = Spanned::new("let a = 1;", true).with_source_id(SourceId(1));
+//!
+//!     // #1:1:1
+//!     println!("{}", span.pos());
+//! }
+//! ```
+//!
+//! ### Reading files
+//!
+//! A `SourceFile` owns the name and the contents of a file, read with `SourceFile::open` or given to `SourceFile::new`. Its `span` is over the contents with the source id of the file, and its `render_snippet` names the file in the header of the error:
+//!
+//! ```ignore
+//! use nom_span::{SourceFile, SourceId, Span};
+//!
+//! fn main() -> std::io::Result<()> {
+//!     let file = SourceFile::open("config.txt")?.with_source_id(SourceId(1));
+//!     let span = file.span();
+//!
+//!     // config.txt:1:1: expected a key
+//!     //   |
+//!     // 1 | = 1
+//!     //   | ^
+//!     println!("{}", file.render_snippet(Span::new(span.pos(), span.pos()), "expected a key"));
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Positions from byte offsets
+//!
+//! To keep only byte offsets after parsing, a `LineIndex` built once from the input gives the position of an offset when it's needed, and the offset of a line and a column. The positions are the ones of the spans sliced to the same offsets:
+//!
+//! ```ignore
+//! use nom_span::{LineIndex, Position};
+//!
+//! fn main() {
+//!     let index = LineIndex::new("let a = 1;\nlet b = 2;", true);
+//!
+//!     assert_eq!(index.position(15), Position::new(2, 5, 15));
+//!     assert_eq!(index.offset(2, 5), Some(15));
+//! }
+//! ```
+//!
+//! Within a span, `position_at` gives the position a number of bytes after the current one without slicing, and `offset_of` gives the offset of a line and a column from the current position.
+//!
+//! ### Streaming
+//!
+//! Spans work with the streaming parsers of nom. A parser returning `Incomplete` doesn't change the input, so once more data arrived, parse again from a span over the longer buffer. `respan` keeps the position of a span over other data, so the positions are the same as if everything was there from the start:
+//!
+//! ```ignore
+//! use nom::{bytes::streaming::tag, Err, IResult};
+//! use nom_span::Spanned;
+//!
+//! type Span<'a> = Spanned<&'a [u8]>;
+//!
+//! fn hello(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+//!     tag("hello")(input)
+//! }
+//!
+//! fn main() {
+//!     let mut buffer = b"hel".to_vec();
+//!     let span = Span::new(&buffer[..], false);
+//!
+//!     assert!(matches!(hello(span), Err(Err::Incomplete(_))));
+//!
+//!     // Remember where to resume from, without borrowing the buffer
+//!     let resume = span.respan(());
+//!     buffer.extend_from_slice(b"lo");
+//!
+//!     let span = resume.respan(&buffer[resume.byte_offset()..]);
+//!     let (rest, _) = hello(span).unwrap();
+//!     assert_eq!(rest.col(), 6);
+//! }
+//! ```
+//!
+//! ### Using nom 8
+//!
+//! nom 7 is supported by default. To use nom 8 instead, disable the default features and enable `nom8`, both can't be enabled at the same time:
+//!
+//! ```toml
+//! [dependencies]
+//! nom-span = { version = "0.1", default-features = false, features = ["nom8"] }
+//! ```
+//!
+//! ### Using winnow
+//!
+//! With the `winnow` feature, `Spanned<&str>` and `Spanned<&[u8]>` are winnow streams too. The slices returned by the parsers are spans, so they keep their position:
+//!
+//! ```ignore
+//! use nom_span::Spanned;
+//! use winnow::{ascii::alpha1, ModalResult, Parser};
+//!
+//! type Span<'a> = Spanned<&'a str>;
+//!
+//! fn word<'a>(input: &mut Span<'a>) -> ModalResult<Span<'a>> {
+//!     alpha1.parse_next(input)
+//! }
+//! ```
+//!
+//! ### Using bstr
+//!
+//! With the `bstr` feature, a `Spanned<BStrInput>` wraps a `&BStr` that nom can parse, as the nom traits can't be implemented for `&BStr` itself. It's parsed like a `&[u8]`, the columns being the chars `bstr` decodes, every invalid sequence being one replacement char. The spans convert to and from `Spanned<&BStr>` and `Spanned<&[u8]>` keeping their position:
+//!
+//! ```ignore
+//! use bstr::{BStr, ByteSlice};
+//! use nom::{bytes::complete::take_until, IResult};
+//! use nom_span::{BStrInput, Spanned};
+//!
+//! fn until_bang(input: Spanned<BStrInput>) -> IResult<Spanned<BStrInput>, Spanned<BStrInput>> {
+//!     take_until("!")(input)
+//! }
+//!
+//! let (rest, before) = until_bang(Spanned::new(BStrInput::new(b"caf\xc3\xa9 \xff!"), true)).unwrap();
+//!
+//! let before: Spanned<&BStr> = before.into();
+//! assert_eq!(rest.col(), before.chars().count() + 1);
+//! ```
+//!
+//! ### Ignoring the case of Unicode letters
+//!
+//! nom's `tag_no_case` only ignores the case of ASCII letters on `&[u8]`, and on `&str` the match must have the length of the tag. With the `caseless` feature, `tag_no_case_unicode` uses the Unicode case folding instead, so `"Straße"` matches `"STRASSE"`:
+//!
+//! ```ignore
+//! use nom::IResult;
+//! use nom_span::{tag_no_case_unicode, Spanned};
+//!
+//! type Span<'a> = Spanned<&'a str>;
+//!
+//! fn street(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+//!     tag_no_case_unicode("strasse")(input)
+//! }
+//! ```
+//!
+//! ### Errors without the input
+//!
+//! `SpannedError` is a nom error keeping the position where the parser failed instead of the rest of the input, so it doesn't borrow the input. `SpannedError<String>` also keeps the first 40 chars of the rest, the contexts of `nom::error::context` are kept with the position where they started, and the error of the function given to `map_res` is kept as the source of the error:
+//!
+//! ```ignore
+//! use nom::{character::complete::digit1, IResult};
+//! use nom_span::{SpannedError, Spanned};
+//!
+//! fn number(input: Spanned<&str>) -> IResult<Spanned<&str>, Spanned<&str>, SpannedError<String>> {
+//!     digit1(input)
+//! }
+//!
+//! // expected Digit at line 1, column 1, found "abc"
+//! println!("{}", number(Spanned::new("abc", true)).unwrap_err());
+//! ```
+//!
+//! At the top of the parser, `finish_with_position()` turns its result into a `Result` of the output, or a `LocatedFailure` telling where it failed and whether the error was recoverable, or that more data is needed. The position is taken from a nom `Error`, a `SpannedError` or any error implementing `ErrorPosition`. To return a failure once the input is gone, `into_owned(n)` replaces the nom `Error` by a `SpannedError<String>` keeping the `n` first chars of the rest, and `SpannedError::from` or `SpannedError::from_nom_error` does it for a nom `Error` alone.
+//!
+//! With nom 7, `convert_spanned_error(input, error)` renders the frames of a `VerboseError` over spans like `nom::error::convert_error` does for a `&str`, each frame being located with the line and the column of its span.
+//!
+//! When every branch of an `alt` failed, the error is the one of the last branch. Wrapping the error in `Furthest`, as in `Furthest<SpannedError>`, keeps the error of the branch that went the furthest in the input instead, for any error implementing `ErrorPosition`.
+//!
+//! In application code, `.at(&span)` or `.with_position(position)` on any `Result` wraps its error in a `LocatedError`, displayed as `at line 3, column 14: <error>` with the error as its source, so the position is kept in the reports of anyhow.
+//!
+//! ### Picking the deepest error
+//!
+//! With the `greedyerror` feature, spans implement the `Position` trait of [nom-greedyerror](https://github.com/dalance/nom-greedyerror), so `GreedyError` keeps the error of the `alt` branch that went the furthest. It's only available with nom 7:
+//!
+//! ```ignore
+//! use nom::{branch::alt, bytes::complete::tag, error::ErrorKind, IResult};
+//! use nom_greedyerror::GreedyError;
+//! use nom_span::Spanned;
+//!
+//! type Span<'a> = Spanned<&'a str>;
+//!
+//! fn keyword(input: Span<'_>) -> IResult<Span<'_>, Span<'_>, GreedyError<Span<'_>, ErrorKind>> {
+//!     alt((tag("let"), tag("fn")))(input)
+//! }
+//! ```
+//!
+//! ### Tracing the parsers
+//!
+//! With the `tracable` feature, a span with a `TracableInfo` as its extra data can be traced by [nom-tracable](https://github.com/dalance/nom-tracable), the traces showing the line and the column of the input. It's only available with nom 7:
+//!
+//! ```ignore
+//! use nom::{character::complete::digit1, IResult};
+//! use nom_span::Spanned;
+//! use nom_tracable::{tracable_parser, TracableInfo};
+//!
+//! type Span<'a> = Spanned<&'a str, TracableInfo>;
+//!
+//! #[tracable_parser]
+//! fn number(input: Span) -> IResult<Span, Span> {
+//!     digit1(input)
+//! }
+//!
+//! fn main() {
+//!     let info = TracableInfo::new().forward(true).backward(true);
+//!     let result = number(Spanned::new_extra("42", true, info));
+//! }
+//! ```
+//!
+//! ### Left recursion
+//!
+//! With the `recursive` feature, a span with a `RecursiveInfo` as its extra data can be used by [nom-recursive](https://github.com/dalance/nom-recursive) to parse left recursive grammars:
+//!
+//! ```ignore
+//! use nom::{branch::alt, character::complete::{char, digit1}, combinator::recognize, sequence::tuple, IResult};
+//! use nom_recursive::{recursive_parser, RecursiveInfo};
+//! use nom_span::Spanned;
+//!
+//! type Span<'a> = Spanned<&'a str, RecursiveInfo>;
+//!
+//! fn expr(input: Span) -> IResult<Span, Span> {
+//!     alt((add, digit1))(input)
+//! }
+//!
+//! #[recursive_parser]
+//! fn add(input: Span) -> IResult<Span, Span> {
+//!     recognize(tuple((expr, char('+'), digit1)))(input)
+//! }
+//! ```
+//!
+//! ### Memoizing the parsers
+//!
+//! With the `packrat` feature, the results of the parsers can be memoized by [nom-packrat](https://github.com/dalance/nom-packrat). A result is reused for the same position in the input and the same extra data. The `packrat_parser` macro uses the traits of nom 7:
+//!
+//! ```ignore
+//! use nom::{character::complete::digit1, IResult};
+//! use nom_packrat::{init, packrat_parser, storage};
+//! use nom_span::{Position, Spanned};
+//!
+//! type Span<'a> = Spanned<&'a str>;
+//!
+//! storage!(Position);
+//!
+//! #[packrat_parser]
+//! fn number(input: Span) -> IResult<Span, Position> {
+//!     let (rest, digits) = digit1(input)?;
+//!     Ok((rest, digits.pos()))
+//! }
+//!
+//! fn main() {
+//!     init!();
+//!     let result = number(Span::new("42", true));
+//! }
+//! ```
+//!
+//! ### Error trees of nom-supreme
+//!
+//! With the `supreme` feature, `locate_error_tree` replaces the spans of an `ErrorTree` of [nom-supreme](https://github.com/Lucretiel/nom-supreme) by their `Position`, so the tree displays the line and the column of every error. `final_parser` can also give an `ErrorTree<Position>` or an `ErrorTree<Location>` directly, without counting the input again. It's only available with nom 7:
+//!
+//! ```ignore
+//! use nom::{character::complete::digit1, IResult};
+//! use nom_span::{locate_error_tree, Spanned};
+//! use nom_supreme::error::ErrorTree;
+//!
+//! type Span<'a> = Spanned<&'a str>;
+//!
+//! fn main() {
+//!     let result: IResult<Span, Span, ErrorTree<Span>> = digit1(Span::new("x", true));
+//!
+//!     if let Err(nom::Err::Error(tree)) = result {
+//!         println!("{}", locate_error_tree(tree));
+//!     }
+//! }
+//! ```
+//!
+//! ### Using miette
+//!
+//! With the `miette` feature, spans convert into the `SourceSpan` of the labels, and the span over the whole input, as created before parsing, can be the source code of a diagnostic:
+//!
+//! ```ignore
+//! use miette::{Diagnostic, SourceSpan};
+//! use nom_span::Spanned;
+//!
+//! #[derive(Debug, Diagnostic, thiserror::Error)]
+//! #[error("expected a number")]
+//! struct ExpectedNumber<'a> {
+//!     #[source_code]
+//!     input: Spanned<&'a str>,
+//!     #[label("here")]
+//!     at: SourceSpan,
+//! }
+//!
+//! fn expected_number<'a>(input: Spanned<&'a str>, at: &Spanned<&'a str>) -> ExpectedNumber<'a> {
+//!     ExpectedNumber { input, at: at.into() }
+//! }
+//! ```
+//!
+//! ### Using codespan-reporting
+//!
+//! With the `codespan` feature, the span over the whole input gives the file the labels refer to, and the spans convert into the ranges of the labels:
+//!
+//! ```ignore
+//! use codespan_reporting::diagnostic::{Diagnostic, Label};
+//! use nom_span::Spanned;
+//!
+//! fn declared_twice(input: Spanned<&str>, first: &Spanned<&str>, second: &Spanned<&str>) {
+//!     let file = input.to_simple_file("main.txt");
+//!     let diagnostic = Diagnostic::error()
+//!         .with_message("declared twice")
+//!         .with_labels(vec![
+//!             Label::primary((), second),
+//!             Label::secondary((), first).with_message("first declared here"),
+//!         ]);
+//! }
+//! ```
+//!
+//! ### Using ariadne
+//!
+//! With the `ariadne` feature, spans give the spans of the labels in a source, and the span over the whole input, as created before parsing, gives the cache of that source without copying it. The offsets are bytes, so the reports are configured with `IndexType::Byte`:
+//!
+//! ```ignore
+//! use ariadne::{Config, IndexType, Label, Report, ReportKind};
+//! use nom_span::Spanned;
+//!
+//! fn declared_twice(input: Spanned<&str>, first: &Spanned<&str>, second: &Spanned<&str>) {
+//!     Report::build(ReportKind::Error, second.report_span("main.txt"))
+//!         .with_config(Config::default().with_index_type(IndexType::Byte))
+//!         .with_message("declared twice")
+//!         .with_label(Label::new(second.report_span("main.txt")))
+//!         .with_label(Label::new(first.report_span("main.txt")).with_message("first declared here"))
+//!         .finish()
+//!         .eprint(input.to_ariadne_cache("main.txt"))
+//!         .unwrap();
+//! }
+//! ```
+//! ### Using annotate-snippets
+//!
+//! With the `annotate-snippets` feature, spans give the snippet of the lines of the source they cover, numbered from their line and annotated at their columns, or an error with that snippet:
+//!
+//! ```ignore
+//! use annotate_snippets::{Level, Renderer};
+//! use nom_span::{Span, Spanned};
+//!
+//! fn unterminated(source: &str, start: &Spanned<&str>, end: &Spanned<&str>) -> String {
+//!     let report = [Level::ERROR
+//!         .primary_title("unterminated string")
+//!         .element(Span::between(start, end).to_annotated_snippet(source, "starts here"))];
+//!
+//!     Renderer::plain().render(&report)
+//! }
+//! ```
+//! ### Using lsp-types
+//!
+//! With the `lsp` feature, positions and spans convert into the positions and the ranges of the Language Server Protocol, given the source they come from. The LSP lines and characters start at 0 and the characters are counted in UTF-16 code units, whatever the unit of the columns, and the LSP positions convert back:
+//!
+//! ```ignore
+//! use nom_span::{Position, Spanned};
+//!
+//! fn diagnostic_range(source: &str, start: &Spanned<&str>, end: &Spanned<&str>) -> lsp_types::Range {
+//!     start.to_lsp_range(end, source)
+//! }
+//!
+//! fn hovered(source: &str, hover: lsp_types::Position) -> Option<Position> {
+//!     Position::from_lsp_position(source, hover)
+//! }
+//! ```
+//! ### Showing the errors
+//!
+//! `render_snippet` renders the line of the source at a position with a caret under it, or underlines the lines covered by a `Span`, without any dependency. The caret is aligned on the cells the chars take in a terminal with the `unicode-width` feature, and it's colored with the `color` feature:
+//!
+//! ```ignore
+//! use nom_span::Spanned;
+//!
+//! let source = "let x = 🙌 + y;";
+//! let span = Spanned::new(source, true);
+//!
+//! // 1:1: expected a statement
+//! //   |
+//! // 1 | let x = 🙌 + y;
+//! //   | ^
+//! println!("{}", span.render_snippet(source, "expected a statement"));
+//! ```
+//!
 //! ## What about [nom_locate](https://github.com/fflorent/nom_locate)?
 //!
 //! I was initially using [nom_locate](https://github.com/fflorent/nom_locate), but I faced some huge performance issue while building a [json parser](https://github.com/julesguesnon/spanned-json-parser), so I decided to implement my own input. I basically cloned [nom_locate](https://github.com/fflorent/nom_locate) and modified the counting function that was causing the performance issue. So thanks a lot for this awesome crate and please go add a star to it!
@@ -62,62 +550,322 @@
 //!
 //! So if you're planning to get the column only a few times, for example, only when an error occur, it may be better to use [nom_locate](https://github.com/fflorent/nom_locate), but if you need it quite often, this crate should be better.
 
-use bytecount::num_chars;
-use memchr::Memchr;
 use nom::{
-    AsBytes, Compare, Err, ExtendInto, FindSubstring, FindToken, InputIter, InputLength, InputTake,
-    InputTakeAtPosition, Offset, ParseTo, Slice,
+    AsBytes, Compare, CompareResult, ExtendInto, FindSubstring, FindToken, HexDisplay, Offset,
+    ParseTo,
 };
 use std::{
-    ops::{RangeFrom, RangeTo},
+    borrow::Cow,
+    fmt,
+    ops::{Bound, Deref, Range, RangeBounds},
     str::FromStr,
 };
 
+#[cfg(feature = "ariadne")]
+pub use ariadne_report::ReportSpan;
+pub use builder::SpannedBuilder;
+#[cfg(feature = "bstr")]
+pub use byte_str::BStrInput;
+#[cfg(feature = "caseless")]
+pub use case_folding::tag_no_case_unicode;
+pub use chars::PositionedChars;
+pub use combinator::{consumed_span, position, take_chars, take_chars_streaming, with_span};
+pub use cursor::SpannedCursor;
+pub use encoding::{Ascii, ColumnUnit, Dynamic, Encoding, Utf8};
+pub use error::{Excerpt, SpannedError};
+pub use finish::{ErrorPosition, FinishWithPosition, LocatedFailure};
+pub use full_input::{FullInput, FullSpanned};
+pub use furthest::Furthest;
+pub use line_index::LineIndex;
+pub use lines::{LineEnding, Lines};
+pub use located::{LocatedError, WithPosition};
+#[cfg(feature = "nom7")]
+pub use parser_ext::{SpannedParserExt, WithSpan};
+pub use position::{Position, SourceId};
+pub use shared::{ArcStr, CowData, CowStr, RcStr, Shared, SharedCharIndices, SharedChars};
+pub use source_file::SourceFile;
+pub use span::Span;
+#[cfg(feature = "supreme")]
+pub use supreme::locate_error_tree;
+pub use token::{ElementTracker, TokenPosition, TokenTracker};
+pub use tracker::{
+    CounterStorage, Counters, Items, KeepCounters, KeptCounters, PositionTracker, RecordTracker,
+};
+#[cfg(feature = "nom7")]
+pub use verbose_error::convert_spanned_error;
+
+#[cfg(feature = "annotate-snippets")]
+mod annotate;
+#[cfg(feature = "ariadne")]
+mod ariadne_report;
+mod builder;
+#[cfg(feature = "bstr")]
+mod byte_str;
+#[cfg(feature = "caseless")]
+mod case_folding;
+mod chars;
+#[cfg(feature = "codespan")]
+mod codespan;
+mod combinator;
+mod cursor;
+mod encoding;
+mod error;
+mod find;
+mod finish;
+mod full_input;
+mod furthest;
+#[cfg(feature = "unicode-segmentation")]
+mod grapheme;
+#[cfg(feature = "greedyerror")]
+mod greedy_error;
+mod line_index;
+mod lines;
+mod located;
+#[cfg(feature = "lsp")]
+mod lsp;
+#[cfg(feature = "miette")]
+mod miette_source;
+#[cfg(feature = "nom7")]
+mod nom7;
+#[cfg(feature = "nom8")]
+mod nom8;
+#[cfg(feature = "packrat")]
+mod packrat;
+#[cfg(feature = "nom7")]
+mod parser_ext;
+mod position;
+#[cfg(feature = "recursive")]
+mod recursive;
+mod shared;
+mod skip;
+mod snippet;
+mod source_file;
+mod span;
+#[cfg(feature = "supreme")]
+mod supreme;
+mod token;
+#[cfg(feature = "tracable")]
+mod tracable;
+mod tracker;
+#[cfg(feature = "nom7")]
+mod verbose_error;
+#[cfg(feature = "unicode-width")]
+mod width;
+#[cfg(feature = "winnow")]
+mod winnow_stream;
+
+#[cfg(feature = "annotate-snippets")]
+extern crate annotate_snippets;
+#[cfg(feature = "ariadne")]
+extern crate ariadne;
+#[cfg(feature = "bstr")]
+extern crate bstr;
 extern crate bytecount;
+#[cfg(feature = "caseless")]
+extern crate caseless;
+#[cfg(feature = "codespan")]
+extern crate codespan_reporting;
+#[cfg(feature = "lsp")]
+extern crate lsp_types;
 extern crate memchr;
+#[cfg(feature = "miette")]
+extern crate miette;
+#[cfg(feature = "nom7")]
 extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+#[cfg(feature = "greedyerror")]
+extern crate nom_greedyerror;
+#[cfg(feature = "packrat")]
+extern crate nom_packrat;
+#[cfg(feature = "recursive")]
+extern crate nom_recursive;
+#[cfg(feature = "supreme")]
+extern crate nom_supreme;
+#[cfg(feature = "tracable")]
+extern crate nom_tracable;
+#[cfg(feature = "serde")]
+extern crate serde;
+extern crate simdutf8;
+#[cfg(feature = "unicode-segmentation")]
+extern crate unicode_segmentation;
+#[cfg(feature = "unicode-width")]
+extern crate unicode_width;
+#[cfg(feature = "winnow")]
+extern crate winnow;
+
+#[cfg(all(feature = "nom7", feature = "nom8"))]
+compile_error!("the `nom7` and `nom8` features can't be enabled at the same time");
+#[cfg(not(any(feature = "nom7", feature = "nom8")))]
+compile_error!("either the `nom7` or the `nom8` feature has to be enabled");
 
 /// You can wrap your input in this struct with [`Spanned::new`]
+///
+/// `X` is some extra data carried along the input, see [`Spanned::new_extra`]. `C` picks how the
+/// lines and the columns are counted, see [`Encoding`] and [`PositionTracker`]. It defaults to
+/// [`Dynamic`], where the choice is made at runtime
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
-pub struct Spanned<T> {
+pub struct Spanned<T, X = (), C = Dynamic> {
     data: T,
+    offset: usize,
     line: usize,
     col: usize,
-    offset: usize,
-    handle_utf8: bool,
+    extra: X,
+    tracker: C,
 }
 
 impl<T> Spanned<T> {
     pub fn new(data: T, handle_utf8: bool) -> Self {
+        Self::with_tracker(data, Dynamic::new(handle_utf8))
+    }
+
+    /// Create a span that starts counting at the given position instead of `1:1` and offset `0`,
+    /// which is useful when parsing a chunk of a bigger input
+    ///
+    /// Like the line and the column of any span, `line` and `col` start at 1. As the chars before
+    /// `data` aren't known, the char offset starts at `offset`
+    pub fn new_at(data: T, line: usize, col: usize, offset: usize, handle_utf8: bool) -> Self {
+        debug_assert!(line >= 1, "line numbers start at 1");
+        debug_assert!(col >= 1, "column numbers start at 1");
+
+        let mut tracker = Dynamic::new(handle_utf8);
+        *tracker.kept_mut() = KeptCounters::new(col, offset, SourceId::default());
+
         Self {
             data,
-            line: 1,
-            col: 1,
-            offset: 0,
-            handle_utf8,
+            offset,
+            line,
+            col,
+            extra: (),
+            tracker,
         }
     }
 
+    /// Rebuild a span from its raw parts, mirroring `nom_locate`'s `LocatedSpan::new_from_raw_offset`
+    ///
+    /// # Safety
+    ///
+    /// The caller has to make sure that `offset`, `line` and `col` are the ones `data` would have
+    /// if it was obtained by slicing the original input, as every position computed from this span
+    /// will be relative to them
+    pub unsafe fn new_from_raw_offset(
+        offset: usize,
+        line: usize,
+        col: usize,
+        data: T,
+        handle_utf8: bool,
+    ) -> Self {
+        Self::new_at(data, line, col, offset, handle_utf8)
+    }
+
+    /// Start configuring a span over `data`, see [`SpannedBuilder`]
+    pub fn builder(data: T) -> SpannedBuilder<T> {
+        SpannedBuilder::new(data)
+    }
+
     pub fn new_for_ut8(data: T) -> Self {
+        Self::new(data, true)
+    }
+
+    pub fn new_for_ascii(data: T) -> Self {
+        Self::new(data, false)
+    }
+}
+
+impl<T, X> Spanned<T, X> {
+    /// Create a span carrying some extra data, that will be copied in every span obtained by
+    /// slicing this one
+    pub fn new_extra(data: T, handle_utf8: bool, extra: X) -> Self {
         Self {
             data,
+            offset: 0,
             line: 1,
             col: 1,
-            offset: 0,
-            handle_utf8: true,
+            extra,
+            tracker: Dynamic::new(handle_utf8),
         }
     }
 
-    pub fn new_for_ascii(data: T) -> Self {
+    /// Get a copy of the span where the columns will be counted in UTF-8 chars or in bytes
+    /// depending on `handle_utf8`, starting from the current position
+    #[must_use]
+    pub fn with_utf8(mut self, handle_utf8: bool) -> Self {
+        self.tracker.set_utf8(handle_utf8);
+        self
+    }
+}
+
+impl<T> Spanned<T, (), Utf8> {
+    /// Create a span that always counts the columns in UTF-8 chars
+    pub fn utf8(data: T) -> Self {
+        Self::with_tracker(data, Utf8)
+    }
+}
+
+impl<T> Spanned<T, (), Ascii> {
+    /// Create a span that always counts the columns in bytes
+    pub fn ascii(data: T) -> Self {
+        Self::with_tracker(data, Ascii)
+    }
+}
+
+impl<T, C> Spanned<T, (), C> {
+    /// Create a span where the lines and the columns are counted by `tracker`, see
+    /// [`PositionTracker`]. What the tracker keeps is taken as the start of the input
+    pub fn with_tracker(data: T, tracker: C) -> Self {
         Self {
             data,
+            offset: 0,
             line: 1,
             col: 1,
-            offset: 0,
-            handle_utf8: false,
+            extra: (),
+            tracker,
         }
     }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    C: PositionTracker + KeepCounters,
+{
+    /// Get the current column number counted in bytes, whatever unit the columns are counted in.
+    /// The `byte_col() - 1` bytes before the current position are the start of the line
+    ///
+    /// A span made with [`Spanned::new_at`] doesn't know the bytes before it, so its byte column
+    /// starts at the column it's given
+    pub fn byte_col(&self) -> usize {
+        self.tracker.kept().byte_col()
+    }
+
+    /// Check if the current position is at the start of a line
+    pub fn is_at_line_start(&self) -> bool {
+        self.byte_col() == 1
+    }
+
+    /// Get the byte offset where the current line starts, which is the start of the input when
+    /// the line starts before it, like for a span made with [`Spanned::new_at`] with a column
+    /// further than its offset
+    ///
+    /// The offset of a span over tokens isn't counted in bytes, so it has no line start offset:
+    ///
+    /// ```compile_fail
+    /// use nom_span::{Spanned, TokenPosition};
+    ///
+    /// struct Token;
+    ///
+    /// impl TokenPosition for Token {
+    ///     fn line(&self) -> usize { 1 }
+    ///     fn col(&self) -> usize { 5 }
+    /// }
+    ///
+    /// Spanned::from_tokens(&[Token][..]).line_start_offset();
+    /// ```
+    pub fn line_start_offset(&self) -> usize {
+        self.offset.saturating_sub(self.byte_col() - 1)
+    }
+}
 
+impl<T, X, C> Spanned<T, X, C> {
     /// Get the current line number
     pub fn line(&self) -> usize {
         self.line
@@ -128,278 +876,739 @@ impl<T> Spanned<T> {
         self.col
     }
 
+    /// Get the current line number, starting at 0
+    pub fn line0(&self) -> usize {
+        self.line.saturating_sub(1)
+    }
+
+    /// Get the current column number, starting at 0
+    pub fn col0(&self) -> usize {
+        self.col.saturating_sub(1)
+    }
+
     /// Get the current byte offset
     pub fn byte_offset(&self) -> usize {
         self.offset
     }
 
+    /// Get the offset in bits of the `bit`-th bit of the span, `bit` being the one paired with the
+    /// span in the input of nom's bit parsers
+    pub fn bit_offset(&self, bit: usize) -> usize {
+        self.offset * 8 + bit
+    }
+
+    /// Get the range of bytes from the start of this span to the start of `end`, like the range of
+    /// what was consumed from this span to get `end`
+    pub fn byte_range_to<U, Y, D>(&self, end: &Spanned<U, Y, D>) -> Range<usize> {
+        self.offset..end.offset
+    }
+
     /// Get the current data in the span
     pub fn data(&self) -> &T {
         &self.data
     }
-}
 
-impl<T> core::ops::Deref for Spanned<T> {
-    type Target = T;
-    fn deref(&self) -> &Self::Target {
-        &self.data
+    /// Get the extra data carried by the span
+    pub fn extra(&self) -> &X {
+        &self.extra
     }
-}
 
-impl<T, U> core::convert::AsRef<U> for Spanned<&T>
-where
-    T: ?Sized + core::convert::AsRef<U>,
-    U: ?Sized,
-{
-    fn as_ref(&self) -> &U {
-        self.data.as_ref()
+    /// Get the tracker counting the lines and the columns of the span
+    pub fn tracker(&self) -> &C {
+        &self.tracker
+    }
+
+    /// Consume the span and get the data it holds
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    /// Consume the span and get the data, the line, the column and the byte offset
+    pub fn into_parts(self) -> (T, usize, usize, usize) {
+        (self.data, self.line, self.col, self.offset)
+    }
+
+    /// Transform the data of the span while keeping its position
+    pub fn map_data<U, F>(self, f: F) -> Spanned<U, X, C>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Spanned {
+            data: f(self.data),
+            offset: self.offset,
+            line: self.line,
+            col: self.col,
+            extra: self.extra,
+            tracker: self.tracker,
+        }
+    }
+
+    /// Transform the extra data of the span while keeping its data and its position
+    pub fn map_extra<U, F>(self, f: F) -> Spanned<T, U, C>
+    where
+        F: FnOnce(X) -> U,
+    {
+        Spanned {
+            data: self.data,
+            offset: self.offset,
+            line: self.line,
+            col: self.col,
+            extra: f(self.extra),
+            tracker: self.tracker,
+        }
+    }
+
+    /// Try to transform the data of the span while keeping its position
+    ///
+    /// The error is also wrapped in a span so it doesn't lose the position
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f` located at the position of the span
+    pub fn try_map_data<U, E, F>(self, f: F) -> Result<Spanned<U, X, C>, Spanned<E, X, C>>
+    where
+        F: FnOnce(T) -> Result<U, E>,
+    {
+        let Self {
+            data,
+            offset,
+            line,
+            col,
+            extra,
+            tracker,
+        } = self;
+
+        match f(data) {
+            Ok(data) => Ok(Spanned {
+                data,
+                offset,
+                line,
+                col,
+                extra,
+                tracker,
+            }),
+            Err(data) => Err(Spanned {
+                data,
+                offset,
+                line,
+                col,
+                extra,
+                tracker,
+            }),
+        }
     }
 }
 
-impl<T> AsBytes for Spanned<T>
+impl<T, X, C> Spanned<T, X, C>
 where
-    T: AsBytes,
+    C: CounterStorage,
 {
-    fn as_bytes(&self) -> &[u8] {
-        self.data.as_bytes()
+    /// Get the current position, with the line, the column and the byte offset
+    pub fn pos(&self) -> Position {
+        Position::new(self.line, self.col, self.offset).with_source(self.source_id())
+    }
+
+    /// Get the current position in the input containing the one of this span, `base` being the
+    /// position of this span's input in it. See [`Position::rebase_onto`]
+    pub fn position_relative_to(&self, base: Position) -> Position {
+        self.pos().rebase_onto(base)
+    }
+
+    /// Get the id of the input the span is over, see [`Spanned::with_source_id`]. It's the
+    /// default one if the tracker doesn't keep it
+    pub fn source_id(&self) -> SourceId {
+        self.tracker
+            .kept_counters()
+            .map_or_else(SourceId::default, KeptCounters::source)
     }
 }
 
-impl<T, Comp> Compare<Comp> for Spanned<T>
+impl<T, X, C> Spanned<T, X, C>
 where
-    T: Compare<Comp>,
+    C: KeepCounters,
 {
-    fn compare(&self, t: Comp) -> nom::CompareResult {
-        self.data.compare(t)
+    /// Get the current offset in chars, which is the same as the byte offset if the span doesn't
+    /// handle UTF-8
+    pub fn char_offset(&self) -> usize {
+        self.tracker.kept().char_offset()
     }
 
-    fn compare_no_case(&self, t: Comp) -> nom::CompareResult {
-        self.data.compare_no_case(t)
+    /// Get a copy of the span over the input `source`, copied in every span obtained by slicing
+    /// this one and in their positions, to tell apart the spans of several files
+    #[must_use]
+    pub fn with_source_id(mut self, source: SourceId) -> Self {
+        self.tracker.kept_mut().set_source(source);
+        self
     }
 }
 
-impl<T> ExtendInto for Spanned<T>
+impl<T, X, C> Spanned<T, X, C>
 where
-    T: ExtendInto,
+    C: Encoding,
 {
-    type Item = T::Item;
-
-    type Extender = T::Extender;
-
-    fn new_builder(&self) -> Self::Extender {
-        self.data.new_builder()
+    /// Whether the columns are counted in UTF-8 chars rather than in bytes
+    pub fn handle_utf8(&self) -> bool {
+        self.tracker.handle_utf8()
     }
+}
 
-    fn extend_into(&self, acc: &mut Self::Extender) {
-        self.data.extend_into(acc);
+impl<T, X, C> Spanned<T, X, C> {
+    /// Check in debug builds that `what`, made from this span for a report crate, is made from the
+    /// span over the whole input, as the labels of the spans parsed from it would be located
+    /// from the wrong place otherwise
+    #[cfg(any(feature = "codespan", feature = "ariadne"))]
+    fn debug_assert_whole_input(&self, what: &str) {
+        debug_assert_eq!(
+            self.offset, 0,
+            "{what} must be made from the span over the whole input, as created before parsing"
+        );
     }
 }
 
-impl<T> FindSubstring<T> for Spanned<T>
+impl<T, X, C> Spanned<T, X, C>
 where
-    T: FindSubstring<T>,
+    C: Clone,
 {
-    fn find_substring(&self, substr: T) -> Option<usize> {
-        self.data.find_substring(substr)
+    /// Compute the counters and the tracker reached after consuming `consumed` from the current
+    /// position, `rest` being what remains after it
+    fn advance<I>(&self, consumed: &[I], rest: &[I]) -> (Counters, C)
+    where
+        C: PositionTracker<I>,
+    {
+        let kept = self.tracker.kept_counters();
+        let mut counters = Counters::load(self.line, self.col, self.offset, kept);
+        let mut tracker = self.tracker.clone();
+
+        tracker.advance(&mut counters, consumed, rest);
+        if let Some(kept) = tracker.kept_counters_mut() {
+            counters.keep(kept);
+        }
+
+        (counters, tracker)
     }
 }
 
-impl<T, Token> FindToken<Token> for Spanned<T>
+impl<T, X, C> Spanned<T, X, C>
 where
-    T: FindToken<Token>,
+    T: AsBytes,
+    C: PositionTracker,
 {
-    fn find_token(&self, token: Token) -> bool {
-        self.data.find_token(token)
+    /// Get the position right after the last byte of the data
+    ///
+    /// For an empty span it's the same as [`Spanned::pos`]
+    pub fn end_position(&self) -> Position {
+        let data = self.data.as_bytes();
+        let (counters, _) = self.advance(data, &[]);
+
+        Position::new(counters.line, counters.col, self.offset + data.len())
+            .with_source(self.source_id())
+    }
+
+    /// Get the line number right after the last byte of the data
+    pub fn end_line(&self) -> usize {
+        self.end_position().line
+    }
+
+    /// Get the column number right after the last byte of the data
+    pub fn end_col(&self) -> usize {
+        self.end_position().col
+    }
+
+    /// Get the number of bytes consumed before the span, its byte offset
+    pub fn bytes_consumed(&self) -> usize {
+        self.offset
+    }
+
+    /// Get the number of bytes left in the data
+    pub fn bytes_remaining(&self) -> usize {
+        self.data.as_bytes().len()
+    }
+
+    /// Get the fraction of the input consumed, from 0 to 1, to report the progress of a long
+    /// parse
+    ///
+    /// The input is taken to end with the data, which is the case of the rest given by the
+    /// parsers, so it's only meaningful for those. An empty input is fully consumed
+    #[allow(clippy::cast_precision_loss)] // The precision is more than enough for a progress
+    pub fn progress(&self) -> f64 {
+        let total = self.bytes_consumed() + self.bytes_remaining();
+
+        if total == 0 {
+            1.0
+        } else {
+            self.bytes_consumed() as f64 / total as f64
+        }
     }
 }
 
-impl<T> InputIter for Spanned<T>
+impl<T, X, C> Spanned<T, X, C>
 where
-    T: InputIter,
+    T: AsBytes,
 {
-    type Item = T::Item;
+    /// Get the offset of `other` from the start of this span, if `other` lies within its data
+    ///
+    /// Unlike [`Offset::offset`], it checks that both spans come from the same input, as the
+    /// offset between unrelated spans is meaningless
+    pub fn checked_offset(&self, other: &Self) -> Option<usize> {
+        let data = self.data.as_bytes();
+        let other = other.data.as_bytes();
 
-    type Iter = T::Iter;
+        let start = data.as_ptr() as usize;
+        let other_start = other.as_ptr() as usize;
 
-    type IterElem = T::IterElem;
+        let offset = other_start.checked_sub(start)?;
 
-    fn iter_indices(&self) -> Self::Iter {
-        self.data.iter_indices()
-    }
-
-    fn iter_elements(&self) -> Self::IterElem {
-        self.data.iter_elements()
+        if offset + other.len() <= data.len() {
+            Some(offset)
+        } else {
+            None
+        }
     }
 
-    fn position<P>(&self, predicate: P) -> Option<usize>
-    where
-        P: Fn(Self::Item) -> bool,
-    {
-        self.data.position(predicate)
+    /// Get the range of bytes of the data in the input, as wanted by most editors and diagnostic
+    /// libraries
+    pub fn byte_range_of_fragment(&self) -> Range<usize> {
+        self.offset..self.offset + self.data.as_bytes().len()
     }
+}
 
-    fn slice_index(&self, count: usize) -> Result<usize, nom::Needed> {
-        self.data.slice_index(count)
+impl<T, X, C> Spanned<T, X, C>
+where
+    X: Clone,
+    C: Clone,
+{
+    /// Get a span over `data` that starts at the current position, like a buffer derived from the
+    /// span (an unescaped string literal, decoded base64, ...)
+    ///
+    /// What's parsed in it is located from the start of the span, and can be located precisely in
+    /// the span with `map_back`
+    pub fn respan<U>(&self, data: U) -> Spanned<U, X, C> {
+        Spanned {
+            data,
+            offset: self.offset,
+            line: self.line,
+            col: self.col,
+            extra: self.extra.clone(),
+            tracker: self.tracker.clone(),
+        }
     }
 }
 
-impl<T> InputLength for Spanned<T>
+impl<B, X, C> Spanned<Cow<'_, B>, X, C>
 where
-    T: InputLength,
+    B: ToOwned + ?Sized,
+    X: Clone,
+    C: Clone,
 {
-    fn input_len(&self) -> usize {
-        self.data.input_len()
+    /// Get a span borrowing the data at the same position, for example to parse a `Cow<str>` as a
+    /// `&str` while the span is kept. A span converted to a [`CowStr`] is parsed without a borrow
+    pub fn to_borrowed(&self) -> Spanned<&B, X, C> {
+        self.respan(self.data.as_ref())
+    }
+
+    /// Get a span owning the data at the same position
+    pub fn into_owned(self) -> Spanned<B::Owned, X, C> {
+        self.map_data(Cow::into_owned)
     }
 }
 
-impl<T> InputTake for Spanned<T>
+impl<B, X, C> Spanned<&B, X, C>
 where
-    Self: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+    B: ToOwned + ?Sized,
 {
-    fn take(&self, count: usize) -> Self {
-        self.slice(..count)
+    /// Get a span owning a copy of the data at the same position, so it can outlive the input,
+    /// like an error kept after parsing
+    pub fn into_owned(self) -> Spanned<B::Owned, X, C> {
+        self.map_data(ToOwned::to_owned)
     }
+}
 
-    fn take_split(&self, count: usize) -> (Self, Self) {
-        (self.slice(count..), self.slice(..count))
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: Deref,
+    X: Clone,
+    C: Clone,
+{
+    /// Get a span borrowing the data at the same position, like `Option::as_deref`. A
+    /// `Spanned<String>` gives a `Spanned<&str>` that can be parsed again
+    pub fn as_deref(&self) -> Spanned<&T::Target, X, C> {
+        self.respan(&*self.data)
     }
 }
 
-impl<T> InputTakeAtPosition for Spanned<T>
+impl<'a, X, C> Spanned<&'a str, X, C>
 where
-    T: InputTakeAtPosition + InputLength + InputIter,
-    Self: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>> + Clone,
+    X: Clone,
+    C: Clone,
 {
-    type Item = <T as InputIter>::Item;
+    /// Get a span over the bytes of the data at the same position, with the same tracker, to go
+    /// on parsing binary data after some text
+    pub fn as_bytes_span(&self) -> Spanned<&'a [u8], X, C> {
+        self.respan(self.data.as_bytes())
+    }
 
-    fn split_at_position<P, E: nom::error::ParseError<Self>>(
-        &self,
-        predicate: P,
-    ) -> nom::IResult<Self, Self, E>
-    where
-        P: Fn(Self::Item) -> bool,
-    {
-        match self.data.position(predicate) {
-            Some(n) => Ok(self.take_split(n)),
-            None => Err(Err::Incomplete(nom::Needed::new(1))),
+    /// Take the first `count` bytes, or less to not cut a char in half, like
+    /// `str::floor_char_boundary`. The span taken starts at the current position
+    #[must_use]
+    pub fn take_floor_char_boundary(&self, count: usize) -> Self {
+        let mut end = count.min(self.data.len());
+
+        while !self.data.is_char_boundary(end) {
+            end -= 1;
         }
+
+        self.respan(&self.data[..end])
     }
 
-    fn split_at_position1<P, E: nom::error::ParseError<Self>>(
-        &self,
-        predicate: P,
-        _e: nom::error::ErrorKind,
-    ) -> nom::IResult<Self, Self, E>
-    where
-        P: Fn(Self::Item) -> bool,
-    {
-        match self.data.position(predicate) {
-            Some(n) => Ok(self.take_split(n)),
-            None => Err(Err::Incomplete(nom::Needed::new(1))),
+    /// Take the first `count` bytes, or more to not cut a char in half, like
+    /// `str::ceil_char_boundary`. The span taken starts at the current position
+    #[must_use]
+    pub fn take_ceil_char_boundary(&self, count: usize) -> Self {
+        let mut end = count.min(self.data.len());
+
+        while !self.data.is_char_boundary(end) {
+            end += 1;
         }
+
+        self.respan(&self.data[..end])
     }
+}
 
-    fn split_at_position_complete<P, E: nom::error::ParseError<Self>>(
-        &self,
-        predicate: P,
-    ) -> nom::IResult<Self, Self, E>
-    where
-        P: Fn(Self::Item) -> bool,
-    {
-        match self.split_at_position(predicate) {
-            Err(Err::Incomplete(_)) => Ok(self.take_split(self.input_len())),
-            res => res,
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: AsBytes,
+    X: Clone,
+    C: PositionTracker,
+{
+    /// Get the span over `rest`, that comes right after `consumed` in the current data
+    fn advance_to(&self, consumed: &[u8], rest: T) -> Self {
+        let (counters, tracker) = self.advance(consumed, rest.as_bytes());
+
+        Self {
+            data: rest,
+            offset: self.offset + consumed.len(),
+            line: counters.line,
+            col: counters.col,
+            extra: self.extra.clone(),
+            tracker,
         }
     }
+}
 
-    fn split_at_position1_complete<P, E: nom::error::ParseError<Self>>(
-        &self,
-        predicate: P,
-        e: nom::error::ErrorKind,
-    ) -> nom::IResult<Self, Self, E>
-    where
-        P: Fn(Self::Item) -> bool,
-    {
-        match self.data.position(predicate) {
-            Some(0) => Err(Err::Error(E::from_error_kind(self.clone(), e))),
-            Some(n) => Ok(self.take_split(n)),
-            None => {
-                if self.data.input_len() == 0 {
-                    Err(Err::Error(E::from_error_kind(self.clone(), e)))
-                } else {
-                    Ok(self.take_split(self.input_len()))
+macro_rules! impl_try_slice {
+    ($($fragment:ty),*) => {
+        $(
+            impl<'a, X, C> Spanned<$fragment, X, C>
+            where
+                X: Clone,
+                C: PositionTracker,
+            {
+                /// Get the span over `range` of the data, located like with nom's `Slice`
+                ///
+                /// Returns `None` instead of panicking if the range is out of the data, or if it
+                /// doesn't start and end on char boundaries for a `&str`
+                #[must_use]
+                pub fn try_slice<R>(&self, range: R) -> Option<Self>
+                where
+                    R: RangeBounds<usize>,
+                {
+                    let start = match range.start_bound() {
+                        Bound::Included(&start) => start,
+                        Bound::Excluded(&start) => start.checked_add(1)?,
+                        Bound::Unbounded => 0,
+                    };
+                    let data = self
+                        .data
+                        .get((Bound::Included(start), range.end_bound().cloned()))?;
+
+                    Some(self.advance_to(&self.data.as_bytes()[..start], data))
+                }
+
+                /// Split the span at `mid`, in reading order: the first half keeps the current
+                /// position and the second one is located like with `slice(mid..)`
+                ///
+                /// # Panics
+                ///
+                /// Panics if `mid` is past the end of the data, or if it's not on a char boundary
+                /// for a `&str`
+                #[must_use]
+                pub fn split_at(&self, mid: usize) -> (Self, Self) {
+                    let (head, tail) = self.data.split_at(mid);
+
+                    (self.respan(head), self.advance_to(head.as_bytes(), tail))
+                }
+
+                /// Split the span at `mid` like [`Self::split_at`], or get `None` instead of
+                /// panicking
+                #[must_use]
+                pub fn split_at_checked(&self, mid: usize) -> Option<(Self, Self)> {
+                    let (head, tail) = self.data.split_at_checked(mid)?;
+
+                    Some((self.respan(head), self.advance_to(head.as_bytes(), tail)))
                 }
             }
+        )*
+    };
+}
+
+impl_try_slice!(&'a str, &'a [u8]);
+
+/// Create a span at `1:1` and offset `0` over the default data. With the default encoding UTF-8 is
+/// handled, like with `Spanned::new(T::default(), true)`
+impl<T, X, C> Default for Spanned<T, X, C>
+where
+    T: Default,
+    X: Default,
+    C: Default,
+{
+    fn default() -> Self {
+        Self {
+            data: T::default(),
+            offset: 0,
+            line: 1,
+            col: 1,
+            extra: X::default(),
+            tracker: C::default(),
         }
     }
 }
 
-impl<T> Offset for Spanned<T>
+/// Same as `Spanned::new(data, true)`
+impl<'a> From<&'a str> for Spanned<&'a str> {
+    fn from(data: &'a str) -> Self {
+        Self::new(data, true)
+    }
+}
+
+/// Same as `Spanned::new(data, true)`
+impl<'a> From<&'a [u8]> for Spanned<&'a [u8]> {
+    fn from(data: &'a [u8]) -> Self {
+        Self::new(data, true)
+    }
+}
+
+/// Same as [`Spanned::byte_range_of_fragment`]
+impl<T, X, C> From<&Spanned<T, X, C>> for Range<usize>
 where
-    T: Offset,
+    T: AsBytes,
 {
-    fn offset(&self, second: &Self) -> usize {
-        self.data.offset(&second.data)
+    fn from(span: &Spanned<T, X, C>) -> Self {
+        span.byte_range_of_fragment()
+    }
+}
+
+impl<'a, X, C> From<Spanned<&'a str, X, C>> for &'a str {
+    fn from(span: Spanned<&'a str, X, C>) -> Self {
+        span.data
     }
 }
 
-impl<T, R: FromStr> ParseTo<R> for Spanned<T>
+impl<'a, X, C> From<Spanned<&'a [u8], X, C>> for &'a [u8] {
+    fn from(span: Spanned<&'a [u8], X, C>) -> Self {
+        span.data
+    }
+}
+
+/// Display the data, so errors holding a span can be displayed
+impl<T, X, C> fmt::Display for Spanned<T, X, C>
 where
-    T: ParseTo<R>,
+    T: fmt::Display,
 {
-    fn parse_to(&self) -> Option<R> {
-        self.data.parse_to()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.data.fmt(f)
+    }
+}
+
+impl<T, X, C> core::ops::Deref for Spanned<T, X, C> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.data
     }
 }
 
-impl<T, R> Slice<R> for Spanned<T>
+impl<T, U, X, C> core::convert::AsRef<U> for Spanned<&T, X, C>
 where
-    T: Slice<R> + Offset + AsBytes + Slice<RangeTo<usize>>,
+    T: ?Sized + core::convert::AsRef<U>,
+    U: ?Sized,
+{
+    fn as_ref(&self) -> &U {
+        self.data.as_ref()
+    }
+}
+
+impl<T, X, C> AsBytes for Spanned<T, X, C>
+where
+    T: AsBytes,
 {
-    fn slice(&self, range: R) -> Self {
-        let next_data = self.data.slice(range);
+    fn as_bytes(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+}
+
+/// Compare a fragment with a tag of the same type or of the other one, like nom does for `&str` and
+/// `&[u8]`, and with byte arrays so `tag(b"abc")` works on both
+macro_rules! impl_compare {
+    ($($fragment:ty),*) => {$(
+        impl<'a, 'b, X, C> Compare<&'b str> for Spanned<$fragment, X, C> {
+            fn compare(&self, t: &'b str) -> CompareResult {
+                self.data.compare(t)
+            }
 
-        let offset = self.data.offset(&next_data);
+            fn compare_no_case(&self, t: &'b str) -> CompareResult {
+                self.data.compare_no_case(t)
+            }
+        }
 
-        let old_data = self.data.slice(..offset);
+        impl<'a, 'b, X, C> Compare<&'b [u8]> for Spanned<$fragment, X, C> {
+            fn compare(&self, t: &'b [u8]) -> CompareResult {
+                self.data.compare(t)
+            }
 
-        if offset == 0 {
-            return Self {
-                data: next_data,
-                line: self.line,
-                col: self.col,
-                offset: self.offset,
-                handle_utf8: self.handle_utf8,
-            };
+            fn compare_no_case(&self, t: &'b [u8]) -> CompareResult {
+                self.data.compare_no_case(t)
+            }
         }
 
-        let new_line_iter = Memchr::new(b'\n', old_data.as_bytes());
+        impl<'a, 'b, X, C, const N: usize> Compare<&'b [u8; N]> for Spanned<$fragment, X, C> {
+            fn compare(&self, t: &'b [u8; N]) -> CompareResult {
+                self.data.compare(&t[..])
+            }
 
-        let mut lines_to_add = 0;
-        let mut last_index = None;
-        for i in new_line_iter {
-            lines_to_add += 1;
-            last_index = Some(i);
+            fn compare_no_case(&self, t: &'b [u8; N]) -> CompareResult {
+                self.data.compare_no_case(&t[..])
+            }
         }
-        let last_index = last_index.map_or(0, |v| v + 1);
 
-        let col = if self.handle_utf8 {
-            num_chars(old_data.as_bytes().slice(last_index..))
-        } else {
-            old_data.as_bytes().len() - last_index
-        };
+        impl<'a, X, C, const N: usize> Compare<[u8; N]> for Spanned<$fragment, X, C> {
+            fn compare(&self, t: [u8; N]) -> CompareResult {
+                self.data.compare(&t[..])
+            }
 
-        Self {
-            data: next_data,
-            line: self.line + lines_to_add,
-            col: if lines_to_add == 0 {
-                self.col + col
-            } else {
-                // When going to a new line, char starts at 1
-                col + 1
-            },
-            offset: self.offset + offset,
-            handle_utf8: self.handle_utf8,
+            fn compare_no_case(&self, t: [u8; N]) -> CompareResult {
+                self.data.compare_no_case(&t[..])
+            }
         }
+    )*};
+}
+
+impl_compare!(&'a str, &'a [u8]);
+
+#[cfg(feature = "bstr")]
+impl_compare!(byte_str::BStrInput<'a>);
+
+/// Use a span as a tag, like a delimiter parsed earlier that has to be repeated
+impl<T, U, X, C, Y, D> Compare<Spanned<U, Y, D>> for Spanned<T, X, C>
+where
+    T: Compare<U>,
+{
+    fn compare(&self, t: Spanned<U, Y, D>) -> CompareResult {
+        self.data.compare(t.data)
+    }
+
+    fn compare_no_case(&self, t: Spanned<U, Y, D>) -> CompareResult {
+        self.data.compare_no_case(t.data)
+    }
+}
+
+impl<T, X, C> ExtendInto for Spanned<T, X, C>
+where
+    T: ExtendInto,
+{
+    type Item = T::Item;
+
+    type Extender = T::Extender;
+
+    fn new_builder(&self) -> Self::Extender {
+        self.data.new_builder()
+    }
+
+    fn extend_into(&self, acc: &mut Self::Extender) {
+        self.data.extend_into(acc);
+    }
+}
+
+/// Search with any needle the data can be searched with, like a `&str` in a `&[u8]`
+impl<T, U, X, C> FindSubstring<U> for Spanned<T, X, C>
+where
+    T: FindSubstring<U>,
+{
+    fn find_substring(&self, substr: U) -> Option<usize> {
+        self.data.find_substring(substr)
+    }
+}
+
+/// Search for a span in a `&str`, so a span can be the needle of another span through the impl
+/// above
+impl<'b, X, C> FindSubstring<Spanned<&'b str, X, C>> for &str {
+    fn find_substring(&self, substr: Spanned<&'b str, X, C>) -> Option<usize> {
+        self.find_substring(substr.data)
+    }
+}
+
+/// Search for a span in a `&[u8]`, so a span can be the needle of another span through the impl
+/// above
+impl<'b, X, C> FindSubstring<Spanned<&'b [u8], X, C>> for &[u8] {
+    fn find_substring(&self, substr: Spanned<&'b [u8], X, C>) -> Option<usize> {
+        self.find_substring(substr.data)
+    }
+}
+
+/// Search for a `&str` span in a `&[u8]`, like nom does for a `&str` needle
+impl<'b, X, C> FindSubstring<Spanned<&'b str, X, C>> for &[u8] {
+    fn find_substring(&self, substr: Spanned<&'b str, X, C>) -> Option<usize> {
+        self.find_substring(substr.data)
+    }
+}
+
+impl<T, Token, X, C> FindToken<Token> for Spanned<T, X, C>
+where
+    T: FindToken<Token>,
+{
+    fn find_token(&self, token: Token) -> bool {
+        self.data.find_token(token)
+    }
+}
+
+/// The addresses of the dump start at the byte offset of the span, so they match the original input
+impl<H, X, C> HexDisplay for Spanned<&H, X, C>
+where
+    H: HexDisplay + ?Sized,
+{
+    fn to_hex(&self, chunk_size: usize) -> String {
+        self.data.to_hex_from(chunk_size, self.offset)
+    }
+
+    fn to_hex_from(&self, chunk_size: usize, from: usize) -> String {
+        self.data.to_hex_from(chunk_size, from)
+    }
+}
+
+/// In debug builds, `second` is checked to lie within `self`, see [`Spanned::checked_offset`]
+impl<T, X, C> Offset for Spanned<T, X, C>
+where
+    T: Offset + AsBytes,
+{
+    fn offset(&self, second: &Self) -> usize {
+        debug_assert!(
+            self.checked_offset(second).is_some(),
+            "the span at {}:{} (byte {}) doesn't contain the span at {}:{} (byte {}), they may come from different inputs",
+            self.line(),
+            self.col(),
+            self.offset,
+            second.line(),
+            second.col(),
+            second.offset,
+        );
+
+        self.data.offset(&second.data)
+    }
+}
+
+impl<T, R: FromStr, X, C> ParseTo<R> for Spanned<T, X, C>
+where
+    T: ParseTo<R>,
+{
+    fn parse_to(&self) -> Option<R> {
+        self.data.parse_to()
     }
 }
@@ -0,0 +1,135 @@
+use encoding::{Dynamic, Encoding};
+use memchr::Memchr;
+use position::{Position, SourceId};
+use std::{borrow::Cow, iter};
+use tracker::{Counters, PositionTracker};
+
+/// The offsets where the lines of an input start, to get the position of a byte offset long
+/// after parsing without keeping the spans
+///
+/// It's built once from the whole input, and every lookup is a binary search on the lines then a
+/// count of the columns on the line found. The lines and the columns are counted like the spans
+/// made by [`Spanned::new`](crate::Spanned::new) do, so the positions are the same as the ones of
+/// the spans sliced to the same offsets
+#[derive(Clone, Debug)]
+pub struct LineIndex<'a> {
+    input: &'a str,
+    line_starts: Cow<'a, [usize]>,
+    tracker: Dynamic,
+    source: SourceId,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Index the lines of `input`, the columns being counted in chars if `handle_utf8` is true
+    /// and in bytes otherwise
+    #[must_use]
+    pub fn new(input: &'a str, handle_utf8: bool) -> Self {
+        Self::with_line_starts(input, Cow::Owned(line_starts(input)), handle_utf8)
+    }
+
+    /// Index `input` with the `line_starts` already found in it by [`line_starts`]
+    pub(crate) fn with_line_starts(
+        input: &'a str,
+        line_starts: Cow<'a, [usize]>,
+        handle_utf8: bool,
+    ) -> Self {
+        Self {
+            input,
+            line_starts,
+            tracker: Dynamic::new(handle_utf8),
+            source: SourceId::default(),
+        }
+    }
+
+    /// Get the same index with its positions in the input `source`
+    #[must_use]
+    pub fn with_source_id(mut self, source: SourceId) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Get the number of lines, the empty line after a last newline being counted
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Get the text of the line `line`, starting at 1, without its `\n` or `\r\n`
+    #[must_use]
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+
+        Some(match self.line_starts.get(line) {
+            Some(next) => {
+                let text = &self.input[start..next - 1];
+                text.strip_suffix('\r').unwrap_or(text)
+            }
+            None => &self.input[start..],
+        })
+    }
+
+    /// Get the position at the byte `offset`, which is clamped to the end of the input
+    #[must_use]
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.input.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+
+        let bytes = self.input.as_bytes();
+        let mut counters = Counters::new(line, 1, 0);
+        self.tracker
+            .clone()
+            .advance(&mut counters, &bytes[line_start..offset], &bytes[offset..]);
+
+        Position::new(line, counters.col, offset).with_source(self.source)
+    }
+
+    /// Get the byte offset of the column `col` of the line `line`, both starting at 1
+    ///
+    /// The column right after the last char of the line, before its `\n`, is the end of the
+    /// line. It's `None` for a position outside of the input, or in the middle of a char when
+    /// the columns are counted in bytes
+    #[must_use]
+    pub fn offset(&self, line: usize, col: usize) -> Option<usize> {
+        let line_start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let line_end = self
+            .line_starts
+            .get(line)
+            .map_or(self.input.len(), |next| next - 1);
+        let text = &self.input[line_start..line_end];
+        let col0 = col.checked_sub(1)?;
+
+        let index = if self.tracker.handle_utf8() {
+            text.char_indices()
+                .map(|(index, _)| index)
+                .chain(iter::once(text.len()))
+                .nth(col0)?
+        } else if text.is_char_boundary(col0) {
+            col0
+        } else {
+            return None;
+        };
+
+        Some(line_start + index)
+    }
+}
+
+/// Get the offsets where the lines of `input` start, the first one at 0 and then one after every
+/// `\n`
+pub(crate) fn line_starts(input: &str) -> Vec<usize> {
+    iter::once(0)
+        .chain(Memchr::new(b'\n', input.as_bytes()).map(|newline| newline + 1))
+        .collect()
+}
+
+/// Clamp `offset` to the end of `source` and move it back to the start of the char it's in, like
+/// `str::floor_char_boundary`
+pub(crate) fn floor_char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
@@ -0,0 +1,231 @@
+use bytecount;
+use encoding::Dynamic;
+use memchr::memchr;
+use nom::AsBytes;
+use std::iter::FusedIterator;
+use tracker::{KeepCounters, PositionTracker};
+use Spanned;
+
+impl<'a, X, C> Spanned<&'a str, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+    /// Iterate over the lines of the data, each line being located in the input
+    ///
+    /// Like `str::lines`, the lines end with `\n` or `\r\n`, which isn't part of them, and the
+    /// last line can have no terminator
+    #[must_use]
+    pub fn lines(&self) -> Lines<'a, X, C> {
+        Lines {
+            rest: self.clone(),
+            inclusive: false,
+        }
+    }
+
+    /// Iterate over the lines of the data like [`Spanned::lines`], with their terminator
+    #[must_use]
+    pub fn lines_inclusive(&self) -> Lines<'a, X, C> {
+        Lines {
+            rest: self.clone(),
+            inclusive: true,
+        }
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: AsBytes,
+{
+    /// Count the `\n` in the data, so the lines coming after the current one. A last line without
+    /// newline isn't counted, as it's the line after the last newline
+    ///
+    /// Only `\n` is counted, whatever the tracker counts as a line ending. For `\r\n` it makes
+    /// no difference
+    #[must_use]
+    pub fn remaining_lines(&self) -> usize {
+        bytecount::count(self.data.as_bytes(), b'\n')
+    }
+
+    /// Get the byte offset where the current line ends, before its `\n` or `\r\n`. Only the
+    /// data is looked at, so it's the end of the data if the line goes on after it
+    #[must_use]
+    pub fn line_end_offset(&self) -> usize {
+        let data = self.data.as_bytes();
+
+        let end = match memchr(b'\n', data) {
+            Some(newline) if newline > 0 && data[newline - 1] == b'\r' => newline - 1,
+            Some(newline) => newline,
+            None => data.len(),
+        };
+
+        self.offset + end
+    }
+
+    /// Get the number of the line the data ends on, the empty line after it when it ends with a
+    /// newline
+    #[must_use]
+    pub fn last_line_number(&self) -> usize {
+        self.line() + self.remaining_lines()
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    C: PositionTracker + KeepCounters,
+{
+    /// Get the whole text of the current line in `original`, the input the span comes from,
+    /// without its `\n` or `\r\n`, including what was consumed before the current position
+    #[must_use]
+    pub fn current_line_text<'o>(&self, original: &'o str) -> &'o str {
+        let rest = &original[self.line_start_offset()..];
+        let line = &rest[..memchr(b'\n', rest.as_bytes()).unwrap_or(rest.len())];
+
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+}
+
+/// What ends a line taken with `take_line`
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// The end of the data, for a last line without terminator
+    Eof,
+}
+
+impl LineEnding {
+    /// Get the terminator, empty for [`LineEnding::Eof`]
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+            Self::Eof => "",
+        }
+    }
+}
+
+macro_rules! impl_line_splits {
+    ($($fragment:ty),*) => {
+        $(
+            impl<'a, X, C> Spanned<$fragment, X, C>
+            where
+                X: Clone,
+                C: PositionTracker,
+            {
+                /// Take the first line of the data, giving the rest after its terminator, the line
+                /// without it and what ended it, located like with `slice`
+                ///
+                /// The lines end with `\n` or `\r\n`. When there's no newline, the whole data is
+                /// the line and the rest is empty
+                #[must_use]
+                pub fn take_line(&self) -> (Self, Self, LineEnding) {
+                    let bytes = self.data.as_bytes();
+
+                    let Some(newline) = memchr(b'\n', bytes) else {
+                        let rest = &self.data[bytes.len()..];
+
+                        return (self.advance_to(bytes, rest), self.clone(), LineEnding::Eof);
+                    };
+
+                    let (end, ending) = if newline > 0 && bytes[newline - 1] == b'\r' {
+                        (newline - 1, LineEnding::CrLf)
+                    } else {
+                        (newline, LineEnding::Lf)
+                    };
+
+                    (
+                        self.advance_to(&bytes[..=newline], &self.data[newline + 1..]),
+                        self.respan(&self.data[..end]),
+                        ending,
+                    )
+                }
+
+                /// Cut the data into `chunks` pieces of about the same size, to be parsed in
+                /// parallel, each piece being located in the input
+                ///
+                /// The pieces are only cut after a `\n`, so a line is never split, and there can
+                /// be less pieces than asked when the lines are long. The data is counted once,
+                /// from one piece to the next
+                #[must_use]
+                pub fn split_for_parallel(&self, chunks: usize) -> Vec<Self> {
+                    let bytes = self.data.as_bytes();
+                    let chunks = chunks.max(1);
+
+                    let mut pieces = Vec::with_capacity(chunks);
+                    let mut rest = self.clone();
+                    let mut start = 0;
+
+                    for i in 1..chunks {
+                        // A cut right at the target is after the `\n` before it
+                        let target = (bytes.len() * i / chunks).saturating_sub(1).max(start);
+                        let Some(newline) = memchr(b'\n', &bytes[target..]) else {
+                            break;
+                        };
+                        let end = target + newline + 1;
+
+                        if end < bytes.len() {
+                            let (piece, tail) = rest.split_at(end - start);
+                            pieces.push(piece);
+                            rest = tail;
+                            start = end;
+                        }
+                    }
+                    pieces.push(rest);
+
+                    pieces
+                }
+            }
+        )*
+    };
+}
+
+impl_line_splits!(&'a str, &'a [u8]);
+
+/// The iterator of the lines of a span, given by [`Spanned::lines`] and
+/// [`Spanned::lines_inclusive`]
+#[derive(Clone, Debug)]
+pub struct Lines<'a, X = (), C = Dynamic> {
+    rest: Spanned<&'a str, X, C>,
+    inclusive: bool,
+}
+
+impl<'a, X, C> Iterator for Lines<'a, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+    type Item = Spanned<&'a str, X, C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.data.is_empty() {
+            return None;
+        }
+
+        let (rest, mut line, ending) = self.rest.take_line();
+        if self.inclusive {
+            line = self
+                .rest
+                .respan(&self.rest.data[..line.len() + ending.as_str().len()]);
+        }
+        self.rest = rest;
+
+        Some(line)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.data.len();
+
+        (usize::from(len > 0), Some(len))
+    }
+}
+
+impl<X, C> FusedIterator for Lines<'_, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+}
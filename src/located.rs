@@ -0,0 +1,80 @@
+use position::Position;
+use std::{error::Error, fmt};
+use tracker::CounterStorage;
+use Spanned;
+
+/// An error with the position it happened at, given by [`WithPosition`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocatedError<E> {
+    /// Where the error happened
+    pub position: Position,
+    /// The error, which is the source of this one
+    pub error: E,
+}
+
+impl<E> LocatedError<E> {
+    #[must_use]
+    pub fn new(position: Position, error: E) -> Self {
+        Self { position, error }
+    }
+
+    /// Get the error, dropping the position
+    #[must_use]
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+/// Display the error as `at line 3, column 14: <error>`
+impl<E> fmt::Display for LocatedError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at {:#}: {}", self.position, self.error)
+    }
+}
+
+impl<E> Error for LocatedError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Attach a position to the error of a `Result`, like the context of anyhow
+pub trait WithPosition<T, E> {
+    /// Wrap the error in a [`LocatedError`] at `position`
+    ///
+    /// # Errors
+    ///
+    /// The error of the result, with the position
+    fn with_position(self, position: Position) -> Result<T, LocatedError<E>>;
+
+    /// Wrap the error in a [`LocatedError`] at the current position of `span`
+    ///
+    /// # Errors
+    ///
+    /// The error of the result, with the position
+    fn at<U, X, C>(self, span: &Spanned<U, X, C>) -> Result<T, LocatedError<E>>
+    where
+        C: CounterStorage;
+}
+
+impl<T, E> WithPosition<T, E> for Result<T, E>
+where
+    E: Error,
+{
+    fn with_position(self, position: Position) -> Result<T, LocatedError<E>> {
+        self.map_err(|error| LocatedError::new(position, error))
+    }
+
+    fn at<U, X, C>(self, span: &Spanned<U, X, C>) -> Result<T, LocatedError<E>>
+    where
+        C: CounterStorage,
+    {
+        self.with_position(span.pos())
+    }
+}
@@ -0,0 +1,119 @@
+use line_index::floor_char_boundary;
+use lsp_types;
+use memchr::memchr;
+use position::Position;
+use span::Span;
+use std::convert::TryFrom;
+use tracker::{KeepCounters, PositionTracker};
+use Spanned;
+
+impl Position {
+    /// Get the LSP position of this position in `source`, the input it comes from. The line and
+    /// the character start at 0, and the character is counted in UTF-16 code units, whatever the
+    /// unit of the column
+    ///
+    /// An offset after the end of `source` is the end of `source`, and one in the middle of a
+    /// char is at the start of the char
+    #[must_use]
+    pub fn to_lsp_position(&self, source: &str) -> lsp_types::Position {
+        let offset = floor_char_boundary(source, self.offset);
+        let line_start = source[..offset]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+
+        lsp_position(source, self.line, line_start, offset)
+    }
+
+    /// Get the position in `source` of an LSP position, counted in UTF-16 code units, the column
+    /// being counted in chars. It's `None` when the line is after the last one
+    ///
+    /// Like in the protocol, a character after the end of the line is the end of the line, and
+    /// one in the middle of a char is at the start of the char
+    #[must_use]
+    pub fn from_lsp_position(source: &str, position: lsp_types::Position) -> Option<Self> {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            line_start += memchr(b'\n', &source.as_bytes()[line_start..])? + 1;
+        }
+
+        let rest = &source[line_start..];
+        let line = &rest[..memchr(b'\n', rest.as_bytes()).unwrap_or(rest.len())];
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        let mut units = 0;
+        let mut col = 1;
+        let mut len = 0;
+        for c in line.chars() {
+            units += c.len_utf16();
+            if units > position.character as usize {
+                break;
+            }
+            col += 1;
+            len += c.len_utf8();
+        }
+
+        Some(Self::new(position.line as usize + 1, col, line_start + len))
+    }
+}
+
+impl Span {
+    /// Get the LSP range of the span in `source`, the input it comes from, like
+    /// [`Position::to_lsp_position`]
+    #[must_use]
+    pub fn to_lsp_range(&self, source: &str) -> lsp_types::Range {
+        lsp_types::Range::new(
+            self.start.to_lsp_position(source),
+            self.end.to_lsp_position(source),
+        )
+    }
+
+    /// Get the span in `source` of an LSP range, like [`Position::from_lsp_position`]
+    #[must_use]
+    pub fn from_lsp_range(source: &str, range: lsp_types::Range) -> Option<Self> {
+        Some(Self::new(
+            Position::from_lsp_position(source, range.start)?,
+            Position::from_lsp_position(source, range.end)?,
+        ))
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    C: PositionTracker + KeepCounters,
+{
+    /// Get the LSP position of the current position in `source`, the input it comes from, like
+    /// [`Position::to_lsp_position`]. Only the current line of `source` is looked at
+    #[must_use]
+    pub fn to_lsp_position(&self, source: &str) -> lsp_types::Position {
+        lsp_position(source, self.line(), self.line_start_offset(), self.offset)
+    }
+
+    /// Get the LSP range in `source` from the current position to the one of `end`, like
+    /// [`Position::to_lsp_position`]
+    #[must_use]
+    pub fn to_lsp_range<U, Y, D>(&self, end: &Spanned<U, Y, D>, source: &str) -> lsp_types::Range
+    where
+        D: PositionTracker + KeepCounters,
+    {
+        lsp_types::Range::new(self.to_lsp_position(source), end.to_lsp_position(source))
+    }
+}
+
+/// Get the LSP position at `offset` in `source`, on the line `line` starting at `line_start`,
+/// the offsets being floored to a char boundary of `source`
+fn lsp_position(
+    source: &str,
+    line: usize,
+    line_start: usize,
+    offset: usize,
+) -> lsp_types::Position {
+    let offset = floor_char_boundary(source, offset);
+    let line_start = floor_char_boundary(source, line_start).min(offset);
+    let character = source[line_start..offset].encode_utf16().count();
+
+    lsp_types::Position::new(saturate(line.saturating_sub(1)), saturate(character))
+}
+
+fn saturate(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
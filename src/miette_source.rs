@@ -0,0 +1,50 @@
+use miette::{MietteError, SourceCode, SourceOffset, SourceSpan, SpanContents};
+use nom::AsBytes;
+use position::Position;
+use span::Span;
+use Spanned;
+
+/// The span of the data in the input
+impl<T, X, C> From<&Spanned<T, X, C>> for SourceSpan
+where
+    T: AsBytes,
+{
+    fn from(span: &Spanned<T, X, C>) -> Self {
+        (span.offset, span.data.as_bytes().len()).into()
+    }
+}
+
+impl From<Span> for SourceSpan {
+    fn from(span: Span) -> Self {
+        (span.start.offset, span.len_bytes()).into()
+    }
+}
+
+impl From<Position> for SourceOffset {
+    fn from(pos: Position) -> Self {
+        pos.offset.into()
+    }
+}
+
+/// The span over the whole input is the source code of the diagnostics labeling the spans parsed
+/// from it. Reading fails with [`MietteError::OutOfBounds`] for any other span
+impl<T, X, C> SourceCode for Spanned<T, X, C>
+where
+    T: SourceCode,
+    X: Send + Sync,
+    C: Send + Sync,
+{
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        if self.offset != 0 {
+            return Err(MietteError::OutOfBounds);
+        }
+
+        self.data
+            .read_span(span, context_lines_before, context_lines_after)
+    }
+}
@@ -0,0 +1,219 @@
+use nom::{
+    error::{ErrorKind, ParseError},
+    Err, IResult, InputIter, InputLength, InputTake, InputTakeAtPosition, Needed, Offset, Slice,
+};
+use std::{
+    cmp::min,
+    mem::size_of,
+    ops::{RangeFrom, RangeTo},
+};
+use tracker::{Items, PositionTracker};
+use Spanned;
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: InputLength,
+    Self: Slice<RangeFrom<usize>>,
+{
+    /// Get the span of the data at the position of `derived`, a span made with
+    /// [`Spanned::respan`] from this one or parsed from it
+    ///
+    /// `map` translates the offset in the derived buffer into an offset in the data, the result
+    /// being capped to the length of the data
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset given by `map` can't be used to slice the data, like an offset in the
+    /// middle of a char for a `&str`
+    #[must_use]
+    pub fn map_back<U, Y, D, F>(&self, derived: &Spanned<U, Y, D>, map: F) -> Self
+    where
+        F: FnOnce(usize) -> usize,
+    {
+        let offset = map(derived.offset.saturating_sub(self.offset));
+
+        self.slice(min(offset, self.data.input_len())..)
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    Self: Offset + Slice<RangeTo<usize>>,
+{
+    /// Get the span of what was consumed from this span to get `later`, at the current position,
+    /// like what `recognize` gives for the parsers that went from one to the other
+    ///
+    /// In debug builds, panics if `later` isn't within this span
+    #[must_use]
+    pub fn span_until(&self, later: &Self) -> Self {
+        self.slice(..self.offset(later))
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: Items,
+    X: Clone,
+    C: PositionTracker<T::Item>,
+{
+    /// Get the span over `rest`, that comes right after the items `consumed` in the current data
+    fn advance_over(&self, consumed: &[T::Item], rest: T) -> Self {
+        let (counters, tracker) = self.advance(consumed, rest.items());
+
+        Self {
+            data: rest,
+            offset: self.offset + consumed.len(),
+            line: counters.line,
+            col: counters.col,
+            extra: self.extra.clone(),
+            tracker,
+        }
+    }
+}
+
+/// The iterators are the ones of the data, so for a `&'a str` they borrow `'a` and not the span
+impl<T, X, C> InputIter for Spanned<T, X, C>
+where
+    T: InputIter,
+{
+    type Item = T::Item;
+
+    type Iter = T::Iter;
+
+    type IterElem = T::IterElem;
+
+    fn iter_indices(&self) -> Self::Iter {
+        self.data.iter_indices()
+    }
+
+    fn iter_elements(&self) -> Self::IterElem {
+        self.data.iter_elements()
+    }
+
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.data.position(predicate)
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.data.slice_index(count)
+    }
+}
+
+impl<T, X, C> InputLength for Spanned<T, X, C>
+where
+    T: InputLength,
+{
+    fn input_len(&self) -> usize {
+        self.data.input_len()
+    }
+}
+
+impl<T, X, C> InputTake for Spanned<T, X, C>
+where
+    Self: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+{
+    fn take(&self, count: usize) -> Self {
+        self.slice(..count)
+    }
+
+    fn take_split(&self, count: usize) -> (Self, Self) {
+        (self.slice(count..), self.slice(..count))
+    }
+}
+
+/// Behaves like the impls of nom for `&str` and `&[u8]`: the streaming variants ask for 1 more
+/// item when the predicate never matches, and the `1` variants fail on an empty match
+impl<T, X, C> InputTakeAtPosition for Spanned<T, X, C>
+where
+    T: InputTakeAtPosition + InputLength + InputIter,
+    Self: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>> + Clone,
+{
+    type Item = <T as InputIter>::Item;
+
+    fn split_at_position<P, E: ParseError<Self>>(&self, predicate: P) -> IResult<Self, Self, E>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        match self.data.position(predicate) {
+            Some(n) => Ok(self.take_split(n)),
+            None => Err(Err::Incomplete(Needed::new(1))),
+        }
+    }
+
+    fn split_at_position1<P, E: ParseError<Self>>(
+        &self,
+        predicate: P,
+        e: ErrorKind,
+    ) -> IResult<Self, Self, E>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        match self.data.position(predicate) {
+            Some(0) => Err(Err::Error(E::from_error_kind(self.clone(), e))),
+            Some(n) => Ok(self.take_split(n)),
+            None => Err(Err::Incomplete(Needed::new(1))),
+        }
+    }
+
+    fn split_at_position_complete<P, E: ParseError<Self>>(
+        &self,
+        predicate: P,
+    ) -> IResult<Self, Self, E>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        match self.data.position(predicate) {
+            Some(n) => Ok(self.take_split(n)),
+            None => Ok(self.take_split(self.input_len())),
+        }
+    }
+
+    fn split_at_position1_complete<P, E: ParseError<Self>>(
+        &self,
+        predicate: P,
+        e: ErrorKind,
+    ) -> IResult<Self, Self, E>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        match self.data.position(predicate) {
+            Some(0) => Err(Err::Error(E::from_error_kind(self.clone(), e))),
+            Some(n) => Ok(self.take_split(n)),
+            None => {
+                if self.data.input_len() == 0 {
+                    Err(Err::Error(E::from_error_kind(self.clone(), e)))
+                } else {
+                    Ok(self.take_split(self.input_len()))
+                }
+            }
+        }
+    }
+}
+
+/// The tracker advances over the items of the data, so slices of tokens are spans too
+impl<T, R, X, C> Slice<R> for Spanned<T, X, C>
+where
+    T: Slice<R> + Items + Slice<RangeTo<usize>>,
+    X: Clone,
+    C: PositionTracker<T::Item>,
+{
+    fn slice(&self, range: R) -> Self {
+        let next_data = self.data.slice(range);
+
+        let offset = items_offset(self.data.items(), next_data.items());
+
+        let old_data = self.data.slice(..offset);
+
+        self.advance_over(old_data.items(), next_data)
+    }
+}
+
+/// Get the number of items between the start of `data` and the start of `next`, a slice of it
+fn items_offset<I>(data: &[I], next: &[I]) -> usize {
+    let bytes = next.as_ptr() as usize - data.as_ptr() as usize;
+
+    bytes / size_of::<I>().max(1)
+}
@@ -0,0 +1,104 @@
+use nom::{AsBytes, Input, Needed, Offset};
+use std::cmp::min;
+use tracker::PositionTracker;
+use Spanned;
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: Input + AsBytes,
+    X: Clone,
+    C: PositionTracker,
+{
+    /// Get the span of the data at the position of `derived`, a span made with
+    /// [`Spanned::respan`] from this one or parsed from it
+    ///
+    /// `map` translates the offset in the derived buffer into an offset in the data, the result
+    /// being capped to the length of the data
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset given by `map` can't be used to split the data, like an offset in the
+    /// middle of a char for a `&str`
+    #[must_use]
+    pub fn map_back<U, Y, D, F>(&self, derived: &Spanned<U, Y, D>, map: F) -> Self
+    where
+        F: FnOnce(usize) -> usize,
+    {
+        let offset = map(derived.offset.saturating_sub(self.offset));
+
+        self.take_from(min(offset, self.data.input_len()))
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    T: Input + AsBytes + Offset,
+    X: Clone,
+    C: PositionTracker,
+{
+    /// Get the span of what was consumed from this span to get `later`, at the current position,
+    /// like what `recognize` gives for the parsers that went from one to the other
+    ///
+    /// In debug builds, panics if `later` isn't within this span
+    #[must_use]
+    pub fn span_until(&self, later: &Self) -> Self {
+        self.take(self.offset(later))
+    }
+}
+
+/// The span is advanced in [`Input::take_from`] and [`Input::take_split`], the parsers taking it
+/// from there
+impl<T, X, C> Input for Spanned<T, X, C>
+where
+    T: Input + AsBytes,
+    X: Clone,
+    C: PositionTracker,
+{
+    type Item = T::Item;
+
+    type Iter = T::Iter;
+
+    type IterIndices = T::IterIndices;
+
+    fn input_len(&self) -> usize {
+        self.data.input_len()
+    }
+
+    fn take(&self, index: usize) -> Self {
+        self.respan(self.data.take(index))
+    }
+
+    fn take_from(&self, index: usize) -> Self {
+        let consumed = self.data.take(index);
+
+        self.advance_to(consumed.as_bytes(), self.data.take_from(index))
+    }
+
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        let (rest, consumed) = self.data.take_split(index);
+
+        (
+            self.advance_to(consumed.as_bytes(), rest),
+            self.respan(consumed),
+        )
+    }
+
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.data.position(predicate)
+    }
+
+    fn iter_elements(&self) -> Self::Iter {
+        self.data.iter_elements()
+    }
+
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.data.iter_indices()
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.data.slice_index(count)
+    }
+}
@@ -0,0 +1,13 @@
+use nom_packrat::HasExtraState;
+use Spanned;
+
+/// The results are memoized for the position of the data in the input and for the extra data, so
+/// a result is only reused when the extra data is the same. Without extra data the state is `()`
+impl<T, X, C> HasExtraState<X> for Spanned<T, X, C>
+where
+    X: Clone,
+{
+    fn get_extra_state(&self) -> X {
+        self.extra.clone()
+    }
+}
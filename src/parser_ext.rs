@@ -0,0 +1,89 @@
+use combinator::{consumed_span, with_span};
+use nom::{IResult, InputTake, Parser};
+use position::Position;
+use tracker::CounterStorage;
+use Spanned;
+
+type ParseResult<T, X, C, O, E> = IResult<Spanned<T, X, C>, O, E>;
+type WithSpanResult<T, X, C, O, E> = ParseResult<T, X, C, WithSpan<O>, E>;
+
+/// The output of a parser with the position where it started and the position where it stopped,
+/// as given by [`SpannedParserExt::spanned`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WithSpan<O> {
+    value: O,
+    start: Position,
+    end: Position,
+}
+
+impl<O> WithSpan<O> {
+    #[must_use]
+    pub fn new(value: O, start: Position, end: Position) -> Self {
+        Self { value, start, end }
+    }
+
+    /// Get the output of the parser
+    pub fn value(&self) -> &O {
+        &self.value
+    }
+
+    /// Get the position where the parser started
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Get the position where the parser stopped, the one of the rest of the input
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Get the output of the parser, dropping the positions
+    pub fn into_value(self) -> O {
+        self.value
+    }
+
+    /// Map the output, keeping the positions
+    pub fn map<U, F>(self, f: F) -> WithSpan<U>
+    where
+        F: FnOnce(O) -> U,
+    {
+        WithSpan::new(f(self.value), self.start, self.end)
+    }
+}
+
+/// Method versions of [`with_span`] and [`consumed_span`], for every nom parser of spans
+pub trait SpannedParserExt<T, X, C, O, E>: Parser<Spanned<T, X, C>, O, E> + Sized {
+    /// Give the output of the parser with the position where it started and where it stopped
+    fn spanned(self) -> impl FnMut(Spanned<T, X, C>) -> WithSpanResult<T, X, C, O, E>
+    where
+        C: CounterStorage,
+    {
+        let mut parser = with_span(self);
+
+        move |input| {
+            let (rest, (value, start, end)) = parser(input)?;
+
+            Ok((rest, WithSpan::new(value, start, end)))
+        }
+    }
+
+    /// Map the output of the parser with the span of what it consumed
+    fn map_with_span<U, F>(
+        self,
+        mut f: F,
+    ) -> impl FnMut(Spanned<T, X, C>) -> ParseResult<T, X, C, U, E>
+    where
+        Spanned<T, X, C>: InputTake + Clone,
+        F: FnMut(O, Spanned<T, X, C>) -> U,
+    {
+        let mut parser = consumed_span(self);
+
+        move |input| {
+            let (rest, (consumed, value)) = parser(input)?;
+
+            Ok((rest, f(value, consumed)))
+        }
+    }
+}
+
+impl<T, X, C, O, E, P> SpannedParserExt<T, X, C, O, E> for P where P: Parser<Spanned<T, X, C>, O, E> {}
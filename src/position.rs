@@ -0,0 +1,115 @@
+use std::fmt;
+
+/// The identifier of the input a span is over, to tell apart the positions in several files
+///
+/// It's `SourceId(0)` for the spans that weren't given one
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceId(pub u32);
+
+impl SourceId {
+    /// Check if it's the id of the spans that weren't given one
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// Display the id as `#3`
+impl fmt::Display for SourceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// A position in the input, as returned by [`Spanned::pos`](crate::Spanned::pos)
+///
+/// Positions are ordered by their source then their byte offset, which makes the comparison
+/// meaningful as long as both positions come from the same input
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    /// The input the position is in, see [`Spanned::with_source_id`](crate::Spanned::with_source_id)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "SourceId::is_default")
+    )]
+    pub source: SourceId,
+    /// The byte offset, starting at 0
+    pub offset: usize,
+    /// The line number, starting at 1
+    pub line: usize,
+    /// The column number, starting at 1
+    pub col: usize,
+}
+
+impl Position {
+    #[must_use]
+    pub fn new(line: usize, col: usize, offset: usize) -> Self {
+        Self {
+            source: SourceId::default(),
+            offset,
+            line,
+            col,
+        }
+    }
+
+    /// Get the same position in the input `source`
+    #[must_use]
+    pub fn with_source(self, source: SourceId) -> Self {
+        Self { source, ..self }
+    }
+
+    /// Get the line number, starting at 0
+    #[must_use]
+    pub fn line0(&self) -> usize {
+        self.line.saturating_sub(1)
+    }
+
+    /// Get the column number, starting at 0
+    #[must_use]
+    pub fn col0(&self) -> usize {
+        self.col.saturating_sub(1)
+    }
+
+    /// Translate a position computed relatively to an embedded fragment into a position in the
+    /// input containing it, `base` being the position where the fragment starts
+    ///
+    /// On the first line of the fragment the columns are added to the `base` one, while on the
+    /// lines after they are kept as is. The position is in the source of `base`
+    #[must_use]
+    pub fn rebase_onto(self, base: Position) -> Self {
+        if self.line == 1 {
+            Self {
+                source: base.source,
+                offset: base.offset + self.offset,
+                line: base.line,
+                col: base.col + self.col - 1,
+            }
+        } else {
+            Self {
+                source: base.source,
+                offset: base.offset + self.offset,
+                line: base.line + self.line - 1,
+                col: self.col,
+            }
+        }
+    }
+}
+
+/// Display the position as `line:col`, or as `line 1, column 2` with the alternate flag. The
+/// source is shown when it's not the default one, as `#3:line:col` or `line 1, column 2 in #3`
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (f.alternate(), self.source.is_default()) {
+            (true, true) => write!(f, "line {}, column {}", self.line, self.col),
+            (true, false) => write!(
+                f,
+                "line {}, column {} in {}",
+                self.line, self.col, self.source
+            ),
+            (false, true) => write!(f, "{}:{}", self.line, self.col),
+            (false, false) => write!(f, "{}:{}:{}", self.source, self.line, self.col),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use nom_recursive::{HasRecursiveInfo, RecursiveInfo};
+use Spanned;
+
+/// The [`RecursiveInfo`] is kept in the extra data, like with `Spanned<&str, RecursiveInfo>`
+impl<T, X, C> HasRecursiveInfo for Spanned<T, X, C>
+where
+    X: HasRecursiveInfo,
+{
+    fn get_recursive_info(&self) -> RecursiveInfo {
+        self.extra.get_recursive_info()
+    }
+
+    fn set_recursive_info(self, info: RecursiveInfo) -> Self {
+        self.map_extra(|extra| extra.set_recursive_info(info))
+    }
+}
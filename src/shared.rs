@@ -0,0 +1,476 @@
+#[cfg(feature = "nom8")]
+use nom::Input;
+use nom::{AsBytes, Compare, CompareResult, ExtendInto, FindSubstring, Needed, Offset, ParseTo};
+#[cfg(feature = "nom7")]
+use nom::{InputIter, InputLength, InputTake, Slice, UnspecializedInput};
+#[cfg(feature = "nom7")]
+use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use std::{borrow::Cow, fmt, ops::Deref, rc::Rc, str::FromStr, sync::Arc};
+use tracker::Items;
+use Spanned;
+
+/// A `str` shared behind a pointer like an [`Arc`] or an [`Rc`], together with the range of it that
+/// is still to parse
+///
+/// Slicing only moves the range, so a `Spanned<Shared<P>>` owns its data and can outlive the
+/// original input, or be sent to another thread with an `Arc`
+///
+/// ```ignore
+/// use nom_span::{ArcStr, Spanned};
+/// use std::sync::Arc;
+///
+/// let span = Spanned::new(ArcStr::from(Arc::from("hello world")), true);
+/// ```
+#[derive(Clone)]
+pub struct Shared<P> {
+    data: P,
+    start: usize,
+    end: usize,
+}
+
+/// A `str` shared between threads, see [`Shared`]
+pub type ArcStr = Shared<Arc<str>>;
+
+/// A `str` shared on a single thread, see [`Shared`]
+pub type RcStr = Shared<Rc<str>>;
+
+/// A `Cow<str>` that can be parsed, see [`Shared`] and [`CowData`]
+pub type CowStr<'a> = Shared<CowData<'a>>;
+
+/// The data of a [`CowStr`], borrowed or owned behind an [`Arc`] so that slicing it doesn't copy
+/// the owned `str`
+#[derive(Clone, Debug)]
+pub enum CowData<'a> {
+    Borrowed(&'a str),
+    Owned(Arc<str>),
+}
+
+impl Deref for CowData<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            CowData::Borrowed(data) => data,
+            CowData::Owned(data) => data,
+        }
+    }
+}
+
+/// An owned `String` is moved behind an [`Arc`], which copies it once
+impl<'a> From<Cow<'a, str>> for CowStr<'a> {
+    fn from(data: Cow<'a, str>) -> Self {
+        Self::new(match data {
+            Cow::Borrowed(data) => CowData::Borrowed(data),
+            Cow::Owned(data) => CowData::Owned(data.into()),
+        })
+    }
+}
+
+impl<'a, X, C> From<Spanned<Cow<'a, str>, X, C>> for Spanned<CowStr<'a>, X, C> {
+    fn from(span: Spanned<Cow<'a, str>, X, C>) -> Self {
+        span.map_data(CowStr::from)
+    }
+}
+
+impl<P> Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    /// Share the whole `str` of `data`
+    pub fn new(data: P) -> Self {
+        let end = data.len();
+
+        Self {
+            data,
+            start: 0,
+            end,
+        }
+    }
+
+    /// Get the part of the `str` that is covered
+    pub fn as_str(&self) -> &str {
+        &self.data[self.start..self.end]
+    }
+
+    /// Get the pointer to the whole `str`
+    pub fn source(&self) -> &P {
+        &self.data
+    }
+}
+
+impl<P> Shared<P>
+where
+    P: Deref<Target = str> + Clone,
+{
+    /// Get the shared `str` covering `part`, that must come from [`Shared::as_str`]
+    fn share(&self, part: &str) -> Self {
+        let start = part.as_ptr() as usize - self.data.as_ptr() as usize;
+
+        Self {
+            data: self.data.clone(),
+            start,
+            end: start + part.len(),
+        }
+    }
+}
+
+impl From<Arc<str>> for ArcStr {
+    fn from(data: Arc<str>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<Rc<str>> for RcStr {
+    fn from(data: Rc<str>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<P> Deref for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<P> fmt::Debug for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<P> fmt::Display for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+/// Shared strs are equal when they cover the same text, wherever it's stored
+impl<P, Q> PartialEq<Shared<Q>> for Shared<P>
+where
+    P: Deref<Target = str>,
+    Q: Deref<Target = str>,
+{
+    fn eq(&self, other: &Shared<Q>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<P> Eq for Shared<P> where P: Deref<Target = str> {}
+
+impl<P> PartialEq<str> for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<P> PartialEq<&str> for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<P> Items for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    type Item = u8;
+
+    fn items(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+/// The chars of a [`Shared`] str, owning a pointer to it
+#[derive(Clone)]
+pub struct SharedChars<P> {
+    data: Shared<P>,
+}
+
+impl<P> Iterator for SharedChars<P>
+where
+    P: Deref<Target = str>,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.data.as_str().chars().next()?;
+        self.data.start += c.len_utf8();
+
+        Some(c)
+    }
+}
+
+/// The chars of a [`Shared`] str with their byte index, owning a pointer to it
+#[derive(Clone)]
+pub struct SharedCharIndices<P> {
+    chars: SharedChars<P>,
+    start: usize,
+}
+
+impl<P> Iterator for SharedCharIndices<P>
+where
+    P: Deref<Target = str>,
+{
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<(usize, char)> {
+        let index = self.chars.data.start - self.start;
+
+        self.chars.next().map(|c| (index, c))
+    }
+}
+
+impl<P> Shared<P>
+where
+    P: Clone,
+{
+    fn chars_owned(&self) -> SharedChars<P> {
+        SharedChars { data: self.clone() }
+    }
+
+    fn char_indices_owned(&self) -> SharedCharIndices<P> {
+        SharedCharIndices {
+            chars: self.chars_owned(),
+            start: self.start,
+        }
+    }
+}
+
+impl<P> AsBytes for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+#[cfg(feature = "nom7")]
+impl<P> InputLength for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn input_len(&self) -> usize {
+        self.as_str().len()
+    }
+}
+
+/// The iterators own a clone of the pointer, so they don't borrow the shared str
+#[cfg(feature = "nom7")]
+impl<P> InputIter for Shared<P>
+where
+    P: Deref<Target = str> + Clone,
+{
+    type Item = char;
+
+    type Iter = SharedCharIndices<P>;
+
+    type IterElem = SharedChars<P>;
+
+    fn iter_indices(&self) -> Self::Iter {
+        self.char_indices_owned()
+    }
+
+    fn iter_elements(&self) -> Self::IterElem {
+        self.chars_owned()
+    }
+
+    fn position<F>(&self, predicate: F) -> Option<usize>
+    where
+        F: Fn(char) -> bool,
+    {
+        self.as_str().position(predicate)
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.as_str().slice_index(count)
+    }
+}
+
+#[cfg(feature = "nom7")]
+impl<P> InputTake for Shared<P>
+where
+    P: Deref<Target = str> + Clone,
+{
+    fn take(&self, count: usize) -> Self {
+        self.share(&self.as_str()[..count])
+    }
+
+    fn take_split(&self, count: usize) -> (Self, Self) {
+        let (prefix, suffix) = self.as_str().split_at(count);
+
+        (self.share(suffix), self.share(prefix))
+    }
+}
+
+/// [`nom::InputTakeAtPosition`] comes from the impl of nom for the inputs that aren't `&str` or
+/// `&[u8]`, that works on chars like the one for `&str`
+#[cfg(feature = "nom7")]
+impl<P> UnspecializedInput for Shared<P> {}
+
+#[cfg(feature = "nom7")]
+macro_rules! impl_slice {
+    ($($range:ty),*) => {$(
+        impl<P> Slice<$range> for Shared<P>
+        where
+            P: Deref<Target = str> + Clone,
+        {
+            fn slice(&self, range: $range) -> Self {
+                self.share(&self.as_str()[range])
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "nom7")]
+impl_slice!(Range<usize>, RangeTo<usize>, RangeFrom<usize>, RangeFull);
+
+#[cfg(feature = "nom8")]
+impl<P> Input for Shared<P>
+where
+    P: Deref<Target = str> + Clone,
+{
+    type Item = char;
+
+    type Iter = SharedChars<P>;
+
+    type IterIndices = SharedCharIndices<P>;
+
+    fn input_len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    fn take(&self, index: usize) -> Self {
+        self.share(&self.as_str()[..index])
+    }
+
+    fn take_from(&self, index: usize) -> Self {
+        self.share(&self.as_str()[index..])
+    }
+
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        let (prefix, suffix) = self.as_str().split_at(index);
+
+        (self.share(suffix), self.share(prefix))
+    }
+
+    fn position<F>(&self, predicate: F) -> Option<usize>
+    where
+        F: Fn(char) -> bool,
+    {
+        self.as_str().position(predicate)
+    }
+
+    fn iter_elements(&self) -> Self::Iter {
+        self.chars_owned()
+    }
+
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.char_indices_owned()
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.as_str().slice_index(count)
+    }
+}
+
+impl<'b, P> Compare<&'b str> for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.as_str().compare(t)
+    }
+
+    fn compare_no_case(&self, t: &'b str) -> CompareResult {
+        self.as_str().compare_no_case(t)
+    }
+}
+
+impl<P, Q> Compare<Shared<Q>> for Shared<P>
+where
+    P: Deref<Target = str>,
+    Q: Deref<Target = str>,
+{
+    fn compare(&self, t: Shared<Q>) -> CompareResult {
+        self.as_str().compare(t.as_str())
+    }
+
+    fn compare_no_case(&self, t: Shared<Q>) -> CompareResult {
+        self.as_str().compare_no_case(t.as_str())
+    }
+}
+
+/// Compare with a tag, like the impls for `Spanned<&str>`
+impl<'b, P, X, C> Compare<&'b str> for Spanned<Shared<P>, X, C>
+where
+    P: Deref<Target = str>,
+{
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.data.compare(t)
+    }
+
+    fn compare_no_case(&self, t: &'b str) -> CompareResult {
+        self.data.compare_no_case(t)
+    }
+}
+
+impl<'b, P> FindSubstring<&'b str> for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.as_str().find(substr)
+    }
+}
+
+impl<P> ExtendInto for Shared<P>
+where
+    P: Deref<Target = str>,
+{
+    type Item = char;
+
+    type Extender = String;
+
+    fn new_builder(&self) -> String {
+        String::new()
+    }
+
+    fn extend_into(&self, acc: &mut String) {
+        acc.push_str(self.as_str());
+    }
+}
+
+/// The offset is counted in bytes, `second` having to be a part of `self`
+impl<P> Offset for Shared<P> {
+    fn offset(&self, second: &Self) -> usize {
+        second.start - self.start
+    }
+}
+
+impl<P, R> ParseTo<R> for Shared<P>
+where
+    P: Deref<Target = str>,
+    R: FromStr,
+{
+    fn parse_to(&self) -> Option<R> {
+        self.as_str().parse().ok()
+    }
+}
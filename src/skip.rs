@@ -0,0 +1,60 @@
+use memchr::memchr;
+use nom::AsBytes;
+use tracker::PositionTracker;
+use Spanned;
+
+impl<X, C> Spanned<&str, X, C>
+where
+    X: Clone,
+    C: PositionTracker,
+{
+    /// Get the span after the whitespace at the start of the data, as `char::is_whitespace`
+    /// tells, the newlines skipped being counted like with `slice`
+    #[must_use]
+    pub fn skip_whitespace(&self) -> Self {
+        let end = self
+            .data
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(self.data.len());
+
+        self.advance_to(&self.data.as_bytes()[..end], &self.data[end..])
+    }
+}
+
+macro_rules! impl_skip {
+    ($($fragment:ty),*) => {
+        $(
+            impl<'a, X, C> Spanned<$fragment, X, C>
+            where
+                X: Clone,
+                C: PositionTracker,
+            {
+                /// Get the span after the spaces, tabs, `\r` and `\n` at the start of the data,
+                /// like after nom's `multispace0` but without going through a parser
+                #[must_use]
+                pub fn skip_ascii_whitespace(&self) -> Self {
+                    let bytes = self.data.as_bytes();
+                    let end = bytes
+                        .iter()
+                        .position(|byte| !matches!(byte, b' ' | b'\t' | b'\r' | b'\n'))
+                        .unwrap_or(bytes.len());
+
+                    self.advance_to(&bytes[..end], &self.data[end..])
+                }
+
+                /// Get the span at the start of the next line, after the next `\n`, even if the
+                /// current position is at the start of a line. On the last line it's the end of
+                /// the data
+                #[must_use]
+                pub fn skip_to_next_line(&self) -> Self {
+                    let bytes = self.data.as_bytes();
+                    let end = memchr(b'\n', bytes).map_or(bytes.len(), |newline| newline + 1);
+
+                    self.advance_to(&bytes[..end], &self.data[end..])
+                }
+            }
+        )*
+    };
+}
+
+impl_skip!(&'a str, &'a [u8]);
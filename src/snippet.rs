@@ -0,0 +1,138 @@
+use bytecount;
+use line_index::floor_char_boundary;
+use memchr::memchr;
+use position::Position;
+use span::Span;
+use std::fmt::Write;
+use tracker::CounterStorage;
+use Spanned;
+
+impl Position {
+    /// Render the line of `source` at this position with a caret under it, after a `line:col`
+    /// header with `message`
+    ///
+    /// `source` is the input the position comes from. With the `unicode-width` feature the caret
+    /// is aligned on the cells the chars take in a terminal, otherwise every char takes one, and
+    /// the tabs are kept so the caret is aligned whatever their width
+    #[must_use]
+    pub fn render_snippet(&self, source: &str, message: &str) -> String {
+        render(&self.to_string(), source, *self, *self, message)
+    }
+}
+
+impl Span {
+    /// Render the lines of `source` covered by the span, underlining what it covers, after a
+    /// `line:col` header with `message`
+    ///
+    /// The alignment is the one of [`Position::render_snippet`]. An empty span gets a caret
+    #[must_use]
+    pub fn render_snippet(&self, source: &str, message: &str) -> String {
+        render(
+            &self.start.to_string(),
+            source,
+            self.start,
+            self.end,
+            message,
+        )
+    }
+}
+
+impl<T, X, C> Spanned<T, X, C>
+where
+    C: CounterStorage,
+{
+    /// Render the line of `source` at the current position with a caret under it, like
+    /// [`Position::render_snippet`]
+    #[must_use]
+    pub fn render_snippet(&self, source: &str, message: &str) -> String {
+        self.pos().render_snippet(source, message)
+    }
+}
+
+/// Render the lines of `source` between `start` and `end` after a `location: message` header
+///
+/// The offsets are clamped to the end of `source` and floored to a char boundary, like
+/// [`LineIndex::position`](crate::LineIndex::position) clamps them
+pub(crate) fn render(
+    location: &str,
+    source: &str,
+    mut start: Position,
+    mut end: Position,
+    message: &str,
+) -> String {
+    start.offset = floor_char_boundary(source, start.offset);
+    end.offset = floor_char_boundary(source, end.offset).max(start.offset);
+    // The last byte covered, to not show the line after a range ending with a newline
+    let last = if end.offset > start.offset {
+        end.offset - 1
+    } else {
+        start.offset
+    };
+    let last_line = start.line + bytecount::count(&source.as_bytes()[start.offset..last], b'\n');
+    let gutter = last_line.to_string().len();
+
+    let mut snippet = format!("{location}: {message}\n{:gutter$} |\n", "");
+    let mut line_start = source[..start.offset]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1);
+
+    for line in start.line..=last_line {
+        let rest = &source[line_start..];
+        let len = memchr(b'\n', rest.as_bytes()).unwrap_or(rest.len());
+        let text = rest[..len].strip_suffix('\r').unwrap_or(&rest[..len]);
+
+        let from = start.offset.saturating_sub(line_start).min(text.len());
+        let to = (end.offset - line_start).min(text.len()).max(from);
+
+        let padding = padding(&text[..from]);
+        let underline: usize = text[from..to].chars().map(width).sum();
+        let marker = if line == start.line {
+            format!("^{}", "~".repeat(underline.saturating_sub(1)))
+        } else {
+            "~".repeat(underline)
+        };
+
+        let _ = writeln!(snippet, "{line:>gutter$} | {text}");
+        let _ = writeln!(snippet, "{:gutter$} | {padding}{}", "", colored(&marker));
+
+        line_start += len + 1;
+    }
+
+    snippet
+}
+
+/// Get the blanks to put under `text` to align what comes after it, keeping the tabs
+pub(crate) fn padding(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c == '\t' {
+                "\t".into()
+            } else {
+                " ".repeat(width(c))
+            }
+        })
+        .collect()
+}
+
+/// Get the number of cells `c` takes in a terminal
+#[cfg(feature = "unicode-width")]
+fn width(c: char) -> usize {
+    ::width::count(c.encode_utf8(&mut [0; 4]).as_bytes())
+}
+
+/// Get the number of cells `c` takes in a terminal, every char taking one without the
+/// `unicode-width` feature
+#[cfg(not(feature = "unicode-width"))]
+fn width(_: char) -> usize {
+    1
+}
+
+#[cfg(feature = "color")]
+fn colored(marker: &str) -> String {
+    format!("\x1b[1;31m{marker}\x1b[0m")
+}
+
+#[cfg(not(feature = "color"))]
+fn colored(marker: &str) -> String {
+    marker.into()
+}
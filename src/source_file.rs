@@ -0,0 +1,115 @@
+use line_index::{self, LineIndex};
+use position::{Position, SourceId};
+use snippet;
+use span::Span;
+use std::{borrow::Cow, fs, io, path::Path};
+use Spanned;
+
+/// A file read in memory with its name, owning the input the spans parsed from it borrow
+///
+/// It's the input to keep alive while parsing and to render the errors with, the name being the
+/// one shown in their header
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SourceFile {
+    name: String,
+    contents: String,
+    line_starts: Vec<usize>,
+    source: SourceId,
+}
+
+impl SourceFile {
+    /// Create a file named `name` holding `contents`, with the default source id
+    pub fn new<N>(name: N, contents: String) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            line_starts: line_index::line_starts(&contents),
+            contents,
+            source: SourceId::default(),
+        }
+    }
+
+    /// Read the file at `path`, named after its path
+    ///
+    /// # Errors
+    ///
+    /// The error of the read, like when the file doesn't exist or isn't valid UTF-8
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+
+        Ok(Self::new(path.display().to_string(), contents))
+    }
+
+    /// Get the same file with the id `source`, given to its spans
+    #[must_use]
+    pub fn with_source_id(mut self, source: SourceId) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Get the id given to the spans of the file
+    #[must_use]
+    pub fn source_id(&self) -> SourceId {
+        self.source
+    }
+
+    /// Get the name of the file, its path when it was opened
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the whole contents of the file
+    #[must_use]
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    /// Get a span over the whole contents, with the source id of the file
+    #[must_use]
+    pub fn span(&self) -> Spanned<&str> {
+        Spanned::new(self.contents.as_str(), true).with_source_id(self.source)
+    }
+
+    /// Get the index of the lines of the contents, to get the positions of byte offsets in the
+    /// file. The lines are found once when the file is created, and the index borrows them
+    #[must_use]
+    pub fn line_index(&self) -> LineIndex<'_> {
+        LineIndex::with_line_starts(&self.contents, Cow::Borrowed(&self.line_starts), true)
+            .with_source_id(self.source)
+    }
+
+    /// Get the number of lines, the empty line after a last newline being counted like
+    /// [`LineIndex::line_count`] does
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.line_index().line_count()
+    }
+
+    /// Get the text of the line `number`, starting at 1, without its `\n` or `\r\n`
+    #[must_use]
+    pub fn line(&self, number: usize) -> Option<&str> {
+        self.line_index().line(number)
+    }
+
+    /// Get the text of the line `position` is on, without its `\n` or `\r\n`
+    #[must_use]
+    pub fn line_at(&self, position: Position) -> Option<&str> {
+        self.line(position.line)
+    }
+
+    /// Render the lines of the file covered by `span` like [`Span::render_snippet`], the header
+    /// being `name:line:col`
+    #[must_use]
+    pub fn render_snippet(&self, span: Span, message: &str) -> String {
+        let location = format!("{}:{}:{}", self.name, span.start.line, span.start.col);
+
+        snippet::render(&location, &self.contents, span.start, span.end, message)
+    }
+}
@@ -0,0 +1,97 @@
+use nom::AsBytes;
+use position::{Position, SourceId};
+use std::{
+    cmp::{max, min},
+    ops::Range,
+};
+use tracker::{CounterStorage, PositionTracker};
+use Spanned;
+
+/// A range of the input, from a start [`Position`] to the end one, to keep in the nodes of an AST
+/// without the data
+///
+/// The end is excluded, and spans are ordered by their start then their end, so by their source
+/// first. Like for the positions, the comparisons are meaningful as long as both spans come from
+/// the same input
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    /// The position of the first byte
+    pub start: Position,
+    /// The position right after the last byte
+    pub end: Position,
+}
+
+impl Span {
+    #[must_use]
+    pub fn new(start: Position, end: Position) -> Self {
+        debug_assert!(start <= end, "the span ends before it starts");
+        debug_assert_eq!(start.source, end.source, "the span is over two sources");
+
+        Self { start, end }
+    }
+
+    /// Get the span from the start of `start` to the start of `end`, like the span of what was
+    /// consumed from `start` to get `end`
+    #[must_use]
+    pub fn between<T, U, X, Y, C, D>(start: &Spanned<T, X, C>, end: &Spanned<U, Y, D>) -> Self
+    where
+        C: CounterStorage,
+        D: CounterStorage,
+    {
+        Self::new(start.pos(), end.pos())
+    }
+
+    /// Get the span of the data of `fragment`
+    #[must_use]
+    pub fn of<T, X, C>(fragment: &Spanned<T, X, C>) -> Self
+    where
+        T: AsBytes,
+        C: PositionTracker,
+    {
+        Self::new(fragment.pos(), fragment.end_position())
+    }
+
+    /// Get the id of the input the span is over
+    #[must_use]
+    pub fn source(&self) -> SourceId {
+        self.start.source
+    }
+
+    /// Get the smallest span covering both spans, and what's between them. The spans must be
+    /// over the same source, which is checked in debug builds
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self::new(min(self.start, other.start), max(self.end, other.end))
+    }
+
+    /// Check if the byte at `pos` is in the span, an empty span containing nothing, and a span
+    /// containing nothing of another source
+    #[must_use]
+    pub fn contains(&self, pos: Position) -> bool {
+        self.source() == pos.source
+            && self.start.offset <= pos.offset
+            && pos.offset < self.end.offset
+    }
+
+    /// Check if both spans have bytes in common, an empty span intersecting nothing, and nor
+    /// does a span of another source
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.source() == other.source()
+            && max(self.start.offset, other.start.offset) < min(self.end.offset, other.end.offset)
+    }
+
+    /// Get the length of the span in bytes
+    #[must_use]
+    pub fn len_bytes(&self) -> usize {
+        self.end.offset - self.start.offset
+    }
+}
+
+/// Get the range of bytes of the span in the input
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start.offset..span.end.offset
+    }
+}
@@ -0,0 +1,39 @@
+use nom_supreme::{
+    error::GenericErrorTree,
+    final_parser::{Location, RecreateContext},
+};
+use position::Position;
+use tracker::CounterStorage;
+use Spanned;
+
+/// Replace the spans of an error tree by their position, so the tree can be displayed with the
+/// line and the column of every error
+pub fn locate_error_tree<T, X, C, Tag, Context, E>(
+    tree: GenericErrorTree<Spanned<T, X, C>, Tag, Context, E>,
+) -> GenericErrorTree<Position, Tag, Context, E>
+where
+    C: CounterStorage,
+{
+    tree.map_locations(|span| span.pos())
+}
+
+/// The tail already knows its position, so `final_parser` can give positions without recounting
+/// the input
+impl<T, X, C> RecreateContext<Spanned<T, X, C>> for Position
+where
+    C: CounterStorage,
+{
+    fn recreate_context(_original_input: Spanned<T, X, C>, tail: Spanned<T, X, C>) -> Self {
+        tail.pos()
+    }
+}
+
+/// The column is the one of the tail, so it's counted in the unit of its tracker
+impl<T, X, C> RecreateContext<Spanned<T, X, C>> for Location {
+    fn recreate_context(_original_input: Spanned<T, X, C>, tail: Spanned<T, X, C>) -> Self {
+        Location {
+            line: tail.line(),
+            column: tail.col(),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use tracker::{CounterStorage, Counters, PositionTracker};
+use Spanned;
+
+/// Where a token starts in the source it was lexed from
+pub trait TokenPosition {
+    /// The line number, starting at 1
+    fn line(&self) -> usize;
+
+    /// The column number, starting at 1
+    fn col(&self) -> usize;
+}
+
+/// Track a span over tokens with the position of the next token in the source, see
+/// [`TokenPosition`]
+///
+/// The offset of the span is counted in tokens. Once every token is consumed, the position stays
+/// on the last one. Nothing but the line and the column is kept, see [`CounterStorage`]
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub struct TokenTracker;
+
+impl CounterStorage for TokenTracker {}
+
+impl<T> PositionTracker<T> for TokenTracker
+where
+    T: TokenPosition,
+{
+    fn advance(&mut self, counters: &mut Counters, consumed: &[T], rest: &[T]) {
+        let Some(token) = rest.first().or_else(|| consumed.last()) else {
+            return;
+        };
+
+        counters.line = token.line();
+        counters.col = token.col();
+    }
+}
+
+/// Count every element of the data as a column, on a single line. It's a fallback to track a
+/// span over tokens that don't know their position
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub struct ElementTracker;
+
+impl CounterStorage for ElementTracker {}
+
+impl<T> PositionTracker<T> for ElementTracker {
+    fn advance(&mut self, counters: &mut Counters, consumed: &[T], _rest: &[T]) {
+        counters.col += consumed.len();
+    }
+}
+
+impl<'a, T> Spanned<&'a [T], (), TokenTracker>
+where
+    T: TokenPosition,
+{
+    /// Wrap tokens in a span starting at the position of the first one, or at `1:1` if there's
+    /// none
+    pub fn from_tokens(tokens: &'a [T]) -> Self {
+        let span = Self::with_tracker(tokens, TokenTracker);
+        let (counters, _) = span.advance(&[], tokens);
+
+        Self {
+            line: counters.line,
+            col: counters.col,
+            ..span
+        }
+    }
+}
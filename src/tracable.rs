@@ -0,0 +1,53 @@
+use nom::AsBytes;
+use nom_tracable::{FragmentDisplay, HasTracableInfo, Tracable, TracableInfo};
+use Spanned;
+
+/// The [`TracableInfo`] is kept in the extra data, like with `Spanned<&str, TracableInfo>`
+impl<T, X, C> HasTracableInfo for Spanned<T, X, C>
+where
+    X: HasTracableInfo,
+{
+    fn get_tracable_info(&self) -> TracableInfo {
+        self.extra.get_tracable_info()
+    }
+
+    fn set_tracable_info(self, info: TracableInfo) -> Self {
+        self.map_extra(|extra| extra.set_tracable_info(info))
+    }
+}
+
+/// The traces show the line and the column of the input, then the start of its data
+impl<T, X, C> Tracable for Spanned<T, X, C>
+where
+    T: FragmentDisplay + AsBytes,
+    X: HasTracableInfo,
+{
+    fn inc_depth(self) -> Self {
+        let info = self.get_tracable_info();
+        let depth = info.depth + 1;
+
+        self.set_tracable_info(info.depth(depth))
+    }
+
+    fn dec_depth(self) -> Self {
+        let info = self.get_tracable_info();
+        let depth = info.depth - 1;
+
+        self.set_tracable_info(info.depth(depth))
+    }
+
+    fn format(&self) -> String {
+        let info = self.get_tracable_info();
+        let position = format!("{}:{}", self.line(), self.col());
+
+        format!(
+            "{:<8} : {}",
+            position,
+            self.data.display(info.fragment_width)
+        )
+    }
+
+    fn header(&self) -> String {
+        format!("{:<8} : {}", "line:col", "fragment")
+    }
+}
@@ -0,0 +1,611 @@
+use bytecount::num_chars;
+use encoding::{ColumnUnit, Encoding};
+#[cfg(feature = "unicode-segmentation")]
+use grapheme;
+use memchr::{memchr, Memchr, Memchr2, Memchr3};
+use position::SourceId;
+use std::{cmp::min, convert::TryFrom, num::NonZeroU8, str};
+#[cfg(feature = "unicode-width")]
+use width;
+
+/// What a [`PositionTracker`] keeps up to date while the input is consumed
+///
+/// The byte offset isn't part of it as it's always counted by [`Spanned`](crate::Spanned) itself.
+/// The counters other than the line and the column are only kept by the trackers implementing
+/// [`KeepCounters`]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Counters {
+    /// The line number, starting at 1
+    pub line: usize,
+    /// The column number, starting at 1
+    pub col: usize,
+    /// The offset in chars
+    pub char_offset: usize,
+    /// The column number counted in bytes, starting at 1
+    pub byte_col: usize,
+    /// The continuation bytes still to come for the char cut at the end of what was consumed, with
+    /// [`CUT_LINE_ENDING`] set when the char is a line ending, the new line starting with it
+    cut: u8,
+}
+
+/// Set in [`Counters::cut`] when the cut char is a line ending, so the bytes ending it take no
+/// byte column on the new line
+const CUT_LINE_ENDING: u8 = 0x80;
+
+/// The cut of a span whose tracker keeps none: up to 3 continuation bytes starting what's consumed
+/// are taken as the end of a char cut by the previous slice
+const UNKNOWN_CUT: u8 = 3;
+
+impl Counters {
+    /// As the bytes before the starting column aren't known, the byte column starts at `col`
+    pub(crate) fn new(line: usize, col: usize, char_offset: usize) -> Self {
+        Self {
+            line,
+            col,
+            char_offset,
+            byte_col: col,
+            cut: 0,
+        }
+    }
+
+    /// Get the counters of a span at `line` and `col` and at the byte `offset`, with what its
+    /// tracker keeps of the others
+    pub(crate) fn load(
+        line: usize,
+        col: usize,
+        offset: usize,
+        kept: Option<&KeptCounters>,
+    ) -> Self {
+        match kept {
+            Some(kept) => Self {
+                line,
+                col,
+                char_offset: kept.char_offset,
+                byte_col: kept.byte_col,
+                cut: kept.cut,
+            },
+            None => Self {
+                cut: UNKNOWN_CUT,
+                ..Self::new(line, col, offset)
+            },
+        }
+    }
+
+    /// Keep the counters other than the line and the column in `kept`
+    pub(crate) fn keep(&self, kept: &mut KeptCounters) {
+        kept.char_offset = self.char_offset;
+        kept.byte_col = self.byte_col;
+        kept.cut = self.cut;
+    }
+}
+
+/// The [`Counters`] a tracker keeps for its span besides the line and the column, which the span
+/// keeps itself, with the [`SourceId`] of the input
+///
+/// A tracker keeping them starts with the default ones, at the start of the input
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub struct KeptCounters {
+    char_offset: usize,
+    byte_col: usize,
+    cut: u8,
+    source: SourceId,
+}
+
+impl KeptCounters {
+    /// As the bytes before the starting column aren't known, the byte column starts at `col`
+    pub(crate) fn new(col: usize, char_offset: usize, source: SourceId) -> Self {
+        Self {
+            char_offset,
+            byte_col: col,
+            cut: 0,
+            source,
+        }
+    }
+
+    pub(crate) fn char_offset(&self) -> usize {
+        self.char_offset
+    }
+
+    pub(crate) fn byte_col(&self) -> usize {
+        self.byte_col
+    }
+
+    pub(crate) fn source(&self) -> SourceId {
+        self.source
+    }
+
+    pub(crate) fn set_source(&mut self, source: SourceId) {
+        self.source = source;
+    }
+}
+
+impl Default for KeptCounters {
+    fn default() -> Self {
+        Self::new(1, 0, SourceId::default())
+    }
+}
+
+/// A tracker keeping the [`KeptCounters`] of its span, which gives the span a char offset, a byte
+/// column and a [`SourceId`]
+///
+/// [`Dynamic`](crate::Dynamic) and [`RecordTracker`] keep them, while [`Utf8`](crate::Utf8),
+/// [`Ascii`](crate::Ascii) and the token trackers keep nothing, so their spans only take the room
+/// of the line and the column
+pub trait KeepCounters {
+    fn kept(&self) -> &KeptCounters;
+
+    fn kept_mut(&mut self) -> &mut KeptCounters;
+}
+
+/// Where a span finds the counters besides its line and its column: in its tracker if it
+/// implements [`KeepCounters`], and nowhere otherwise
+///
+/// A tracker keeping nothing only implements the provided methods, like
+/// [`Utf8`](crate::Utf8) and [`Ascii`](crate::Ascii)
+pub trait CounterStorage {
+    fn kept_counters(&self) -> Option<&KeptCounters> {
+        None
+    }
+
+    fn kept_counters_mut(&mut self) -> Option<&mut KeptCounters> {
+        None
+    }
+}
+
+impl<C> CounterStorage for C
+where
+    C: KeepCounters,
+{
+    fn kept_counters(&self) -> Option<&KeptCounters> {
+        Some(self.kept())
+    }
+
+    fn kept_counters_mut(&mut self) -> Option<&mut KeptCounters> {
+        Some(self.kept_mut())
+    }
+}
+
+/// Strategy used by [`Spanned`](crate::Spanned) to count lines and columns while the input is
+/// sliced
+///
+/// Every [`Encoding`] is a tracker counting lines of text, which is what spans do by default.
+/// Implement it to give another meaning to the lines and the columns, like [`RecordTracker`] does
+/// for length prefixed records
+///
+/// A span clones its tracker before advancing it, so the state of the tracker belongs to the
+/// position of the span. The counters other than the line and the column are part of that state
+/// too, see [`CounterStorage`]
+///
+/// The tracker sees the [`Items`] of the data, which are bytes unless the span is over a slice of
+/// something else, like tokens
+pub trait PositionTracker<Item = u8>: Clone + CounterStorage {
+    /// Update `counters` after consuming `consumed`, `rest` being what remains of the input after
+    /// it
+    fn advance(&mut self, counters: &mut Counters, consumed: &[Item], rest: &[Item]);
+}
+
+/// The data seen by the [`PositionTracker`]s as a slice of items: the bytes of a `&str` or the
+/// elements of a slice
+pub trait Items {
+    type Item;
+
+    fn items(&self) -> &[Self::Item];
+}
+
+impl Items for &str {
+    type Item = u8;
+
+    fn items(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<I> Items for &[I] {
+    type Item = I;
+
+    fn items(&self) -> &[I] {
+        self
+    }
+}
+
+impl<E> PositionTracker for E
+where
+    E: Encoding,
+{
+    fn advance(&mut self, counters: &mut Counters, consumed: &[u8], rest: &[u8]) {
+        if consumed.is_empty() {
+            return;
+        }
+
+        // The end of a char cut by the previous slice was counted with its first bytes
+        let cut = counters.cut & !CUT_LINE_ENDING;
+        let skip = if self.handle_utf8() {
+            cut_end_len(consumed, cut)
+        } else {
+            0
+        };
+        let consumed = &consumed[usize::from(skip)..];
+        if counters.cut & CUT_LINE_ENDING == 0 {
+            counters.byte_col += usize::from(skip);
+        }
+
+        if consumed.is_empty() {
+            counters.cut = if skip == cut { 0 } else { counters.cut - skip };
+            return;
+        }
+
+        // A char takes 4 bytes at most, so there are 3 bytes left at most
+        counters.cut = if self.handle_utf8() {
+            u8::try_from(cut_char_len(consumed, rest)).unwrap_or(u8::MAX)
+        } else {
+            0
+        };
+
+        let (lines_to_add, last_index) = count_lines(self, consumed, rest);
+
+        // A line ending cut by the slice already started the new line
+        let last_index = if last_index > consumed.len() {
+            counters.cut |= CUT_LINE_ENDING;
+            consumed.len()
+        } else {
+            last_index
+        };
+
+        let line = &consumed[last_index..];
+        let line_chars = count_chars(self, line);
+        counters.char_offset += count_chars(self, &consumed[..last_index]) + line_chars;
+
+        let start_col = if lines_to_add == 0 {
+            counters.byte_col += line.len();
+            counters.col
+        } else {
+            // When going to a new line, char starts at 1
+            counters.line += lines_to_add;
+            counters.byte_col = line.len() + 1;
+            1
+        };
+
+        // Only the `\r` of a `\r\n` can still be at the end of the line, and it takes no column
+        let (line, line_chars) = if self.crlf() && line.last() == Some(&b'\r') {
+            (&line[..line.len() - 1], line_chars - 1)
+        } else {
+            (line, line_chars)
+        };
+
+        counters.col = match self.tab_width() {
+            Some(tab_width) if memchr(b'\t', line).is_some() => {
+                expand_tabs(self, start_col, line, rest, tab_width)
+            }
+            _ => match self.column_unit() {
+                // The chars of the line were already counted for the char offset
+                ColumnUnit::Bytes | ColumnUnit::Chars => start_col + line_chars,
+                _ => start_col + count_cols(self, line, rest),
+            },
+        };
+    }
+}
+
+/// Count the line endings in `consumed` and get the index where the last line starts, which is past
+/// the end of `consumed` if its last line ending goes on in `rest`
+fn count_lines<E: Encoding>(encoding: &E, consumed: &[u8], rest: &[u8]) -> (usize, usize) {
+    let mut lines_to_add = 0;
+    let mut line_start = 0;
+
+    let separator = encoding.line_separator();
+    let crlf = encoding.crlf();
+    let unicode_newlines = encoding.unicode_newlines() && encoding.handle_utf8();
+
+    if !crlf && !unicode_newlines {
+        for i in Memchr::new(separator, consumed) {
+            lines_to_add += 1;
+            line_start = i + 1;
+        }
+
+        return (lines_to_add, line_start);
+    }
+
+    let count = |i| {
+        if let Some(len) = line_ending_at(encoding, consumed, i, rest) {
+            lines_to_add += 1;
+            line_start = i + len;
+        }
+    };
+
+    match (crlf, unicode_newlines) {
+        (true, true) => (0..consumed.len()).for_each(count),
+        (false, true) => Memchr3::new(separator, 0x0C, 0xE2, consumed).for_each(count),
+        _ => Memchr2::new(separator, b'\r', consumed).for_each(count),
+    }
+
+    (lines_to_add, line_start)
+}
+
+/// Get the length of the line ending starting at `i` in `consumed`, if there's one, its end being
+/// looked for in `rest` when it's cut
+fn line_ending_at<E: Encoding>(
+    encoding: &E,
+    consumed: &[u8],
+    i: usize,
+    rest: &[u8],
+) -> Option<usize> {
+    let separator = encoding.line_separator();
+    let unicode_newlines = encoding.unicode_newlines() && encoding.handle_utf8();
+    let byte = |n| {
+        consumed
+            .get(i + n)
+            .or_else(|| rest.get(i + n - consumed.len()))
+    };
+
+    match consumed[i] {
+        byte if byte == separator => Some(1),
+        b'\r' if encoding.crlf() => match byte(1) {
+            // The line ends on the separator following the `\r`, and with nothing after it the
+            // `\r` is taken as the start of a `\r\n` too
+            Some(&next) if next != separator => Some(1),
+            _ => None,
+        },
+        // The form feed, U+2028 and U+2029
+        0x0C if unicode_newlines => Some(1),
+        0xE2 if unicode_newlines
+            && byte(1) == Some(&0x80)
+            && matches!(byte(2), Some(0xA8 | 0xA9)) =>
+        {
+            Some(3)
+        }
+        _ => None,
+    }
+}
+
+/// Count the chars in `bytes`, or the bytes themselves if UTF-8 isn't handled
+fn count_chars<E: Encoding>(encoding: &E, bytes: &[u8]) -> usize {
+    if encoding.handle_utf8() {
+        count_utf8(bytes, num_chars)
+    } else {
+        bytes.len()
+    }
+}
+
+/// Count `bytes` as UTF-8 with `count_valid`, each invalid sequence counting as one like the
+/// replacement char it would be decoded as
+pub(crate) fn count_utf8<F>(bytes: &[u8], count_valid: F) -> usize
+where
+    F: Fn(&[u8]) -> usize,
+{
+    // Every ASCII byte is a char and a UTF-16 code unit
+    if bytes.is_ascii() {
+        return bytes.len();
+    }
+
+    if is_utf8(bytes) {
+        return count_valid(bytes);
+    }
+
+    bytes
+        .utf8_chunks()
+        .map(|chunk| {
+            count_valid(chunk.valid().as_bytes()) + usize::from(!chunk.invalid().is_empty())
+        })
+        .sum()
+}
+
+/// Get how many of the continuation bytes starting `consumed` end the char cut by the previous
+/// slice, which was still missing `cut` of them
+fn cut_end_len(consumed: &[u8], cut: u8) -> u8 {
+    consumed
+        .iter()
+        .take(usize::from(cut))
+        .take_while(|&&b| is_continuation(b))
+        .fold(0, |len, _| len + 1)
+}
+
+/// Get how many bytes of `rest` are missing to the char cut at the end of `bytes`, or 0 if the
+/// last char isn't cut
+///
+/// A char is what [`String::from_utf8_lossy`] decodes, so an invalid sequence can be cut too. As
+/// long as `rest` doesn't tell otherwise, a truncated sequence is expected to go on
+fn cut_char_len(bytes: &[u8], rest: &[u8]) -> usize {
+    // A char takes 4 bytes at most, so only one of the last 3 bytes can start a cut char
+    let tail = bytes.len().saturating_sub(3);
+    let start = match bytes[tail..].iter().rposition(|&b| !is_continuation(b)) {
+        Some(i) => tail + i,
+        None => return 0,
+    };
+
+    let width = match bytes[start] {
+        0xC2..=0xDF => 2_usize,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return 0,
+    };
+    let present = &bytes[start..];
+    let missing = match width.checked_sub(present.len()) {
+        Some(missing) if missing > 0 => missing,
+        _ => return 0,
+    };
+
+    let next = &rest[..min(missing, rest.len())];
+    let mut char = [0; 4];
+    char[..present.len()].copy_from_slice(present);
+    char[present.len()..present.len() + next.len()].copy_from_slice(next);
+
+    match str::from_utf8(&char[..present.len() + next.len()]) {
+        Ok(_) => missing,
+        Err(error) if error.valid_up_to() == 0 => match error.error_len() {
+            None => missing,
+            // The invalid sequence may still take some of the next bytes, like a valid char
+            Some(len) => len.saturating_sub(present.len()),
+        },
+        Err(_) => 0,
+    }
+}
+
+fn is_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+/// Check that `bytes` is valid UTF-8, the SIMD validation being only worth it on long inputs
+fn is_utf8(bytes: &[u8]) -> bool {
+    if bytes.len() < 64 {
+        str::from_utf8(bytes).is_ok()
+    } else {
+        simdutf8::basic::from_utf8(bytes).is_ok()
+    }
+}
+
+/// Count the UTF-16 code units of valid UTF-8 `bytes`, the chars outside of the BMP taking two
+fn utf16_len(bytes: &[u8]) -> usize {
+    // Only the chars encoded on 4 bytes are outside of the BMP
+    num_chars(bytes) + bytes.iter().filter(|&&b| b >= 0xF0).count()
+}
+
+/// Count the columns taken by `bytes`, which must be on a single line, `next` being what comes
+/// after them
+#[cfg_attr(not(feature = "unicode-segmentation"), allow(unused_variables))]
+fn count_cols<E: Encoding>(encoding: &E, bytes: &[u8], next: &[u8]) -> usize {
+    match encoding.column_unit() {
+        ColumnUnit::Bytes => bytes.len(),
+        ColumnUnit::Chars => count_utf8(bytes, num_chars),
+        ColumnUnit::Utf16 => count_with_cut_char(bytes, next, |bytes| count_utf8(bytes, utf16_len)),
+        #[cfg(feature = "unicode-segmentation")]
+        ColumnUnit::Graphemes => grapheme::count(bytes, next),
+        #[cfg(feature = "unicode-width")]
+        ColumnUnit::Width => count_with_cut_char(bytes, next, width::count),
+    }
+}
+
+/// Count `bytes` with `count`, a char cut at their end being completed with the start of `next` so
+/// it takes all its columns where it starts, and none where it ends
+fn count_with_cut_char<F>(bytes: &[u8], next: &[u8], count: F) -> usize
+where
+    F: Fn(&[u8]) -> usize,
+{
+    let missing = min(cut_char_len(bytes, next), next.len());
+    if missing == 0 {
+        return count(bytes);
+    }
+
+    // The char is cut, so its first byte is one of the last 3
+    let start = bytes
+        .iter()
+        .rposition(|&b| !is_continuation(b))
+        .unwrap_or(0);
+    let present = &bytes[start..];
+    let mut char = [0; 4];
+    char[..present.len()].copy_from_slice(present);
+    char[present.len()..present.len() + missing].copy_from_slice(&next[..missing]);
+
+    count(&bytes[..start]) + count(&char[..present.len() + missing])
+}
+
+/// Compute the column reached after `line` starting at `col`, moving to the next tab stop on every
+/// `\t`
+fn expand_tabs<E: Encoding>(
+    encoding: &E,
+    mut col: usize,
+    line: &[u8],
+    rest: &[u8],
+    tab_width: NonZeroU8,
+) -> usize {
+    let tab_width = usize::from(tab_width.get());
+    let mut start = 0;
+
+    for i in Memchr::new(b'\t', line) {
+        col += count_cols(encoding, &line[start..i], &line[i..]);
+        col = ((col - 1) / tab_width + 1) * tab_width + 1;
+        start = i + 1;
+    }
+
+    col + count_cols(encoding, &line[start..], rest)
+}
+
+/// Count length prefixed records instead of lines: the line is the number of the record and the
+/// column is the byte in the record, starting at 1
+///
+/// Every record starts with the length of what follows as a big endian integer of `prefix_len`
+/// bytes, and the prefix is part of the record. The char offset is the same as the byte offset
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct RecordTracker {
+    prefix_len: usize,
+    /// Bytes of the prefix of the current record consumed so far
+    prefix_read: usize,
+    /// Length read from the prefix so far
+    len: u64,
+    /// Bytes of the current record left to consume after its prefix
+    remaining: usize,
+    kept: KeptCounters,
+}
+
+impl RecordTracker {
+    /// Track records prefixed with their length on `prefix_len` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix_len` isn't between 1 and 8
+    #[must_use]
+    pub fn new(prefix_len: usize) -> Self {
+        assert!(
+            (1..=8).contains(&prefix_len),
+            "the length prefix takes between 1 and 8 bytes"
+        );
+
+        Self {
+            prefix_len,
+            prefix_read: 0,
+            len: 0,
+            remaining: 0,
+            kept: KeptCounters::default(),
+        }
+    }
+}
+
+impl KeepCounters for RecordTracker {
+    fn kept(&self) -> &KeptCounters {
+        &self.kept
+    }
+
+    fn kept_mut(&mut self) -> &mut KeptCounters {
+        &mut self.kept
+    }
+}
+
+impl PositionTracker for RecordTracker {
+    fn advance(&mut self, counters: &mut Counters, consumed: &[u8], _rest: &[u8]) {
+        let mut bytes = consumed;
+
+        while !bytes.is_empty() {
+            let n = if self.prefix_read < self.prefix_len {
+                let n = min(self.prefix_len - self.prefix_read, bytes.len());
+
+                for &byte in &bytes[..n] {
+                    self.len = self.len << 8 | u64::from(byte);
+                }
+                self.prefix_read += n;
+
+                if self.prefix_read == self.prefix_len {
+                    self.remaining = usize::try_from(self.len).unwrap_or(usize::MAX);
+                }
+
+                n
+            } else {
+                let n = min(self.remaining, bytes.len());
+                self.remaining -= n;
+
+                n
+            };
+
+            bytes = &bytes[n..];
+            counters.col += n;
+            counters.byte_col += n;
+            counters.char_offset += n;
+
+            if self.prefix_read == self.prefix_len && self.remaining == 0 {
+                counters.line += 1;
+                counters.col = 1;
+                counters.byte_col = 1;
+                self.prefix_read = 0;
+                self.len = 0;
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use memchr::memchr;
+use nom::error::{VerboseError, VerboseErrorKind};
+use snippet::padding;
+use std::fmt::Write;
+use tracker::{KeepCounters, PositionTracker};
+use Spanned;
+
+/// Render the frames of a `VerboseError` like `nom::error::convert_error`, for spans
+///
+/// `original` is the span the parser was given. Each frame is located with the line and the
+/// column of its span, and gets the line of `original` it's on with a caret under its position,
+/// aligned like with `render_snippet`
+#[must_use]
+#[allow(clippy::needless_pass_by_value)] // Taken like `convert_error` takes them
+pub fn convert_spanned_error<X, C>(
+    original: Spanned<&str, X, C>,
+    error: VerboseError<Spanned<&str, X, C>>,
+) -> String
+where
+    C: PositionTracker + KeepCounters,
+{
+    let mut result = String::new();
+
+    for (i, (frame, kind)) in error.errors.iter().enumerate() {
+        let at = frame.offset - original.offset;
+        let line_start = frame.line_start_offset().max(original.offset) - original.offset;
+        let rest = &original.data[line_start..];
+        let line = &rest[..memchr(b'\n', rest.as_bytes()).unwrap_or(rest.len())];
+        // The untrimmed line, as the error can be on its trailing whitespace
+        let caret = format!("{}^", padding(&line[..(at - line_start).min(line.len())]));
+        let line = line.trim_end();
+        let (line_number, column) = (frame.line(), frame.col());
+
+        let _ = match kind {
+            VerboseErrorKind::Char(c) => match frame.data.chars().next() {
+                Some(actual) => write!(
+                    result,
+                    "{i}: at line {line_number}, column {column}:\n{line}\n{caret}\nexpected '{c}', found {actual}\n\n"
+                ),
+                None => write!(
+                    result,
+                    "{i}: at line {line_number}, column {column}:\n{line}\n{caret}\nexpected '{c}', got end of input\n\n"
+                ),
+            },
+            VerboseErrorKind::Context(context) => write!(
+                result,
+                "{i}: at line {line_number}, column {column}, in {context}:\n{line}\n{caret}\n\n"
+            ),
+            VerboseErrorKind::Nom(kind) => write!(
+                result,
+                "{i}: at line {line_number}, column {column}, in {kind:?}:\n{line}\n{caret}\n\n"
+            ),
+        };
+    }
+
+    result
+}
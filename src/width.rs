@@ -0,0 +1,11 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Count the terminal cells taken by `bytes`
+///
+/// Every invalid UTF-8 sequence takes one cell, like the replacement char it would be displayed as
+pub(crate) fn count(bytes: &[u8]) -> usize {
+    String::from_utf8_lossy(bytes)
+        .chars()
+        .map(|c| c.width().unwrap_or(1))
+        .sum()
+}
@@ -0,0 +1,191 @@
+use nom::AsBytes;
+use std::{fmt::Debug, ops::Range};
+use tracker::PositionTracker;
+use winnow::{
+    error::Needed,
+    stream::{
+        AsBStr, Compare, CompareResult, FindSlice, Location, Offset, ParseSlice, SliceLen, Stream,
+        StreamIsPartial,
+    },
+};
+use Spanned;
+
+/// The span is advanced in [`Stream::next_token`] and [`Stream::next_slice`]. The slices are
+/// spans too, so the output of the parsers keep their position, and a checkpoint is the span itself
+impl<T, X, C> Stream for Spanned<T, X, C>
+where
+    T: Stream<Slice = T> + AsBytes + Clone + Debug,
+    X: Clone + Debug,
+    C: PositionTracker + Debug,
+{
+    type Token = T::Token;
+
+    type Slice = Self;
+
+    type IterOffsets = T::IterOffsets;
+
+    type Checkpoint = Self;
+
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.data.iter_offsets()
+    }
+
+    fn eof_offset(&self) -> usize {
+        self.data.eof_offset()
+    }
+
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let mut rest = self.data.clone();
+        let token = rest.next_token()?;
+        let consumed = self.data.eof_offset() - rest.eof_offset();
+
+        *self = self.advance_to(&self.data.as_bytes()[..consumed], rest);
+
+        Some(token)
+    }
+
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.data.peek_token()
+    }
+
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.data.offset_for(predicate)
+    }
+
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.data.offset_at(tokens)
+    }
+
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let mut rest = self.data.clone();
+        let slice = Self {
+            data: rest.next_slice(offset),
+            ..self.clone()
+        };
+
+        *self = self.advance_to(slice.data.as_bytes(), rest);
+
+        slice
+    }
+
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        Self {
+            data: self.data.peek_slice(offset),
+            ..self.clone()
+        }
+    }
+
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        *self = checkpoint.clone();
+    }
+
+    fn trace(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.data.trace(f)
+    }
+}
+
+impl<T, X, C> StreamIsPartial for Spanned<T, X, C>
+where
+    T: StreamIsPartial,
+{
+    type PartialState = T::PartialState;
+
+    fn complete(&mut self) -> Self::PartialState {
+        self.data.complete()
+    }
+
+    fn restore_partial(&mut self, state: Self::PartialState) {
+        self.data.restore_partial(state);
+    }
+
+    fn is_partial_supported() -> bool {
+        T::is_partial_supported()
+    }
+
+    fn is_partial(&self) -> bool {
+        self.data.is_partial()
+    }
+}
+
+/// Offsets between spans are counted in bytes, so they must come from the same input
+impl<T, X, C> Offset for Spanned<T, X, C>
+where
+    T: AsBytes,
+{
+    fn offset_from(&self, start: &Self) -> usize {
+        debug_assert!(
+            start.checked_offset(self).is_some(),
+            "the span at {}:{} (byte {}) doesn't contain the span at {}:{} (byte {}), they may come from different inputs",
+            start.line(),
+            start.col(),
+            start.offset,
+            self.line(),
+            self.col(),
+            self.offset,
+        );
+
+        self.offset.saturating_sub(start.offset)
+    }
+}
+
+impl<T, X, C> Location for Spanned<T, X, C> {
+    fn previous_token_end(&self) -> usize {
+        self.offset
+    }
+
+    fn current_token_start(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<T, U, X, C> Compare<U> for Spanned<T, X, C>
+where
+    T: Compare<U>,
+{
+    fn compare(&self, t: U) -> CompareResult {
+        self.data.compare(t)
+    }
+}
+
+impl<T, U, X, C> FindSlice<U> for Spanned<T, X, C>
+where
+    T: FindSlice<U>,
+{
+    fn find_slice(&self, substr: U) -> Option<Range<usize>> {
+        self.data.find_slice(substr)
+    }
+}
+
+impl<T, R, X, C> ParseSlice<R> for Spanned<T, X, C>
+where
+    T: ParseSlice<R>,
+{
+    fn parse_slice(&self) -> Option<R> {
+        self.data.parse_slice()
+    }
+}
+
+impl<T, X, C> AsBStr for Spanned<T, X, C>
+where
+    T: AsBStr,
+{
+    fn as_bstr(&self) -> &[u8] {
+        self.data.as_bstr()
+    }
+}
+
+impl<T, X, C> SliceLen for Spanned<T, X, C>
+where
+    T: SliceLen,
+{
+    fn slice_len(&self) -> usize {
+        self.data.slice_len()
+    }
+}
@@ -0,0 +1,84 @@
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn data_borrows_and_into_data_consumes() {
+    let span = Spanned::new(String::from("owned buffer"), true);
+
+    assert_eq!(span.data(), "owned buffer");
+
+    let data: String = span.into_data();
+
+    assert_eq!(data, "owned buffer");
+}
+
+#[test]
+fn into_parts_returns_the_position() {
+    let span = Span::new("line 1\nline 2", true);
+
+    let (rest, _): (_, Span) = take::<_, _, ()>(9usize).parse(span).unwrap();
+
+    let (data, line, col, offset) = rest.into_parts();
+
+    assert_eq!(data, "ne 2");
+    assert_eq!(line, 2);
+    assert_eq!(col, 3);
+    assert_eq!(offset, 9);
+}
+
+#[test]
+fn into_parts_on_an_owned_fragment() {
+    let span = Spanned::new(vec![1u8, 2, 3], false);
+
+    let (data, line, col, offset) = span.into_parts();
+
+    assert_eq!(data, vec![1, 2, 3]);
+    assert_eq!((line, col, offset), (1, 1, 0));
+
+    let span = Span::new("abc", false).slice(1..);
+
+    assert_eq!(span.into_data(), "bc");
+}
+
+#[test]
+fn map_data_keeps_the_position() {
+    let span = Spanned::new(&b"first\nsecond"[..], true).slice(8..);
+
+    let mapped = span.map_data(|bytes| std::str::from_utf8(bytes).unwrap());
+
+    assert_eq!(*mapped.data(), "cond");
+    assert_eq!(mapped.line(), span.line());
+    assert_eq!(mapped.col(), span.col());
+    assert_eq!(mapped.byte_offset(), span.byte_offset());
+    assert_eq!(
+        (mapped.line(), mapped.col(), mapped.byte_offset()),
+        (2, 3, 8)
+    );
+}
+
+#[test]
+fn try_map_data_keeps_the_position_on_success_and_failure() {
+    let valid = Spanned::new(&b"ok\nvalid"[..], true).slice(3..);
+    let invalid = Spanned::new(&b"ok\n\xff\xfe"[..], true).slice(3..);
+
+    let valid = valid.try_map_data(std::str::from_utf8).unwrap();
+
+    assert_eq!(*valid.data(), "valid");
+    assert_eq!((valid.line(), valid.col(), valid.byte_offset()), (2, 1, 3));
+
+    let error = invalid.try_map_data(std::str::from_utf8).unwrap_err();
+
+    assert_eq!(error.data().valid_up_to(), 0);
+    assert_eq!((error.line(), error.col(), error.byte_offset()), (2, 1, 3));
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "annotate-snippets")]
+
+use annotate_snippets::{Level, Renderer};
+use common::Slice;
+use nom::{
+    bytes::complete::{is_not, tag},
+    character::complete::alpha1,
+    sequence::delimited,
+    IResult, Parser,
+};
+use nom_span::{Span, Spanned};
+
+extern crate annotate_snippets;
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// `"<text>"`, the text being able to cross lines
+fn string(input: Input<'_>) -> IResult<Input<'_>, Input<'_>> {
+    delimited(tag("\""), is_not("\""), tag("\"")).parse(input)
+}
+
+#[test]
+fn spans_are_annotated_on_their_line() {
+    let source = "let a = 1;\nlet é = b;\nlet c = 3;\n";
+    let input = Input::new(source, true);
+    let (_, name) = alpha1::<_, ()>(input.slice(20..)).unwrap();
+
+    let report = [Level::ERROR.primary_title("unknown variable").element(
+        name.to_annotated_snippet(source, "not declared")
+            .path("main.txt"),
+    )];
+
+    assert_eq!(
+        Renderer::plain().render(&report),
+        "error: unknown variable
+ --> main.txt:2:9
+  |
+2 | let é = b;
+  |         ^ not declared"
+    );
+}
+
+#[test]
+fn spans_crossing_lines_get_a_multi_line_annotation() {
+    let source = "let a = 1;\nlet s = \"one\ntwo\nthree\";\nlet b = 2;\n";
+    let input = Input::new(source, true);
+    let (rest, _) = string(input.slice(19..)).unwrap();
+
+    let span = Span::between(&input.slice(19..), &rest);
+    let report = [span.to_error_group(source, "unterminated string")];
+
+    assert_eq!((span.start.line, span.end.line), (2, 4));
+    assert_eq!(
+        Renderer::plain().render(&report),
+        "error: unterminated string
+  |
+2 |   let s = \"one
+  |  _________^
+3 | | two
+4 | | three\";
+  | |______^"
+    );
+}
+
+#[test]
+fn spans_at_the_end_are_annotated_after_the_last_char() {
+    let source = "let a = 1;\nlet b =";
+    let input = Input::new(source, true);
+    let eof = input.slice(source.len()..);
+
+    let report = [Level::ERROR
+        .primary_title("expected a value")
+        .element(eof.to_annotated_snippet(source, "input ends here"))];
+
+    assert_eq!(
+        Renderer::plain().render(&report),
+        "error: expected a value
+  |
+2 | let b =
+  |        ^ input ends here"
+    );
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "ariadne")]
+
+use ariadne::{Config, IndexType, Label, Report, ReportKind};
+use common::Slice;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, space0},
+    sequence::{delimited, terminated},
+    IResult, Parser,
+};
+use nom_span::{ReportSpan, Span, Spanned};
+
+extern crate ariadne;
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// `let <name> = `
+fn declaration(input: Input<'_>) -> IResult<Input<'_>, Input<'_>> {
+    delimited(
+        terminated(tag("let"), space0),
+        alpha1,
+        delimited(space0, tag("="), space0),
+    )
+    .parse(input)
+}
+
+#[test]
+fn labels_are_rendered_at_the_position_of_the_spans() {
+    let source = "let é = 1;\nlet a = 2;\n  let a = 3;\n";
+    let input = Input::new(source, true);
+
+    let (_, first) = declaration(input.slice(12..)).unwrap();
+    let (_, second) = declaration(input.slice(25..)).unwrap();
+
+    let report = Report::build(ReportKind::Error, second.report_span("main.txt"))
+        .with_config(
+            Config::default()
+                .with_color(false)
+                .with_index_type(IndexType::Byte),
+        )
+        .with_message("`a` is declared twice")
+        .with_label(Label::new(second.report_span("main.txt")).with_message("declared again"))
+        .with_label(
+            Label::new(Span::of(&first).report_span("main.txt"))
+                .with_message("first declared here"),
+        )
+        .finish();
+
+    let mut rendered = Vec::new();
+    report
+        .write(input.to_ariadne_cache("main.txt"), &mut rendered)
+        .unwrap();
+    let rendered = String::from_utf8(rendered).unwrap();
+
+    assert_eq!((first.line(), first.col()), (2, 5));
+    assert_eq!((second.line(), second.col()), (3, 7));
+    assert!(rendered.contains("main.txt:3:7"));
+    assert!(!rendered.contains("let é"));
+
+    let lines: Vec<&str> = rendered.lines().collect();
+    let row = |text: &str| lines.iter().position(|line| line.ends_with(text)).unwrap();
+
+    let second_line = row(" 3 │   let a = 3;");
+    assert_eq!(lines[second_line + 1].trim_end(), "   │       ┬");
+    assert!(lines[second_line + 2].ends_with("╰── declared again"));
+
+    let first_line = row(" 2 │ let a = 2;");
+    assert_eq!(lines[first_line + 1].trim_end(), "   │     ┬");
+    assert!(lines[first_line + 2].ends_with("╰── first declared here"));
+}
+
+#[test]
+fn spans_cover_the_bytes_of_the_data() {
+    let input = Input::new("é\nab = ?", true);
+    let (_, name) = alpha1::<_, ()>(input.slice(3..)).unwrap();
+
+    assert_eq!(name.report_span(0), ReportSpan::new(0, 3, 5));
+    assert_eq!(Span::of(&name).report_span("f"), ReportSpan::new("f", 3, 5));
+}
@@ -0,0 +1,70 @@
+use common::Slice;
+use nom::{
+    bytes::complete::{tag, take, take_until},
+    character::complete::{digit1, line_ending},
+    combinator::map_res,
+    number::complete::be_u16,
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+const MESSAGE: &[u8] = b"t\xc3\xaftle: 2\n\x00\x05\xff\n\x00\x01";
+
+fn header(input: Span<'_>) -> IResult<Span<'_>, u16> {
+    delimited(
+        preceded(take_until(":"), tag(": ")),
+        map_res(digit1, |count: Span<'_>| count.parse()),
+        line_ending,
+    )
+    .parse(input)
+}
+
+#[test]
+fn bytes_are_parsed_after_the_text() {
+    let text = std::str::from_utf8(&MESSAGE[..10]).unwrap();
+    let (rest, count) = header(Span::new(text, true)).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(rest.pos(), Position::new(2, 1, 10));
+
+    let body = BytesSpan::new(MESSAGE, true);
+    let bytes = rest.as_bytes_span();
+    assert_eq!(bytes.pos(), rest.pos());
+    assert_eq!(bytes.char_offset(), rest.char_offset());
+
+    // The body keeps going in the same buffer
+    let bytes = bytes.respan(&MESSAGE[bytes.byte_offset()..]);
+    let (after, first) = be_u16::<_, ()>(bytes).unwrap();
+    assert_eq!(first, 5);
+    assert_eq!(after.pos(), Position::new(2, 3, 12));
+
+    let (after, _) = take::<_, _, ()>(2usize).parse(after).unwrap();
+    let (after, last) = be_u16::<_, ()>(after).unwrap();
+    assert_eq!(last, 1);
+    assert_eq!(after.pos(), Position::new(3, 3, MESSAGE.len()));
+    assert_eq!(after.pos(), body.slice(MESSAGE.len()..).pos());
+}
+
+fn end(span: BytesSpan<'_>) -> BytesSpan<'_> {
+    take::<_, _, ()>(span.len()).parse(span).unwrap().0
+}
+
+#[test]
+fn the_utf8_flag_is_kept() {
+    let utf8 = Span::new("é\né", true).as_bytes_span();
+    let ascii = Span::new("é\né", false).as_bytes_span();
+
+    assert_eq!(end(utf8).pos(), Position::new(2, 2, 5));
+    assert_eq!(end(ascii).pos(), Position::new(2, 3, 5));
+}
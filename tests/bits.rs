@@ -0,0 +1,85 @@
+use common::{tuple, Slice};
+use nom::{
+    bits::{bits, bytes, complete::take},
+    bytes::complete::tag,
+    error::{Error, ErrorKind},
+    Err, IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+type BitsSpan<'a> = (BytesSpan<'a>, usize);
+
+fn fields(input: BitsSpan<'_>) -> IResult<BitsSpan<'_>, (u8, u8, u16), Error<BitsSpan<'_>>> {
+    tuple((take(3usize), take(5usize), take(12usize))).parse(input)
+}
+
+fn header(input: BytesSpan<'_>) -> IResult<BytesSpan<'_>, (u8, u8, u16)> {
+    bits(fields).parse(input)
+}
+
+#[test]
+fn bit_fields_then_bytes() {
+    let span = BytesSpan::new(b"\xa5\x12\x34rest", false);
+
+    let (rest, (version, kind, len)) = header(span).unwrap();
+    assert_eq!((version, kind, len), (0b101, 0b00101, 0x123));
+
+    // The 4 bits left in the third byte are skipped when going back to bytes
+    assert_eq!(rest.byte_offset(), 3);
+    assert_eq!(rest.col(), 4);
+
+    let (rest, _) = tag::<_, _, ()>("rest").parse(rest).unwrap();
+    assert_eq!(rest.byte_offset(), 7);
+}
+
+#[test]
+fn bytes_inside_bits() {
+    fn flag_then_tag(
+        input: BitsSpan<'_>,
+    ) -> IResult<BitsSpan<'_>, (u8, BytesSpan<'_>), Error<BitsSpan<'_>>> {
+        tuple((take(4usize), bytes(tag::<_, _, Error<BytesSpan<'_>>>("ab")))).parse(input)
+    }
+
+    let span = BytesSpan::new(b"\xf0abc", false);
+
+    let res: IResult<_, _> = bits(flag_then_tag).parse(span);
+    let (rest, (flag, ab)) = res.unwrap();
+
+    assert_eq!(flag, 0xf);
+    assert_eq!(ab.byte_offset(), 1);
+    assert_eq!(rest.byte_offset(), 3);
+    assert_eq!(*rest, b"c");
+}
+
+#[test]
+fn errors_in_bit_parsers_have_a_bit_offset() {
+    let span = BytesSpan::new(b"\x00\xa5\x12", false).slice(1..);
+
+    let Err(Err::Error(Error {
+        input: (input, bit),
+        code,
+    })) = fields((span, 0))
+    else {
+        panic!("expected an error");
+    };
+
+    assert_eq!(code, ErrorKind::Eof);
+    assert_eq!(input.bit_offset(bit), 16);
+}
+
+#[test]
+fn bit_offset_counts_from_the_start_of_the_input() {
+    let span = BytesSpan::new(b"abc", false).slice(2..);
+
+    assert_eq!(span.bit_offset(0), 16);
+    assert_eq!(span.bit_offset(5), 21);
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "bstr")]
+
+use bstr::{BStr, ByteSlice};
+use common::Slice;
+use nom::{
+    bytes::complete::{is_not, tag, take, take_until},
+    character::complete::line_ending,
+    multi::separated_list0,
+    IResult, Parser,
+};
+use nom_span::{BStrInput, Position, Spanned};
+
+extern crate bstr;
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+type BStrSpan<'a> = Spanned<&'a BStr>;
+type InputSpan<'a> = Spanned<BStrInput<'a>>;
+
+/// Invalid UTF-8 right before and right after the line endings
+const LOG: &[u8] = b"ok \xff\n\xe2\x82 cut\r\n\x80\x80tail\xf0\x9f\x99";
+
+fn lines(input: BytesSpan<'_>) -> IResult<BytesSpan<'_>, Vec<BytesSpan<'_>>> {
+    separated_list0(line_ending, is_not("\r\n")).parse(input)
+}
+
+fn input_lines(input: InputSpan<'_>) -> IResult<InputSpan<'_>, Vec<InputSpan<'_>>> {
+    separated_list0(line_ending, is_not("\r\n")).parse(input)
+}
+
+#[test]
+fn conversions_keep_the_position() {
+    let span = BytesSpan::new(LOG, true).slice(5..);
+
+    let bstr: BStrSpan<'_> = span.into();
+    assert_eq!(bstr.pos(), span.pos());
+    assert_eq!(bstr.data().to_vec(), span.to_vec());
+
+    let back: BytesSpan<'_> = bstr.into();
+    assert_eq!(back, span);
+}
+
+#[test]
+fn columns_match_the_chars_of_bstr() {
+    let (_, lines) = lines(BytesSpan::new(LOG, true)).unwrap();
+    assert_eq!(lines.len(), 3);
+
+    for line in lines {
+        let bstr: BStrSpan<'_> = line.into();
+
+        assert_eq!(line.col(), 1);
+        assert_eq!(line.end_col(), bstr.chars().count() + 1, "on {:?}", bstr);
+    }
+}
+
+#[test]
+fn invalid_sequences_around_line_endings() {
+    let (_, lines) = lines(BytesSpan::new(LOG, true)).unwrap();
+
+    // `\xff` then `\n`
+    assert_eq!(lines[0].end_position(), Position::new(1, 5, 4));
+    // A truncated `€` starting the line, then `\r\n`
+    assert_eq!(lines[1].pos(), Position::new(2, 1, 5));
+    assert_eq!(lines[1].end_col(), 6);
+    assert_eq!(lines[2].pos(), Position::new(3, 1, 13));
+    // Two lone continuation bytes and a truncated emoji at the end
+    assert_eq!(lines[2].end_col(), 8);
+}
+
+#[test]
+fn chars_are_decoded_the_same_in_pieces() {
+    let span = BytesSpan::new(LOG, true);
+    let (rest, first) = take::<_, _, ()>(4usize).parse(span).unwrap();
+
+    let bstr: BStrSpan<'_> = first.into();
+    assert_eq!(rest.col(), bstr.chars().count() + 1);
+
+    let end = rest.slice(rest.len()..);
+    assert_eq!(end.pos(), span.slice(LOG.len()..).pos());
+}
+
+#[test]
+fn every_prefix_counts_like_bstr() {
+    let span = BytesSpan::new(LOG, true);
+    let mut rest = span;
+
+    for i in 0..=LOG.len() {
+        assert_eq!(span.slice(i..).char_offset(), LOG[..i].chars().count());
+        assert_eq!(
+            rest.char_offset(),
+            LOG[..i].chars().count(),
+            "after {} bytes",
+            i
+        );
+
+        if !rest.is_empty() {
+            rest = rest.slice(1..);
+        }
+    }
+}
+
+#[test]
+fn bstr_inputs_parse_like_bytes() {
+    let (_, on_bytes) = lines(BytesSpan::new(LOG, true)).unwrap();
+    let (rest, on_bstr) = input_lines(InputSpan::new(BStrInput::new(LOG), true)).unwrap();
+
+    assert!(rest.is_empty());
+    assert_eq!(on_bstr.len(), on_bytes.len());
+
+    for (bstr, bytes) in on_bstr.iter().zip(&on_bytes) {
+        assert_eq!(bstr.pos(), bytes.pos());
+        assert_eq!(bstr.end_position(), bytes.end_position());
+        assert_eq!(**bstr, **bytes);
+    }
+}
+
+#[test]
+fn bstr_inputs_take_str_and_byte_tags() {
+    let span = InputSpan::new(BStrInput::new(LOG), true);
+
+    let (rest, ok) = tag::<_, _, ()>("ok ").parse(span).unwrap();
+    assert_eq!(*ok, "ok ");
+
+    let (rest, _) = take_until::<_, _, ()>(&b"cut"[..]).parse(rest).unwrap();
+    assert_eq!(rest.pos(), Position::new(2, 3, 8));
+
+    let bstr: BStrSpan<'_> = rest.into();
+    assert_eq!(bstr.pos(), rest.pos());
+    let back: InputSpan<'_> = BytesSpan::from(bstr).into();
+    assert_eq!(back, rest);
+}
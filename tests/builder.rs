@@ -0,0 +1,60 @@
+use common::tuple;
+use nom::{
+    bytes::complete::{tag, take},
+    character::complete::line_ending,
+    IResult, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn default_builder_is_like_new() {
+    assert_eq!(Spanned::builder("abc").build(), Span::new("abc", true));
+}
+
+#[test]
+fn builder_with_a_start_position_and_ascii() {
+    let span = Spanned::builder("é\nx")
+        .utf8(false)
+        .start_line(10)
+        .start_col(4)
+        .start_offset(300)
+        .build();
+
+    assert_eq!(span.pos(), Position::new(10, 4, 300));
+    assert!(!span.handle_utf8());
+
+    let (rest, _): (_, Span) = take::<_, _, ()>(1usize).parse(span).unwrap();
+
+    assert_eq!(rest.pos(), Position::new(10, 6, 302));
+}
+
+#[test]
+fn builder_with_extra_and_utf8() {
+    fn parser(input: Spanned<&str, u32>) -> IResult<Spanned<&str, u32>, Spanned<&str, u32>> {
+        let (rest, (_, _, value)) = tuple((tag("é"), line_ending, tag("🙌"))).parse(input)?;
+        Ok((rest, value))
+    }
+
+    let span = Spanned::builder("é\n🙌!")
+        .start_line(5)
+        .extra(42u32)
+        .utf8(true)
+        .build();
+
+    let (rest, value) = parser(span).unwrap();
+
+    assert_eq!(*value.extra(), 42);
+    assert_eq!(value.pos(), Position::new(6, 1, 3));
+    assert_eq!(rest.pos(), Position::new(6, 2, 7));
+    assert_eq!(rest.char_offset(), 3);
+}
@@ -0,0 +1,72 @@
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::{ColumnUnit, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn byte_col_diverges_from_col_on_multibyte_lines() {
+    let span = Span::new("é🙌 x\nà b", true);
+
+    let (after, _) = take::<_, _, ()>(3usize).parse(span).unwrap();
+    assert_eq!(after.col(), 4);
+    assert_eq!(after.byte_col(), 8);
+
+    let next_line = span.slice(9..);
+    assert_eq!(next_line.col(), 1);
+    assert_eq!(next_line.byte_col(), 1);
+
+    let (after, _) = take::<_, _, ()>(2usize).parse(next_line).unwrap();
+    assert_eq!(after.col(), 3);
+    assert_eq!(after.byte_col(), 4);
+}
+
+#[test]
+fn byte_col_is_col_without_utf8() {
+    let span = Span::new("é🙌 x", false);
+
+    let after = span.slice(7..);
+
+    assert_eq!(after.col(), 8);
+    assert_eq!(after.byte_col(), 8);
+}
+
+#[test]
+fn byte_col_slices_the_line() {
+    let input = "first\nsé🙌cond";
+    let span = Span::new(input, true);
+
+    let after = span.slice(input.find('c').unwrap()..);
+    let line_start = after.byte_offset() - (after.byte_col() - 1);
+
+    assert_eq!(&input[line_start..after.byte_offset()], "sé🙌");
+}
+
+#[test]
+fn byte_col_ignores_tab_expansion_and_column_unit() {
+    let span = Spanned::builder("\t🙌x")
+        .tab_width(4)
+        .column_unit(ColumnUnit::Utf16)
+        .build();
+
+    let after = span.slice(5..);
+
+    assert_eq!(after.col(), 7);
+    assert_eq!(after.byte_col(), 6);
+}
+
+#[test]
+fn byte_col_starts_at_the_starting_column() {
+    let span = Spanned::builder("ab").start_col(5).build();
+
+    assert_eq!(span.byte_col(), 5);
+    assert_eq!(span.slice(1..).byte_col(), 6);
+}
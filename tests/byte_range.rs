@@ -0,0 +1,61 @@
+use common::Slice;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, multispace0},
+    sequence::preceded,
+    IResult, Parser,
+};
+use nom_span::{Position, Span, Spanned};
+use std::ops::Range;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+#[test]
+fn ranges_of_a_fragment_and_of_what_was_consumed() {
+    let input = Input::new("é\n  ab;", true);
+
+    let res: IResult<Input<'_>, Input<'_>> =
+        preceded(tag("é"), preceded(multispace0, alpha1)).parse(input);
+    let (rest, word) = res.unwrap();
+
+    assert_eq!(word.byte_range_of_fragment(), 5..7);
+    assert_eq!(Range::from(&word), 5..7);
+    assert_eq!(input.byte_range_to(&rest), 0..7);
+    assert_eq!(word.byte_range_to(&rest), 5..7);
+    assert_eq!(&"é\n  ab;"[word.byte_range_of_fragment()], "ab");
+}
+
+#[test]
+fn empty_fragments_have_empty_ranges() {
+    let input = Input::new("ab", true).slice(1..1);
+
+    assert_eq!(input.byte_range_of_fragment(), 1..1);
+    assert_eq!(input.byte_range_to(&input), 1..1);
+}
+
+#[test]
+fn fragments_ending_at_the_end_of_the_input() {
+    let input = "a\nbé";
+    let tail = Input::new(input, true).slice(2..);
+    let end = tail.slice(tail.len()..);
+
+    assert_eq!(Range::from(&tail), 2..input.len());
+    assert_eq!(tail.byte_range_to(&end), 2..input.len());
+    assert_eq!(end.byte_range_of_fragment(), input.len()..input.len());
+    assert_eq!(Range::from(Span::of(&tail)), Range::from(&tail));
+}
+
+#[test]
+fn spans_convert_to_ranges() {
+    let span = Span::new(Position::new(1, 2, 1), Position::new(2, 1, 4));
+
+    assert_eq!(Range::from(span), 1..4);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "caseless")]
+
+use nom::{
+    error::{Error, ErrorKind},
+    Err, IResult, Parser,
+};
+use nom_span::{tag_no_case_unicode, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn keyword<'a>(tag: &'a str, input: Span<'a>) -> IResult<Span<'a>, Span<'a>> {
+    tag_no_case_unicode(tag).parse(input)
+}
+
+#[test]
+fn non_ascii_letters_are_matched_ignoring_the_case() {
+    let (rest, word) = keyword("été", Span::new("ÉTÉ chaud", true)).unwrap();
+
+    assert_eq!(*word, "ÉTÉ");
+    assert_eq!(word.end_position(), Position::new(1, 4, 5));
+    assert_eq!(*rest, " chaud");
+    assert_eq!(rest.pos(), Position::new(1, 4, 5));
+}
+
+#[test]
+fn matches_can_have_another_length() {
+    let (rest, word) = keyword("STRASSE", Span::new("straße\nx", true)).unwrap();
+
+    assert_eq!(*word, "straße");
+    assert_eq!(rest.pos(), Position::new(1, 7, 7));
+
+    let (rest, word) = keyword("straße", Span::new("STRASSE", true)).unwrap();
+    assert_eq!(*word, "STRASSE");
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn chars_are_not_partially_matched() {
+    let res = keyword("stras", Span::new("straße", true));
+
+    assert!(matches!(
+        res,
+        Err(Err::Error(Error {
+            code: ErrorKind::Tag,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn errors_are_at_the_start_of_the_input() {
+    let span = Span::new("ab", true);
+
+    let Err(Err::Error(Error { input, .. })) = keyword("abc", span) else {
+        panic!("expected an error");
+    };
+    assert_eq!(input, span);
+
+    assert!(keyword("b", span).is_err());
+}
+
+#[test]
+fn lines_are_counted_in_the_match() {
+    let (rest, _) = keyword("A\nB", Span::new("a\nbc", true)).unwrap();
+
+    assert_eq!(rest.pos(), Position::new(2, 2, 3));
+}
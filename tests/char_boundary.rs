@@ -0,0 +1,69 @@
+use common::{InputTake, Slice};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+/// A char of each length, in a line after `a\n`
+const INPUT: &str = "a\nbé€🙌";
+
+#[test]
+fn floor_takes_less_inside_a_char() {
+    let span = Span::new(INPUT, true).slice(2..);
+
+    // 2 bytes char
+    assert_eq!(*span.take_floor_char_boundary(2), "b");
+    assert_eq!(*span.take_floor_char_boundary(3), "bé");
+    // 3 bytes char
+    assert_eq!(*span.take_floor_char_boundary(4), "bé");
+    assert_eq!(*span.take_floor_char_boundary(5), "bé");
+    assert_eq!(*span.take_floor_char_boundary(6), "bé€");
+    // 4 bytes char
+    assert_eq!(*span.take_floor_char_boundary(9), "bé€");
+    assert_eq!(*span.take_floor_char_boundary(10), "bé€🙌");
+    assert_eq!(*span.take_floor_char_boundary(100), "bé€🙌");
+}
+
+#[test]
+fn ceil_takes_more_inside_a_char() {
+    let span = Span::new(INPUT, true).slice(2..);
+
+    assert_eq!(*span.take_ceil_char_boundary(0), "");
+    assert_eq!(*span.take_ceil_char_boundary(2), "bé");
+    assert_eq!(*span.take_ceil_char_boundary(4), "bé€");
+    assert_eq!(*span.take_ceil_char_boundary(5), "bé€");
+    assert_eq!(*span.take_ceil_char_boundary(7), "bé€🙌");
+    assert_eq!(*span.take_ceil_char_boundary(100), "bé€🙌");
+}
+
+#[test]
+fn taken_spans_keep_the_position_in_utf8() {
+    let span = Span::new(INPUT, true).slice(2..);
+
+    let taken = span.take_floor_char_boundary(8);
+    assert_eq!(taken.pos(), Position::new(2, 1, 2));
+    assert_eq!(taken.end_position(), Position::new(2, 4, 8));
+    assert_eq!(taken, span.take(6));
+
+    let taken = span.take_ceil_char_boundary(7);
+    assert_eq!(taken.end_position(), Position::new(2, 5, 12));
+}
+
+#[test]
+fn taken_spans_keep_the_position_in_ascii() {
+    let span = Span::new(INPUT, false).slice(2..);
+
+    let taken = span.take_floor_char_boundary(8);
+    assert_eq!(taken.pos(), Position::new(2, 1, 2));
+    assert_eq!(taken.end_position(), Position::new(2, 7, 8));
+
+    let taken = span.take_ceil_char_boundary(7);
+    assert_eq!(taken.end_position(), Position::new(2, 11, 12));
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "codespan")]
+
+use codespan_reporting::{
+    diagnostic::{Diagnostic, Label},
+    files::Files,
+    term::{self, Config},
+};
+use common::Slice;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, space0},
+    sequence::{delimited, terminated},
+    IResult, Parser,
+};
+use nom_span::{Span, Spanned};
+use std::ops::Range;
+
+extern crate codespan_reporting;
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// `let <name> = `
+fn declaration(input: Input<'_>) -> IResult<Input<'_>, Input<'_>> {
+    delimited(
+        terminated(tag("let"), space0),
+        alpha1,
+        delimited(space0, tag("="), space0),
+    )
+    .parse(input)
+}
+
+#[test]
+fn labels_are_rendered_at_the_position_of_the_spans() {
+    let source = "let a = 1;\n  let a = 2;\n";
+    let input = Input::new(source, true);
+
+    let (_, first) = declaration(input).unwrap();
+    let (_, second) = declaration(input.slice(13..)).unwrap();
+
+    let file = input.to_simple_file("main.txt");
+    let diagnostic = Diagnostic::error()
+        .with_message("`a` is declared twice")
+        .with_labels(vec![
+            Label::primary((), &second).with_message("declared again"),
+            Label::secondary((), Span::of(&first)).with_message("first declared here"),
+        ]);
+
+    let rendered = term::emit_into_string(&Config::default(), &file, &diagnostic).unwrap();
+
+    assert_eq!((second.line(), second.col()), (2, 7));
+    assert_eq!((first.line(), first.col()), (1, 5));
+    assert_eq!(
+        rendered,
+        "error: `a` is declared twice
+  ┌─ main.txt:2:7
+  │
+1 │ let a = 1;
+  │     - first declared here
+2 │   let a = 2;
+  │       ^ declared again
+
+"
+    );
+}
+
+#[test]
+fn files_locate_the_spans_like_the_spans() {
+    let source = "é\nab = ?";
+    let input = Input::new(source, true);
+    let (rest, name) = alpha1::<_, ()>(input.slice(3..)).unwrap();
+
+    let file = input.to_simple_file("f");
+    let line = file.line_index((), name.byte_offset()).unwrap();
+    assert_eq!(line + 1, name.line());
+    assert_eq!(
+        file.column_number((), line, name.byte_offset()).unwrap(),
+        name.col()
+    );
+    assert_eq!(Range::from(&name), 3..5);
+    assert_eq!(name.byte_range_to(&rest), 3..5);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "the file must be made from the span over the whole input")]
+fn files_are_made_from_the_whole_input() {
+    let _ = Input::new("let a = 1;", true)
+        .slice(4..)
+        .to_simple_file("main.txt");
+}
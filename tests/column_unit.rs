@@ -0,0 +1,78 @@
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::{ColumnUnit, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn utf16(input: &str) -> Span<'_> {
+    Spanned::builder(input)
+        .column_unit(ColumnUnit::Utf16)
+        .build()
+}
+
+#[test]
+fn utf16_columns_count_astral_chars_twice() {
+    let span = utf16("a🙌b");
+
+    assert_eq!(span.slice(1..).col(), 2);
+    assert_eq!(span.slice(5..).col(), 4);
+    assert_eq!(span.slice(6..).col(), 5);
+    assert_eq!(span.slice(6..).char_offset(), 3);
+}
+
+#[test]
+fn utf16_columns_on_a_mixed_line() {
+    let span = utf16("x\né€🙌😀z");
+
+    // é and € are in the BMP, 🙌 and 😀 are not
+    assert_eq!(span.slice(2..).pos(), Position::new(2, 1, 2));
+    assert_eq!(span.slice(7..).pos(), Position::new(2, 3, 7));
+    assert_eq!(span.slice(15..).pos(), Position::new(2, 7, 15));
+    assert_eq!(span.slice(16..).pos(), Position::new(2, 8, 16));
+}
+
+#[test]
+fn utf16_columns_survive_multiple_slices() {
+    let span = utf16("é🙌\n😀😀a");
+
+    let mut rest = span;
+    while !rest.is_empty() {
+        rest = take::<_, _, ()>(1usize).parse(rest).unwrap().0;
+
+        assert_eq!(rest.pos(), span.slice(rest.byte_offset()..).pos());
+    }
+
+    assert_eq!(rest.pos(), Position::new(2, 6, 16));
+}
+
+#[test]
+fn utf16_columns_of_an_astral_char_cut_by_byte_slices() {
+    let span = Spanned::builder("🙌x".as_bytes())
+        .column_unit(ColumnUnit::Utf16)
+        .build();
+
+    assert_eq!(span.slice(2..).col(), 3);
+    assert_eq!(span.slice(2..).slice(2..).col(), span.slice(4..).col());
+    assert_eq!(
+        span.slice(1..).slice(2..).slice(1..).pos(),
+        span.slice(4..).pos()
+    );
+    assert_eq!(span.slice(4..).col(), 3);
+}
+
+#[test]
+fn utf16_columns_handle_utf8() {
+    let span = utf16("🙌");
+
+    assert!(span.handle_utf8());
+    assert_eq!(span.with_utf8(true).slice(4..).col(), 3);
+    assert_eq!(span.with_utf8(false).slice(4..).col(), 5);
+}
@@ -0,0 +1,65 @@
+//! What the tests need from nom that isn't the same in nom 7 and nom 8, so the same tests run
+//! with both
+
+#![allow(dead_code, unused_imports)]
+
+#[cfg(feature = "nom8")]
+use std::ops::{Bound, RangeBounds};
+
+#[cfg(feature = "nom7")]
+pub use nom::{sequence::tuple, InputIter, InputLength, InputTake, InputTakeAtPosition, Slice};
+
+// The traits of nom 7 taking the input apart are all `Input` in nom 8
+#[cfg(feature = "nom8")]
+pub use nom::{
+    Input, Input as InputIter, Input as InputLength, Input as InputTake,
+    Input as InputTakeAtPosition,
+};
+
+/// The iterator of the items of an input with their index
+#[cfg(feature = "nom7")]
+pub type IterIndices<I> = <I as InputIter>::Iter;
+
+/// The iterator of the items of an input
+#[cfg(feature = "nom7")]
+pub type IterElements<I> = <I as InputIter>::IterElem;
+
+#[cfg(feature = "nom8")]
+pub type IterIndices<I> = <I as Input>::IterIndices;
+
+#[cfg(feature = "nom8")]
+pub type IterElements<I> = <I as Input>::Iter;
+
+/// Slice an input like `Slice` of nom 7 does, with the `take` and `take_from` of nom 8
+#[cfg(feature = "nom8")]
+pub trait Slice<R> {
+    fn slice(&self, range: R) -> Self;
+}
+
+#[cfg(feature = "nom8")]
+impl<I, R> Slice<R> for I
+where
+    I: Input,
+    R: RangeBounds<usize>,
+{
+    fn slice(&self, range: R) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.input_len(),
+        };
+
+        self.take_from(start).take(end - start)
+    }
+}
+
+/// Sequence parsers, nom 8 implementing `Parser` for the tuples themselves
+#[cfg(feature = "nom8")]
+pub fn tuple<P>(parsers: P) -> P {
+    parsers
+}
@@ -0,0 +1,112 @@
+use common::{InputLength, InputTake};
+use nom::{
+    bytes::complete::{is_a, tag, tag_no_case},
+    character::complete::{alpha1, line_ending, not_line_ending, one_of},
+    multi::many_till,
+    sequence::{preceded, terminated},
+    Compare, IResult, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+#[test]
+fn byte_tags_on_str_spans() {
+    let span = Span::new("héllo", true);
+
+    let (rest, matched) = tag::<_, _, ()>(&b"h\xc3\xa9"[..]).parse(span).unwrap();
+    assert_eq!(*matched, "hé");
+    assert_eq!(rest.pos(), Position::new(1, 3, 3));
+
+    let (rest, _) = tag::<_, _, ()>(&b"ll"[..]).parse(rest).unwrap();
+    assert_eq!(rest.pos(), Position::new(1, 5, 5));
+
+    assert!(tag::<_, _, ()>(&b"x"[..]).parse(span).is_err());
+}
+
+#[test]
+fn str_tags_on_byte_spans() {
+    let span = BytesSpan::new(b"GET / HTTP/1.1", false);
+
+    let (rest, matched) = tag::<_, _, ()>("GET").parse(span).unwrap();
+    assert_eq!(*matched, b"GET");
+    assert_eq!(rest.col(), 4);
+
+    let (rest, _) = tag::<_, _, ()>(&b" /"[..]).parse(rest).unwrap();
+    assert_eq!(rest.col(), 6);
+}
+
+#[test]
+fn tags_without_case_in_both_directions() {
+    let span = Span::new("HELLO", true);
+    let (rest, _) = tag_no_case::<_, _, ()>(&b"hel"[..]).parse(span).unwrap();
+    assert_eq!(rest.col(), 4);
+
+    let span = BytesSpan::new(b"HELLO", false);
+    let (rest, _) = tag_no_case::<_, _, ()>("hel").parse(span).unwrap();
+    assert_eq!(rest.col(), 4);
+}
+
+#[test]
+fn str_tags_in_generic_parsers() {
+    fn get<I>(input: I) -> IResult<I, I, ()>
+    where
+        I: InputTake + Compare<&'static str> + InputLength + Clone,
+    {
+        tag("GET").parse(input)
+    }
+
+    assert_eq!(get(BytesSpan::new(b"GET /", false)).unwrap().0.col(), 4);
+    assert_eq!(get(Span::new("GET /", true)).unwrap().0.col(), 4);
+}
+
+fn heredoc(input: Span<'_>) -> IResult<Span<'_>, (Span<'_>, Vec<Span<'_>>)> {
+    let (input, delimiter) = terminated(preceded(tag("<<"), alpha1), line_ending).parse(input)?;
+    let (input, (lines, _)) =
+        many_till(terminated(not_line_ending, line_ending), tag(delimiter)).parse(input)?;
+
+    Ok((input, (delimiter, lines)))
+}
+
+#[test]
+fn spans_are_tags() {
+    let span = Span::new("<<EOF\nsome text\nEOX\nEOF after", true);
+
+    let (rest, (delimiter, lines)) = heredoc(span).unwrap();
+
+    assert_eq!(*delimiter, "EOF");
+    assert_eq!(lines.len(), 2);
+    assert_eq!(*lines[1], "EOX");
+    assert_eq!(rest.pos(), Position::new(4, 4, 23));
+}
+
+#[test]
+fn spans_are_tags_without_case() {
+    let delimiter = Span::new("END", true);
+    let span = BytesSpan::new(b"end", false);
+
+    let (rest, _) = tag_no_case::<_, _, ()>(delimiter).parse(span).unwrap();
+
+    assert_eq!(rest.col(), 4);
+}
+
+#[test]
+fn spans_are_token_lists() {
+    let tokens = Span::new("+-", true);
+    let span = Span::new("-+x", true);
+
+    let (rest, sign) = one_of::<_, _, ()>(tokens).parse(span).unwrap();
+    assert_eq!(sign, '-');
+
+    let (rest, _) = is_a::<_, _, ()>(tokens).parse(rest).unwrap();
+    assert_eq!(rest.col(), 3);
+}
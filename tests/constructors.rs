@@ -0,0 +1,153 @@
+use common::tuple;
+use nom::{
+    bytes::complete::{tag, take},
+    character::complete::line_ending,
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn new_at_seeds_the_counters() {
+    let span = Span::new_at("chunk", 812, 3, 40231, true);
+
+    assert_eq!(span.line(), 812);
+    assert_eq!(span.col(), 3);
+    assert_eq!(span.byte_offset(), 40231);
+}
+
+#[test]
+fn new_at_keeps_counting_from_the_seed() {
+    let span = Span::new_at("héllo world", 812, 3, 40231, true);
+
+    let (rest, _): (_, Span) = take::<_, _, ()>(6usize).parse(span).unwrap();
+
+    assert_eq!(rest.line(), 812);
+    assert_eq!(rest.col(), 9);
+    assert_eq!(rest.byte_offset(), 40238);
+}
+
+#[test]
+fn new_at_with_newline_right_after_the_seed() {
+    fn parser(input: Span<'_>) -> IResult<Span<'_>, (Span<'_>, Span<'_>)> {
+        tuple((line_ending, tag("next"))).parse(input)
+    }
+
+    let span = Span::new_at("\nnext", 812, 3, 40231, true);
+
+    let (rest, _) = parser(span).unwrap();
+
+    assert_eq!(rest.line(), 813);
+    assert_eq!(rest.col(), 5);
+    assert_eq!(rest.byte_offset(), 40236);
+}
+
+#[test]
+fn new_at_with_consecutive_newlines_after_the_seed() {
+    let span = Span::new_at("\n\n\nx", 10, 7, 100, false);
+
+    let (rest, _): (_, Span) = take::<_, _, ()>(3usize).parse(span).unwrap();
+
+    assert_eq!(rest.line(), 13);
+    assert_eq!(rest.col(), 1);
+    assert_eq!(rest.byte_offset(), 103);
+}
+
+#[test]
+fn new_from_raw_offset_continues_like_the_original() {
+    let input = "first line\nsécond 🙌 line\nthird";
+    let original = Span::new(input, true);
+
+    let (cached, _): (_, Span) = take::<_, _, ()>(18usize).parse(original).unwrap();
+
+    let rebuilt = unsafe {
+        Span::new_from_raw_offset(
+            cached.byte_offset(),
+            cached.line(),
+            cached.col(),
+            *cached.data(),
+            true,
+        )
+    };
+
+    // The char offset can't be given back, everything else must be identical
+    assert_eq!(rebuilt.pos(), cached.pos());
+    assert_eq!(rebuilt.data(), cached.data());
+
+    let (from_cached, _): (_, Span) = take::<_, _, ()>(10usize).parse(cached).unwrap();
+    let (from_rebuilt, _): (_, Span) = take::<_, _, ()>(10usize).parse(rebuilt).unwrap();
+
+    assert_eq!(from_rebuilt.line(), from_cached.line());
+    assert_eq!(from_rebuilt.col(), from_cached.col());
+    assert_eq!(from_rebuilt.byte_offset(), from_cached.byte_offset());
+    assert_eq!(from_rebuilt.line(), 3);
+    assert_eq!(from_rebuilt.col(), 4);
+}
+
+#[test]
+fn default_is_like_new_with_utf8() {
+    assert_eq!(Span::default(), Span::new("", true));
+    assert_eq!(Spanned::<&[u8]>::default(), Spanned::new(&b""[..], true));
+    assert_eq!(
+        Spanned::<&str, (), nom_span::Ascii>::default(),
+        Spanned::ascii("")
+    );
+}
+
+#[test]
+fn default_can_be_derived_on_nodes_holding_spans() {
+    #[derive(Default)]
+    struct Node<'a> {
+        name: Span<'a>,
+        bytes: Spanned<&'a [u8]>,
+    }
+
+    let node = Node::default();
+
+    assert_eq!(node.name.pos(), nom_span::Position::new(1, 1, 0));
+    assert!(node.name.handle_utf8());
+    assert!(node.bytes.data().is_empty());
+}
+
+#[test]
+fn from_str_is_like_new_with_utf8() {
+    let from: Span = "é\nabc".into();
+    let new = Span::new("é\nabc", true);
+
+    assert_eq!(from, new);
+
+    let (from_rest, _): (_, Span) = take::<_, _, ()>(3usize).parse(from).unwrap();
+    let (new_rest, _): (_, Span) = take::<_, _, ()>(3usize).parse(new).unwrap();
+
+    assert_eq!(from_rest, new_rest);
+    assert_eq!((from_rest.line(), from_rest.col()), (2, 2));
+
+    let raw: &str = from_rest.into();
+
+    assert_eq!(raw, "bc");
+}
+
+#[test]
+fn from_bytes_is_like_new_with_utf8() {
+    use common::Slice;
+
+    let input = "é\nabc".as_bytes();
+    let from: Spanned<&[u8]> = input.into();
+    let new = Spanned::new(input, true);
+
+    assert_eq!(from.slice(4..), new.slice(4..));
+    assert_eq!(from.slice(4..).col(), 2);
+
+    let raw: &[u8] = from.slice(4..).into();
+
+    assert_eq!(raw, b"bc");
+}
@@ -0,0 +1,56 @@
+use common::Slice;
+use nom::{
+    character::complete::{char, digit1, multispace0},
+    combinator::map_res,
+    sequence::{preceded, separated_pair},
+    IResult, Parser,
+};
+use nom_span::{consumed_span, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn number(input: Span<'_>) -> IResult<Span<'_>, u32> {
+    map_res(digit1, |digits: Span<'_>| digits.parse()).parse(input)
+}
+
+#[test]
+fn the_consumed_span_starts_where_the_parser_started() {
+    let span = Span::new("é\n12, 3;", true).slice(3..);
+
+    let res: IResult<Span<'_>, _> = consumed_span(separated_pair(
+        number,
+        char(','),
+        preceded(multispace0, number),
+    ))
+    .parse(span);
+    let (rest, (consumed, (left, right))) = res.unwrap();
+
+    assert_eq!((left, right), (12, 3));
+    assert_eq!(*consumed, "12, 3");
+    assert_eq!(
+        (consumed.line(), consumed.col(), consumed.byte_offset()),
+        (span.line(), span.col(), span.byte_offset())
+    );
+    assert_eq!(consumed.len(), rest.byte_offset() - span.byte_offset());
+    assert_eq!(consumed.end_position(), rest.pos());
+}
+
+#[test]
+fn consuming_nothing_gives_an_empty_span() {
+    let span = Span::new("ab", true).slice(1..);
+
+    let res: IResult<Span<'_>, _> = consumed_span(multispace0).parse(span);
+    let (rest, (consumed, _)) = res.unwrap();
+
+    assert_eq!(*consumed, "");
+    assert_eq!(consumed.pos(), span.pos());
+    assert_eq!(rest, span);
+}
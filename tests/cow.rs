@@ -0,0 +1,164 @@
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{alpha1, anychar, line_ending, multispace0, not_line_ending},
+    multi::{many0, many1},
+    sequence::{delimited, terminated},
+    IResult, Parser,
+};
+use nom_span::{CowStr, Position, Spanned};
+use std::borrow::Cow;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type CowSpan<'a> = Spanned<CowStr<'a>>;
+
+fn words(input: Span<'_>) -> IResult<Span<'_>, Vec<Span<'_>>> {
+    many0(terminated(alpha1, multispace0)).parse(input)
+}
+
+fn cow_words(input: CowSpan<'_>) -> IResult<CowSpan<'_>, Vec<CowSpan<'_>>> {
+    many0(terminated(alpha1, multispace0)).parse(input)
+}
+
+/// Replace the `\r\n` by `\n`, only allocating when there's one
+fn normalize(input: &str) -> Cow<'_, str> {
+    if input.contains("\r\n") {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+#[test]
+fn borrowed_spans_parse_like_str() {
+    for input in ["hello wörld\nagain", "a\r\nb", ""] {
+        let cow = Spanned::new(normalize(input), true);
+        let normalized = normalize(input);
+
+        let on_cow: Vec<Position> = words(cow.to_borrowed())
+            .unwrap()
+            .1
+            .iter()
+            .map(Span::pos)
+            .collect();
+        let on_str: Vec<Position> = words(Span::new(&normalized, true))
+            .unwrap()
+            .1
+            .iter()
+            .map(Span::pos)
+            .collect();
+
+        assert_eq!(on_cow, on_str, "on {:?}", input);
+    }
+}
+
+#[test]
+fn owned_data_is_parsed_through_a_borrow() {
+    let cow = Spanned::new(normalize("key\r\nvalue"), true);
+    assert!(matches!(cow.data(), Cow::Owned(_)));
+
+    let (rest, _) = terminated(alpha1::<_, ()>, line_ending)
+        .parse(cow.to_borrowed())
+        .unwrap();
+
+    assert_eq!(*rest, "value");
+    assert_eq!(rest.pos(), Position::new(2, 1, 4));
+}
+
+#[test]
+fn conversions_keep_the_position_and_the_extra() {
+    let span = Spanned::new_extra("say \"hi\"", true, "file.txt");
+    let (quoted, _) = tag::<_, _, ()>("say ").parse(span).unwrap();
+    let cow = quoted.map_data(Cow::Borrowed);
+
+    let borrowed = cow.to_borrowed();
+    assert_eq!(borrowed, quoted);
+    assert_eq!(*borrowed.extra(), "file.txt");
+
+    let (_, inner) = delimited(tag::<_, _, ()>("\""), take_until("\""), tag("\""))
+        .parse(borrowed)
+        .unwrap();
+    assert_eq!(inner.col(), 6);
+
+    let owned = cow.into_owned();
+    assert_eq!(owned.data(), "\"hi\"");
+    assert_eq!(owned.pos(), quoted.pos());
+    assert_eq!(*owned.extra(), "file.txt");
+}
+
+#[test]
+fn byte_cows_are_borrowed_as_slices() {
+    let cow: Cow<'_, [u8]> = Cow::Owned(b"ab\ncd".to_vec());
+    let span = Spanned::new(cow, false);
+
+    let (rest, _) = take_until::<_, _, ()>("\n")
+        .parse(span.to_borrowed())
+        .unwrap();
+
+    assert_eq!(*rest, b"\ncd");
+    assert_eq!(rest.col(), 3);
+    assert_eq!(span.into_owned().data(), b"ab\ncd");
+}
+
+#[test]
+fn cow_strs_parse_like_str() {
+    for input in ["hello wörld\nagain", "a\r\nb", ""] {
+        let normalized = normalize(input);
+        let cow: CowSpan<'_> = Spanned::new(normalize(input), true).into();
+
+        let on_cow: Vec<Position> = cow_words(cow).unwrap().1.iter().map(CowSpan::pos).collect();
+        let on_str: Vec<Position> = words(Span::new(&normalized, true))
+            .unwrap()
+            .1
+            .iter()
+            .map(Span::pos)
+            .collect();
+
+        assert_eq!(on_cow, on_str, "on {:?}", input);
+    }
+}
+
+#[test]
+fn owned_cow_strs_are_parsed_directly() {
+    let cow: CowSpan<'_> = Spanned::new(normalize("key\r\nvalue"), true).into();
+
+    let (rest, key) = terminated(alpha1::<_, ()>, line_ending).parse(cow).unwrap();
+
+    assert_eq!(*key, "key");
+    assert_eq!(*rest, "value");
+    assert_eq!(rest.pos(), Position::new(2, 1, 4));
+}
+
+// The tests of `str.rs` on a `CowStr`
+#[test]
+fn cow_strs_utf8_vs_ascii() {
+    let utf8 = CowSpan::new(CowStr::from(Cow::Owned("🙌".to_owned())), true);
+    let ascii = CowSpan::new(CowStr::from(Cow::Borrowed("🙌")), false);
+
+    let utf8_after: IResult<CowSpan<'_>, Vec<char>> = many1(anychar).parse(utf8);
+    let ascii_after: IResult<CowSpan<'_>, Vec<char>> = many1(anychar).parse(ascii);
+
+    let (utf8_after, _) = utf8_after.unwrap();
+    let (ascii_after, _) = ascii_after.unwrap();
+
+    assert_eq!(utf8_after.col(), 2);
+    assert_eq!(ascii_after.col(), 5);
+}
+
+#[test]
+fn cow_strs_can_compare_with_different_type() {
+    fn until_newline(input: CowSpan<'_>) -> IResult<CowSpan<'_>, CowSpan<'_>> {
+        not_line_ending(input)
+    }
+    let utf8 = CowSpan::new(CowStr::from(Cow::Owned("test\n".to_owned())), true);
+
+    let (utf8_after, comment) = until_newline(utf8).unwrap();
+
+    assert_eq!(*comment, "test");
+    assert_eq!(*utf8_after, "\n");
+}
@@ -0,0 +1,44 @@
+use common::Slice;
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn text_of_the_first_line() {
+    let input = "let a = 1;\nlet b = 2;\n";
+    let span = Span::new(input, true);
+
+    assert_eq!(span.current_line_text(input), "let a = 1;");
+    assert_eq!(span.slice(4..).current_line_text(input), "let a = 1;");
+    assert_eq!(span.slice(10..).current_line_text(input), "let a = 1;");
+}
+
+#[test]
+fn text_of_a_line_after_multibyte_chars() {
+    let input = "é\n🙌 é x\r\nz";
+    let x = Span::new(input, true).slice(11..);
+
+    assert_eq!(x.col(), 5);
+    assert_eq!(x.current_line_text(input), "🙌 é x");
+}
+
+#[test]
+fn text_of_the_last_unterminated_line() {
+    let input = "a\nb\n  end";
+    let span = Span::new(input, true);
+
+    assert_eq!(span.slice(6..).current_line_text(input), "  end");
+    assert_eq!(span.slice(input.len()..).current_line_text(input), "  end");
+    assert_eq!(
+        Span::new("a\n", true).slice(2..).current_line_text("a\n"),
+        ""
+    );
+}
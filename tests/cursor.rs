@@ -0,0 +1,98 @@
+use common::Slice;
+use nom::{
+    character::complete::{digit1, multispace0},
+    IResult,
+};
+use nom_span::{Position, Spanned, SpannedCursor};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Ident(&'a str, Position),
+    Number(&'a str, Position),
+    Arrow(Position),
+    Punct(char, Position),
+}
+
+/// Lex with the cursor, the numbers being parsed with nom
+fn lex(input: &str) -> Vec<Token<'_>> {
+    let mut cursor = SpannedCursor::new(Span::new(input, true));
+    let mut tokens = Vec::new();
+
+    loop {
+        let res: IResult<Span<'_>, Span<'_>, ()> = multispace0(cursor.rest());
+        cursor.set_rest(res.unwrap().0);
+
+        let pos = cursor.pos();
+        match cursor.peek() {
+            None => break,
+            Some(c) if c.is_alphabetic() => {
+                let ident = cursor.eat_while(char::is_alphanumeric);
+                tokens.push(Token::Ident(ident.into_data(), pos));
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let res: IResult<Span<'_>, Span<'_>, ()> = digit1(cursor.rest());
+                let (rest, digits) = res.unwrap();
+                cursor.set_rest(rest);
+                tokens.push(Token::Number(digits.into_data(), pos));
+            }
+            Some(_) if cursor.eat_str("->") => tokens.push(Token::Arrow(pos)),
+            Some(_) => tokens.push(Token::Punct(cursor.bump().unwrap(), pos)),
+        }
+    }
+
+    tokens
+}
+
+#[test]
+fn cursors_and_parsers_can_be_mixed() {
+    assert_eq!(
+        lex("été -> 42;\n  x2 (7)"),
+        [
+            Token::Ident("été", Position::new(1, 1, 0)),
+            Token::Arrow(Position::new(1, 5, 6)),
+            Token::Number("42", Position::new(1, 8, 9)),
+            Token::Punct(';', Position::new(1, 10, 11)),
+            Token::Ident("x2", Position::new(2, 3, 15)),
+            Token::Punct('(', Position::new(2, 6, 18)),
+            Token::Number("7", Position::new(2, 7, 19)),
+            Token::Punct(')', Position::new(2, 8, 20)),
+        ]
+    );
+}
+
+#[test]
+fn every_step_matches_the_slices() {
+    let span = Span::new("aé\n\tb->c", true);
+    let mut cursor = SpannedCursor::from(span);
+
+    assert_eq!(cursor.peek_second(), Some('é'));
+    assert_eq!(cursor.bump(), Some('a'));
+    assert_eq!(cursor.rest(), span.slice(1..));
+    assert!(!cursor.eat('x'));
+    assert!(cursor.eat('é'));
+    assert!(cursor.eat('\n'));
+    assert_eq!(cursor.rest(), span.slice(4..));
+    assert_eq!(cursor.pos(), Position::new(2, 1, 4));
+
+    let eaten = cursor.eat_while(|c| c != '-');
+    assert_eq!(eaten, span.slice(4..6));
+    assert!(!cursor.eat_str("=>"));
+    assert!(cursor.eat_str("->"));
+    assert_eq!(cursor.rest(), span.slice(8..));
+
+    assert_eq!(cursor.bump(), Some('c'));
+    assert!(cursor.is_eof());
+    assert_eq!(cursor.bump(), None);
+    assert_eq!(cursor.peek(), None);
+    assert_eq!(cursor.into_rest(), span.slice(span.len()..));
+}
@@ -0,0 +1,116 @@
+use common::tuple;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{anychar, line_ending},
+    multi::many1,
+    IResult, Parser,
+};
+use nom_span::{Ascii, Spanned, Utf8};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+#[test]
+fn typed_encodings_count_like_runtime_ones() {
+    let input = "héllo\n🙌 wörld";
+
+    let dynamic_utf8 = Spanned::new(input, true);
+    let dynamic_ascii = Spanned::new(input, false);
+    let utf8 = Spanned::utf8(input);
+    let ascii = Spanned::ascii(input);
+
+    let (dynamic_utf8, _): (_, Vec<char>) = many1(anychar::<_, ()>).parse(dynamic_utf8).unwrap();
+    let (dynamic_ascii, _): (_, Vec<char>) = many1(anychar::<_, ()>).parse(dynamic_ascii).unwrap();
+    let (utf8, _): (_, Vec<char>) = many1(anychar::<_, ()>).parse(utf8).unwrap();
+    let (ascii, _): (_, Vec<char>) = many1(anychar::<_, ()>).parse(ascii).unwrap();
+
+    assert_eq!(utf8.line(), dynamic_utf8.line());
+    assert_eq!(utf8.col(), dynamic_utf8.col());
+    assert_eq!(utf8.col(), 8);
+    assert_eq!(utf8.byte_offset(), dynamic_utf8.byte_offset());
+
+    assert_eq!(ascii.line(), dynamic_ascii.line());
+    assert_eq!(ascii.col(), dynamic_ascii.col());
+    assert_eq!(ascii.col(), 12);
+    assert_eq!(ascii.byte_offset(), dynamic_ascii.byte_offset());
+}
+
+#[test]
+fn typed_encodings_work_with_nom_parsers() {
+    type Span<'a, C> = Spanned<&'a str, (), C>;
+
+    fn parser<C: nom_span::Encoding>(
+        input: Span<'_, C>,
+    ) -> IResult<Span<'_, C>, (Span<'_, C>, Span<'_, C>)> {
+        tuple((tag("ça"), line_ending)).parse(input)
+    }
+
+    let (utf8, _) = parser(Spanned::utf8("ça\nva")).unwrap();
+    let (ascii, _) = parser(Spanned::<_, (), Ascii>::ascii("ça\nva")).unwrap();
+
+    assert_eq!(utf8.line(), 2);
+    assert_eq!(utf8.col(), 1);
+    assert_eq!(ascii.line(), 2);
+    assert_eq!(ascii.col(), 1);
+    assert_eq!(*utf8.data(), "va");
+}
+
+#[test]
+fn typed_encodings_are_smaller() {
+    assert!(std::mem::size_of::<Spanned<&str, (), Utf8>>() < std::mem::size_of::<Spanned<&str>>());
+    assert!(std::mem::size_of::<Spanned<&str, (), Ascii>>() < std::mem::size_of::<Spanned<&str>>());
+}
+
+// A span over a `&str` is copied on every slice, so it shouldn't grow unnoticed
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn spans_over_str_stay_small() {
+    assert_eq!(std::mem::size_of::<Spanned<&str>>(), 72);
+    assert_eq!(std::mem::size_of::<Spanned<&str, (), Utf8>>(), 40);
+    assert_eq!(std::mem::size_of::<Spanned<&str, (), Ascii>>(), 40);
+}
+
+// The typed encodings keep nothing but the line and the column, so they are smaller than the
+// 48 bytes a span took before the char offset and the source ids
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn typed_encodings_keep_only_the_line_and_the_column() {
+    assert!(std::mem::size_of::<Spanned<&str, (), Utf8>>() < 48);
+    assert!(std::mem::size_of::<Spanned<&str, (), Ascii>>() < 48);
+}
+
+#[test]
+fn handle_utf8_reports_the_encoding() {
+    assert!(Spanned::new("", true).handle_utf8());
+    assert!(!Spanned::new("", false).handle_utf8());
+    assert!(Spanned::utf8("").handle_utf8());
+    assert!(!Spanned::ascii("").handle_utf8());
+}
+
+#[test]
+fn with_utf8_only_changes_the_counting_from_that_point() {
+    use common::Slice;
+
+    let header = Spanned::new("é: 1\nbody: 🙌é", false);
+
+    let body = header.slice(6..);
+
+    assert_eq!((body.line(), body.col()), (2, 1));
+    assert!(!body.handle_utf8());
+
+    let ascii_rest = body.slice(6..);
+    let utf8 = body.with_utf8(true);
+    let utf8_rest = utf8.slice(6..);
+
+    assert!(utf8.handle_utf8());
+    assert_eq!(utf8.pos(), body.pos());
+    assert_eq!(ascii_rest.col(), 7);
+    assert_eq!(utf8_rest.col(), 7);
+    assert_eq!(ascii_rest.slice(6..).col(), 13);
+    assert_eq!(utf8_rest.slice(6..).col(), 9);
+}
@@ -0,0 +1,201 @@
+use common::Slice;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char, digit1, line_ending, multispace0, space0},
+    combinator::{cut, map, map_res},
+    error::{context, ErrorKind},
+    multi::{many1, separated_list0},
+    sequence::{delimited, preceded, separated_pair, terminated},
+    Err, IResult, Parser,
+};
+use nom_span::{Position, Spanned, SpannedError};
+use std::error::Error;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// `<name> = <number>` lines
+fn assignments<E>(input: Input<'_>) -> IResult<Input<'_>, Vec<(Input<'_>, Input<'_>)>, E>
+where
+    for<'a> E: nom::error::ParseError<Input<'a>>,
+{
+    many1(terminated(
+        separated_pair(
+            alpha1,
+            cut(delimited(space0, tag("="), space0)),
+            cut(digit1),
+        ),
+        cut(line_ending),
+    ))
+    .parse(input)
+}
+
+/// Parse with the error not borrowing the input
+fn parse<E>(source: &str) -> Result<usize, E>
+where
+    for<'a> E: nom::error::ParseError<Input<'a>>,
+{
+    match assignments::<E>(Spanned::new(source, true)) {
+        Ok((_, assignments)) => Ok(assignments.len()),
+        Err(Err::Error(e) | Err::Failure(e)) => Err(e),
+        Err(Err::Incomplete(_)) => unreachable!(),
+    }
+}
+
+#[test]
+fn errors_keep_the_position_where_the_parser_failed() {
+    let source = "a = 1\nbé = 2\n";
+    let error = parse::<SpannedError>(source).unwrap_err();
+
+    assert_eq!(
+        error,
+        SpannedError::new(Position::new(2, 2, 7), ErrorKind::Tag, ())
+    );
+    assert_eq!(error.to_string(), "expected Tag at line 2, column 2");
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn errors_can_keep_the_start_of_the_rest() {
+    let source = "a = 1\nb = x + 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9 + 10 + 11\n";
+    let error = parse::<SpannedError<String>>(source).unwrap_err();
+
+    assert_eq!(error.position, Position::new(2, 5, 10));
+    assert_eq!(error.kind, ErrorKind::Digit);
+    assert_eq!(error.excerpt, "x + 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9 + 10 +");
+    assert_eq!(
+        error.to_string(),
+        "expected Digit at line 2, column 5, found \"x + 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9 + 10 +\""
+    );
+
+    let error = parse::<SpannedError<String>>("a = 1\nb =").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected Digit at line 2, column 4, found the end of the input"
+    );
+}
+
+#[test]
+fn errors_are_static() {
+    fn boxed(source: &str) -> Box<dyn Error + Send + Sync + 'static> {
+        Box::new(parse::<SpannedError<String>>(source).unwrap_err())
+    }
+
+    let error = boxed("a = 1\nb = é");
+    assert_eq!(
+        error.to_string(),
+        "expected Digit at line 2, column 5, found \"é\""
+    );
+}
+
+/// A JSON-like value, with objects and numbers
+fn value(input: Input<'_>) -> IResult<Input<'_>, (), SpannedError> {
+    let member = separated_pair(
+        context("key", delimited(char('"'), alpha1, char('"'))),
+        cut(preceded(multispace0, char(':'))),
+        cut(value),
+    );
+    let object = delimited(
+        char('{'),
+        separated_list0(
+            preceded(multispace0, char(',')),
+            preceded(multispace0, context("member", member)),
+        ),
+        cut(preceded(multispace0, char('}'))),
+    );
+
+    let (rest, _) = preceded(
+        multispace0,
+        alt((
+            map(context("object", object), |_| ()),
+            map(context("number", digit1), |_| ()),
+        )),
+    )
+    .parse(input)?;
+
+    Ok((rest, ()))
+}
+
+#[test]
+fn contexts_are_kept_with_their_position() {
+    let source = "{\n  \"a\": 1,\n  \"b\": {\n    \"c\": x\n  }\n}\n";
+    let Err(Err::Failure(error)) = value(Spanned::new(source, true)) else {
+        panic!("the value should be invalid");
+    };
+
+    let contexts: Vec<_> = error
+        .contexts
+        .iter()
+        .map(|(position, context)| (*context, position.line, position.col))
+        .collect();
+
+    assert_eq!((error.position.line, error.position.col), (4, 10));
+    assert_eq!(
+        contexts,
+        [
+            ("number", 4, 10),
+            ("member", 4, 5),
+            ("object", 3, 8),
+            ("member", 3, 3),
+            ("object", 1, 1),
+        ]
+    );
+    assert_eq!(
+        error.to_string(),
+        "expected Digit at line 4, column 10
+    in number at line 4, column 10
+    in member at line 4, column 5
+    in object at line 3, column 8
+    in member at line 3, column 3
+    in object at line 1, column 1"
+    );
+}
+
+/// `<number>` lines, the number fitting in a `u8`
+fn bytes(input: Input<'_>) -> IResult<Input<'_>, Vec<u8>, SpannedError> {
+    many1(terminated(
+        cut(map_res(digit1, |digits: Input<'_>| digits.parse::<u8>())),
+        line_ending,
+    ))
+    .parse(input)
+}
+
+#[test]
+fn errors_of_map_res_are_kept_with_the_position() {
+    let source = "12\n300\n";
+    let Err(Err::Failure(error)) = bytes(Spanned::new(source, true)) else {
+        panic!("300 shouldn't fit in a u8");
+    };
+
+    let external = error.source().unwrap();
+    assert_eq!(external.downcast_ref(), "300".parse::<u8>().err().as_ref());
+    assert_eq!((error.position.line, error.position.col), (2, 1));
+    assert_eq!(error.kind, ErrorKind::MapRes);
+    assert_eq!(
+        error.to_string(),
+        "number too large to fit in target type at line 2, column 1"
+    );
+}
+
+#[test]
+fn nom_errors_convert_to_errors_not_borrowing_the_input() {
+    let input = Spanned::new("a = é and more", true);
+    let error = nom::error::Error::new(input.slice(4..), ErrorKind::Digit);
+
+    assert_eq!(
+        SpannedError::from_nom_error(&error, 3),
+        SpannedError::new(Position::new(1, 5, 4), ErrorKind::Digit, "é a".to_owned())
+    );
+    assert_eq!(
+        SpannedError::<()>::from(error),
+        SpannedError::new(Position::new(1, 5, 4), ErrorKind::Digit, ())
+    );
+}
@@ -0,0 +1,87 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{escaped, escaped_transform, tag},
+    character::complete::{none_of, one_of},
+    combinator::value,
+    sequence::delimited,
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+fn literal(input: Span<'_>) -> IResult<Span<'_>, String> {
+    delimited(
+        tag("\""),
+        escaped_transform(
+            none_of("\\\""),
+            '\\',
+            alt((
+                value("\\", tag("\\")),
+                value("\"", tag("\"")),
+                value("\n", tag("n")),
+            )),
+        ),
+        tag("\""),
+    )
+    .parse(input)
+}
+
+#[test]
+fn escaped_transform_collects_into_a_string() {
+    let (rest, output) = literal(Span::new(r#""héllo \"wörld\"\n"; x"#, true)).unwrap();
+
+    assert_eq!(output, "héllo \"wörld\"\n");
+    assert_eq!(*rest, "; x");
+    assert_eq!(rest.col(), 20);
+    assert_eq!(rest.byte_offset(), 21);
+}
+
+#[test]
+fn escaped_recognizes_the_literal() {
+    let span = Span::new(r#"a\"é\\b" x"#, true);
+
+    let res: IResult<_, _> = escaped(none_of("\\\""), '\\', one_of("\"\\")).parse(span);
+    let (rest, raw) = res.unwrap();
+
+    assert_eq!(*raw, r#"a\"é\\b"#);
+    assert_eq!(raw.col(), 1);
+    assert_eq!(*rest, "\" x");
+    assert_eq!(rest.col(), 8);
+}
+
+#[test]
+fn escaped_transform_collects_bytes_into_a_vec() {
+    let span = BytesSpan::new(b"ab\\tc\\\\;", true);
+
+    let res: IResult<_, Vec<u8>> = escaped_transform(
+        nom::character::complete::alpha1,
+        '\\',
+        alt((value(&b"\t"[..], tag("t")), value(&b"\\"[..], tag("\\")))),
+    )
+    .parse(span);
+    let (rest, output) = res.unwrap();
+
+    assert_eq!(output, b"ab\tc\\");
+    assert_eq!(*rest, b";");
+    assert_eq!(rest.col(), 8);
+}
+
+#[test]
+fn escaped_works_on_byte_spans() {
+    let span = BytesSpan::new("é\\nb;".as_bytes(), true);
+
+    let res: IResult<_, _> = escaped(none_of("\\;"), '\\', one_of("n"))(span);
+    let (rest, raw) = res.unwrap();
+
+    assert_eq!(*raw, "é\\nb".as_bytes());
+    assert_eq!(rest.col(), 5);
+    assert_eq!(rest.byte_offset(), 5);
+}
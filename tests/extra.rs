@@ -0,0 +1,76 @@
+use common::tuple;
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::line_ending,
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileId(u32);
+
+type Span<'a> = Spanned<&'a str, FileId>;
+
+#[test]
+fn extra_is_kept_while_parsing() {
+    fn parser(input: Span<'_>) -> IResult<Span<'_>, (Span<'_>, Span<'_>, Span<'_>)> {
+        tuple((tag("let"), line_ending, take_until(";"))).parse(input)
+    }
+
+    let span = Span::new_extra("let\nx = 1;", true, FileId(7));
+
+    let (rest, (keyword, _, value)) = parser(span).unwrap();
+
+    assert_eq!(*keyword.extra(), FileId(7));
+    assert_eq!(*value.extra(), FileId(7));
+    assert_eq!(*rest.extra(), FileId(7));
+    assert_eq!((rest.line(), rest.col()), (2, 6));
+}
+
+#[test]
+fn extra_can_be_a_non_copy_type() {
+    let span = Spanned::new_extra("hello world", false, String::from("main.rs"));
+
+    let (rest, _): (_, Spanned<&str, String>) = tag::<_, _, ()>("hello ").parse(span).unwrap();
+
+    assert_eq!(rest.extra(), "main.rs");
+    assert_eq!(rest.col(), 7);
+}
+
+#[test]
+fn default_extra_keeps_existing_code_working() {
+    let span: Spanned<&str> = Spanned::new("abc", true);
+
+    assert_eq!(*span.extra(), ());
+}
+
+#[test]
+fn map_extra_after_partial_consumption() {
+    let span = Spanned::new("let\nx = 1;", true);
+
+    let (rest, _): (_, Spanned<&str>) = tuple((tag::<_, _, ()>("let"), line_ending))
+        .parse(span)
+        .map(|(rest, (keyword, _))| (rest, keyword))
+        .unwrap();
+
+    let tagged = rest.map_extra(|()| FileId(3));
+
+    assert_eq!(*tagged.extra(), FileId(3));
+    assert_eq!(*tagged.data(), *rest.data());
+    assert_eq!(tagged.line(), rest.line());
+    assert_eq!(tagged.col(), rest.col());
+    assert_eq!(tagged.byte_offset(), rest.byte_offset());
+
+    let (after, _): (_, Span) = tag::<_, _, ()>("x").parse(tagged).unwrap();
+
+    assert_eq!(*after.extra(), FileId(3));
+    assert_eq!((after.line(), after.col(), after.byte_offset()), (2, 2, 5));
+}
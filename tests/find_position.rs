@@ -0,0 +1,60 @@
+use common::Slice;
+use nom::FindSubstring;
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn needles_after_several_newlines() {
+    let span = Span::new("a\nb\n\n  key = 1", true).slice(2..);
+
+    let pos = span.find_substring_position("key").unwrap();
+
+    assert_eq!(pos, Position::new(4, 3, 7));
+    assert_eq!(pos, span.slice(span.find_substring("key").unwrap()..).pos());
+    assert_eq!(span.find_char_position('='), Some(Position::new(4, 7, 11)));
+}
+
+#[test]
+fn needles_with_multibyte_chars() {
+    let span = Span::new("🙌 é\nçà été", true);
+
+    assert_eq!(
+        span.find_substring_position("été"),
+        Some(Position::new(2, 4, 13))
+    );
+    assert_eq!(span.find_char_position('à'), Some(Position::new(2, 2, 10)));
+    assert_eq!(
+        Span::new("🙌 é", false).find_char_position('é'),
+        Some(Position::new(1, 6, 5))
+    );
+}
+
+#[test]
+fn missing_needles_have_no_position() {
+    let span = Span::new("abc", true);
+
+    assert_eq!(span.find_substring_position("x"), None);
+    assert_eq!(span.find_char_position('é'), None);
+    assert_eq!(span.find_substring_position(""), Some(span.pos()));
+}
+
+#[test]
+fn needles_in_bytes() {
+    let span = Spanned::new(&b"ab\n\xFF\xFEcd"[..], true);
+
+    assert_eq!(
+        span.find_substring_position(b"cd"),
+        Some(Position::new(2, 3, 5))
+    );
+    assert_eq!(span.find_byte_position(b'\n'), Some(Position::new(1, 3, 2)));
+    assert_eq!(span.find_byte_position(b'x'), None);
+}
@@ -0,0 +1,119 @@
+use nom::{
+    bytes::complete::{is_a, tag, take_until},
+    sequence::terminated,
+    IResult, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+struct Header<'a> {
+    name: BytesSpan<'a>,
+    value: BytesSpan<'a>,
+}
+
+fn header(input: BytesSpan<'_>) -> IResult<BytesSpan<'_>, Header<'_>> {
+    let (input, name) = take_until(":").parse(input)?;
+    let (input, _) = tag(": ").parse(input)?;
+    let (input, value) = terminated(take_until("\r\n"), tag("\r\n")).parse(input)?;
+
+    Ok((input, Header { name, value }))
+}
+
+#[test]
+fn str_needles_on_byte_spans() {
+    let span = BytesSpan::new(b"Host: example.com\r\nAccept: */*\r\n", false);
+
+    let (rest, host) = header(span).unwrap();
+    assert_eq!(*host.name, b"Host");
+    assert_eq!(*host.value, b"example.com");
+    assert_eq!(host.value.pos(), Position::new(1, 7, 6));
+    assert_eq!(rest.pos(), Position::new(2, 1, 19));
+
+    let (rest, accept) = header(rest).unwrap();
+    assert_eq!(*accept.name, b"Accept");
+    assert_eq!(accept.value.pos(), Position::new(2, 9, 27));
+    assert_eq!(rest.pos(), Position::new(3, 1, 32));
+}
+
+#[test]
+fn byte_needles_on_byte_spans() {
+    let span = BytesSpan::new(b"ab\0cd", false);
+
+    let (rest, before) = take_until::<_, _, ()>(&b"\0"[..]).parse(span).unwrap();
+
+    assert_eq!(*before, b"ab");
+    assert_eq!(rest.col(), 3);
+}
+
+#[test]
+fn needles_with_another_lifetime() {
+    let input = String::from("key=value");
+    let needle = String::from("=");
+
+    let span = Span::new(&input, true);
+    let (rest, key) = take_until::<_, Span<'_>, ()>(needle.as_str())
+        .parse(span)
+        .unwrap();
+
+    assert_eq!(*key, "key");
+    assert_eq!(rest.col(), 4);
+}
+
+#[test]
+fn needles_from_a_string() {
+    let needle = String::from("\r\n");
+    let span = BytesSpan::new(b"a\r\nb", false);
+
+    let (rest, _) = take_until::<_, _, ()>(&*needle).parse(span).unwrap();
+
+    assert_eq!(rest.col(), 2);
+}
+
+#[test]
+fn str_needles_on_str_spans() {
+    let span = Span::new("héllo wörld", true);
+
+    let (rest, _) = take_until::<_, _, ()>("wö").parse(span).unwrap();
+
+    assert_eq!(rest.pos(), Position::new(1, 7, 7));
+}
+
+fn fenced(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    let (input, fence) = terminated(is_a("`~"), tag("\n")).parse(input)?;
+    let (input, code) = take_until(fence).parse(input)?;
+    let (input, _) = tag(fence).parse(input)?;
+
+    Ok((input, code))
+}
+
+#[test]
+fn spans_are_needles() {
+    let span = Span::new("````\nlet a = \"```\";\n````\nafter", true);
+
+    let (rest, code) = fenced(span).unwrap();
+
+    assert_eq!(*code, "let a = \"```\";\n");
+    assert_eq!(code.pos(), Position::new(2, 1, 5));
+    assert_eq!(rest.pos(), Position::new(3, 5, 24));
+}
+
+#[test]
+fn spans_are_needles_on_byte_spans() {
+    let needle = Span::new("--", true);
+    let span = BytesSpan::new(b"a-b--c", false);
+
+    let (rest, _) = take_until::<_, _, ()>(needle).parse(span).unwrap();
+    assert_eq!(rest.col(), 4);
+
+    let needle = BytesSpan::new(b"-c", false);
+    let (rest, _) = take_until::<_, _, ()>(needle).parse(rest).unwrap();
+    assert_eq!(rest.col(), 5);
+}
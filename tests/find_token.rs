@@ -0,0 +1,127 @@
+use nom::{
+    bytes::complete::{is_a, is_not},
+    character::complete::{none_of, one_of},
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+const DIGITS: &[char] = &['1', '2', '3'];
+
+#[test]
+fn str_spans_with_str_sets() {
+    let span = Span::new("123é4", true);
+
+    let res: IResult<_, _> = is_a("321").parse(span);
+    let (rest, digits) = res.unwrap();
+    assert_eq!(*digits, "123");
+    assert_eq!(rest.col(), 4);
+
+    let res: IResult<_, _> = is_not("é").parse(span);
+    assert_eq!(*res.unwrap().1, "123");
+
+    let res: IResult<_, _> = one_of("12").parse(span);
+    assert_eq!(res.unwrap().1, '1');
+
+    let res: IResult<_, _> = none_of("23").parse(span);
+    assert_eq!(res.unwrap().1, '1');
+}
+
+#[test]
+fn str_spans_with_char_slices() {
+    let span = Span::new("123é4", true);
+
+    let res: IResult<_, _> = is_a(DIGITS).parse(span);
+    let (rest, digits) = res.unwrap();
+    assert_eq!(*digits, "123");
+    assert_eq!(rest.col(), 4);
+
+    let res: IResult<_, _> = is_not(&['é'][..]).parse(span);
+    assert_eq!(*res.unwrap().1, "123");
+
+    let res: IResult<_, _> = one_of(DIGITS).parse(span);
+    assert_eq!(res.unwrap().1, '1');
+
+    let res: IResult<_, _> = none_of(&['2', '3'][..]).parse(span);
+    assert_eq!(res.unwrap().1, '1');
+}
+
+#[test]
+fn byte_spans_with_str_sets() {
+    let span = BytesSpan::new(b"abc;d", false);
+
+    let res: IResult<_, _> = is_a("cba").parse(span);
+    let (rest, word) = res.unwrap();
+    assert_eq!(*word, b"abc");
+    assert_eq!(rest.col(), 4);
+
+    let res: IResult<_, _> = is_not(";").parse(span);
+    assert_eq!(*res.unwrap().1, b"abc");
+
+    let res: IResult<_, _> = one_of("ab").parse(span);
+    assert_eq!(res.unwrap().1, 'a');
+
+    let res: IResult<_, _> = none_of(";").parse(span);
+    assert_eq!(res.unwrap().1, 'a');
+}
+
+#[test]
+fn byte_spans_with_byte_slices_and_arrays() {
+    let span = BytesSpan::new(b"abc;d", false);
+
+    let res: IResult<_, _> = is_a(&b"cba"[..]).parse(span);
+    assert_eq!(*res.unwrap().1, b"abc");
+
+    let res: IResult<_, _> = is_a(*b"cba").parse(span);
+    assert_eq!(*res.unwrap().1, b"abc");
+
+    let res: IResult<_, _> = is_not(&b";"[..]).parse(span);
+    assert_eq!(*res.unwrap().1, b"abc");
+
+    let res: IResult<_, _> = is_not([b';']).parse(span);
+    let (rest, word) = res.unwrap();
+    assert_eq!(*word, b"abc");
+    assert_eq!(rest.col(), 4);
+
+    let res: IResult<_, _> = one_of(&b"ab"[..]).parse(span);
+    assert_eq!(res.unwrap().1, 'a');
+}
+
+#[test]
+fn spans_can_be_sets() {
+    let set = Span::new("321", true);
+    let span = Span::new("123é4", true);
+
+    let res: IResult<_, _> = is_a(set).parse(span);
+    assert_eq!(*res.unwrap().1, "123");
+
+    let res: IResult<_, _> = one_of(set).parse(span);
+    assert_eq!(res.unwrap().1, '1');
+
+    let set = BytesSpan::new(b"cba", false);
+    let res: IResult<_, _> = is_a(set).parse(BytesSpan::new(b"abc;d", false));
+    assert_eq!(*res.unwrap().1, b"abc");
+}
+
+#[test]
+fn closures_select_the_tokens() {
+    let span = Span::new("ab1", true);
+
+    let res: IResult<_, _> = nom::bytes::complete::take_while1(|c: char| DIGITS.contains(&c))
+        .parse(Span::new("12a", true));
+    assert_eq!(*res.unwrap().1, "12");
+
+    let res: IResult<_, _> =
+        nom::bytes::complete::take_till(|c: char| c.is_ascii_digit()).parse(span);
+    let (rest, word) = res.unwrap();
+    assert_eq!(*word, "ab");
+    assert_eq!(rest.col(), 3);
+}
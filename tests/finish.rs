@@ -0,0 +1,125 @@
+use common::Slice;
+use nom::{
+    bytes::{complete::tag, streaming},
+    character::complete::digit1,
+    combinator::cut,
+    error::{Error, ErrorKind},
+    sequence::preceded,
+    IResult, Needed, Parser,
+};
+use nom_span::{FinishWithPosition, LocatedFailure, Position, Spanned, SpannedError};
+use std::num::NonZeroUsize;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// `n=<number>`, failing without recovery after `n=`
+fn number(input: Input<'_>) -> IResult<Input<'_>, Input<'_>> {
+    preceded(tag("n="), cut(digit1)).parse(input)
+}
+
+#[test]
+fn outputs_are_kept() {
+    let input = Input::new("n=12;", true);
+
+    assert_eq!(
+        number(input).finish_with_position().unwrap().into_data(),
+        "12"
+    );
+}
+
+#[test]
+fn errors_are_located() {
+    let input = Input::new("\nm=12", true);
+    let failure = number(input.slice(1..)).finish_with_position().unwrap_err();
+
+    assert_eq!(failure.position(), Some(Position::new(2, 1, 1)));
+    assert!(failure.is_recoverable());
+    assert_eq!(failure.error().unwrap().code, ErrorKind::Tag);
+    assert!(matches!(failure, LocatedFailure::Error { .. }));
+}
+
+#[test]
+fn failures_are_located() {
+    let input = Input::new("n=é", true);
+    let failure = number(input).finish_with_position().unwrap_err();
+
+    assert_eq!(failure.position(), Some(Position::new(1, 3, 2)));
+    assert!(!failure.is_recoverable());
+    assert!(matches!(
+        failure,
+        LocatedFailure::Failure {
+            error: Error {
+                code: ErrorKind::Digit,
+                ..
+            },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn incomplete_inputs_have_no_position() {
+    let input = Input::new("n", true);
+    let failure = streaming::tag::<_, _, SpannedError>("n=")
+        .parse(input)
+        .finish_with_position()
+        .unwrap_err();
+
+    assert_eq!(
+        failure,
+        LocatedFailure::Incomplete(Needed::Size(NonZeroUsize::new(1).unwrap()))
+    );
+    assert_eq!(failure.position(), None);
+    assert!(failure.is_incomplete() && !failure.is_recoverable());
+    assert_eq!(failure.to_string(), "incomplete input, 1 more bytes needed");
+}
+
+#[test]
+fn failures_are_displayed_with_their_position() {
+    let input = Input::new("n=x", true);
+    let failure = preceded(tag("n="), cut(digit1::<_, SpannedError>))
+        .parse(input)
+        .finish_with_position()
+        .unwrap_err();
+
+    assert_eq!(
+        failure.to_string(),
+        "1:3: expected Digit at line 1, column 3"
+    );
+}
+
+/// Parse a source read at runtime, which is dropped before returning
+fn parse_owned(source: String) -> Result<String, Box<LocatedFailure<SpannedError<String>>>> {
+    let result = number(Input::new(&source, true))
+        .finish_with_position()
+        .map(|number| number.into_data().to_owned())
+        .map_err(|failure| Box::new(failure.into_owned(3)));
+    drop(source);
+
+    result
+}
+
+#[test]
+fn failures_can_outlive_the_input() {
+    fn assert_static_send<T: Send + 'static>(_: &T) {}
+
+    let failure = parse_owned("n=éèàù".to_owned()).unwrap_err();
+    assert_static_send(&failure);
+
+    assert_eq!(parse_owned("n=12".to_owned()).unwrap(), "12");
+    assert_eq!(
+        *failure,
+        LocatedFailure::Failure {
+            position: Position::new(1, 3, 2),
+            error: SpannedError::new(Position::new(1, 3, 2), ErrorKind::Digit, "éèà".to_owned())
+        }
+    );
+}
@@ -0,0 +1,76 @@
+use nom::{
+    number::complete::{double, float, recognize_float},
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+fn parse_double(input: &str) -> (Span<'_>, f64) {
+    let res: IResult<Span<'_>, f64> = double(Span::new(input, true));
+    res.unwrap()
+}
+
+#[test]
+fn double_with_an_exponent() {
+    let (rest, value) = parse_double("1.5e-3");
+
+    assert!((value - 1.5e-3).abs() < f64::EPSILON);
+    assert_eq!(rest.col(), 7);
+}
+
+#[test]
+fn double_infinity() {
+    let (rest, value) = parse_double("inf");
+
+    assert!(value.is_infinite());
+    assert_eq!(rest.col(), 4);
+}
+
+#[test]
+fn double_followed_by_garbage() {
+    let (rest, value) = parse_double("-12.25é;");
+
+    assert!((value + 12.25).abs() < f64::EPSILON);
+    assert_eq!(*rest, "é;");
+    assert_eq!(rest.col(), 7);
+    assert_eq!(rest.byte_offset(), 6);
+}
+
+#[test]
+fn float_on_byte_spans() {
+    let res: IResult<BytesSpan<'_>, f32> = float(BytesSpan::new(b"3.5 rest", false));
+    let (rest, value) = res.unwrap();
+
+    assert!((value - 3.5).abs() < f32::EPSILON);
+    assert_eq!(rest.col(), 4);
+}
+
+#[test]
+fn recognize_float_keeps_the_position() {
+    let span = Span::new("x = 1.5e-3;", true);
+    let (span, _) = nom::bytes::complete::tag::<_, _, ()>("x = ")
+        .parse(span)
+        .unwrap();
+
+    let res: IResult<Span<'_>, Span<'_>> = recognize_float(span);
+    let (rest, number) = res.unwrap();
+
+    assert_eq!(*number, "1.5e-3");
+    assert_eq!(number.col(), 5);
+    assert_eq!(rest.col(), 11);
+}
+
+#[test]
+fn recognize_float_without_a_float() {
+    let res: IResult<Span<'_>, Span<'_>> = recognize_float(Span::new("abc", true));
+
+    assert!(res.is_err());
+}
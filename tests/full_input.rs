@@ -0,0 +1,75 @@
+use common::Slice;
+use nom::{
+    character::complete::{alpha1, anychar, multispace0},
+    multi::many0,
+    sequence::terminated,
+    IResult, Parser,
+};
+use nom_span::{FullSpanned, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = FullSpanned<&'a str>;
+
+/// A word and what was consumed before it
+fn word(input: Span<'_>) -> IResult<Span<'_>, (usize, &str)> {
+    let (rest, word) = terminated(alpha1, multispace0).parse(input)?;
+
+    assert_eq!(word.consumed().len(), word.byte_offset());
+    assert_eq!(rest.consumed().len(), rest.byte_offset());
+
+    Ok((rest, (word.byte_offset(), word.consumed())))
+}
+
+#[test]
+fn consumed_is_what_comes_before_the_position() {
+    let input = "é first\n🙌 second\nthird";
+    let span = Span::with_full_input(input, true);
+
+    let mut rest = span;
+    while let Ok((next, _)) = anychar::<_, ()>(rest) {
+        assert_eq!(next.consumed().len(), next.byte_offset());
+        assert_eq!(next.full_input(), &input);
+        rest = next;
+    }
+    assert_eq!(rest.consumed(), input);
+
+    let input = "é first\nsecond\nthird";
+    let (_, words) = many0(word)
+        .parse(Span::with_full_input(input, true).slice(3..))
+        .unwrap();
+    assert_eq!(
+        words,
+        [(3, "é "), (9, "é first\n"), (16, "é first\nsecond\n")]
+    );
+}
+
+#[test]
+fn lines_are_taken_from_the_full_input() {
+    let input = "a\n\nb\r\nlet c = x;\nd";
+    let x = Span::with_full_input(input, true).slice(14..);
+
+    assert_eq!(*x.slice(..1).data(), "x");
+    assert_eq!(x.line_text(), "let c = x;");
+    assert_eq!(x.lines_before(2), ["", "b"]);
+    assert_eq!(x.lines_before(10), ["a", "", "b"]);
+    assert!(x.lines_before(0).is_empty());
+    assert!(Span::with_full_input(input, true)
+        .lines_before(3)
+        .is_empty());
+}
+
+#[test]
+fn bytes_have_their_consumed_bytes() {
+    let input = &b"ab\xffcd"[..];
+    let span = Spanned::with_full_input(input, false).slice(3..);
+
+    assert_eq!(span.consumed(), b"ab\xff");
+    assert_eq!(span.full_input(), &input);
+}
@@ -0,0 +1,80 @@
+use common::tuple;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, space1},
+    combinator::recognize,
+    error::{Error, ErrorKind},
+    Err, IResult, Parser,
+};
+use nom_span::{Furthest, Position, Spanned, SpannedError};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// `let <name> = <number>`, `fn <name>(<name>) = <number>` or `type <name> = <name>`, the second
+/// branch failing the deepest for `fn f(x) = y`
+fn statement<'a, E>(input: Input<'a>) -> IResult<Input<'a>, Input<'a>, E>
+where
+    E: nom::error::ParseError<Input<'a>>,
+{
+    alt((
+        recognize(tuple((tag("let "), alpha1, tag(" = "), digit1))),
+        recognize(tuple((
+            tag("fn "),
+            alpha1,
+            tag("("),
+            alpha1,
+            tag(") = "),
+            digit1,
+        ))),
+        recognize(tuple((tag("type"), space1, alpha1, tag(" = "), alpha1))),
+    ))
+    .parse(input)
+}
+
+#[test]
+fn the_deepest_error_is_kept() {
+    let input = Input::new("fn f(x) = y", true);
+
+    let Err(Err::Error(Furthest(error))) = statement::<Furthest<SpannedError>>(input) else {
+        panic!("the statement should be invalid");
+    };
+    assert_eq!(error.position, Position::new(1, 11, 10));
+    assert_eq!(error.kind, ErrorKind::Digit);
+
+    let Err(Err::Error(Furthest(error))) = statement::<Furthest<Error<Input<'_>>>>(input) else {
+        panic!("the statement should be invalid");
+    };
+    assert_eq!(error.input.pos(), Position::new(1, 11, 10));
+}
+
+#[test]
+fn the_last_error_is_kept_without_furthest() {
+    let input = Input::new("fn f(x) = y", true);
+
+    let Err(Err::Error(error)) = statement::<SpannedError>(input) else {
+        panic!("the statement should be invalid");
+    };
+    assert_eq!(error.position, Position::new(1, 1, 0));
+    assert_eq!(error.kind, ErrorKind::Tag);
+}
+
+#[test]
+fn the_last_error_is_kept_at_the_same_place() {
+    let input = Input::new("const x", true);
+
+    let Err(Err::Error(Furthest(error))) = statement::<Furthest<SpannedError>>(input) else {
+        panic!("the statement should be invalid");
+    };
+    assert_eq!(error.position, Position::new(1, 1, 0));
+    assert_eq!(error.kind, ErrorKind::Tag);
+    assert_eq!(error.to_string(), "expected Tag at line 1, column 1");
+}
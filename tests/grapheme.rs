@@ -0,0 +1,84 @@
+#![cfg(feature = "unicode-segmentation")]
+
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::{ColumnUnit, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn graphemes(input: &str) -> Span<'_> {
+    Spanned::builder(input)
+        .column_unit(ColumnUnit::Graphemes)
+        .build()
+}
+
+fn take_chars_one_by_one(span: Span<'_>) -> Span<'_> {
+    let mut rest = span;
+    while !rest.is_empty() {
+        rest = take::<_, _, ()>(1usize).parse(rest).unwrap().0;
+    }
+    rest
+}
+
+#[test]
+fn combining_acute_is_part_of_the_previous_column() {
+    let span = graphemes("e\u{301}x");
+
+    assert_eq!(span.slice(3..).col(), 2);
+    assert_eq!(span.slice(4..).col(), 3);
+    assert_eq!(span.slice(4..).char_offset(), 3);
+}
+
+#[test]
+fn combining_acute_consumed_one_char_at_a_time() {
+    let span = graphemes("e\u{301}x");
+
+    assert_eq!(span.slice(1..).col(), 1);
+    assert_eq!(span.slice(1..).slice(2..).col(), 2);
+    assert_eq!(take_chars_one_by_one(span).col(), 3);
+}
+
+#[test]
+fn family_emoji_is_a_single_column() {
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    let input = format!("a{}b", family);
+    let span = graphemes(&input);
+
+    assert_eq!(span.slice(1 + family.len()..).col(), 3);
+    assert_eq!(span.slice(input.len()..).col(), 4);
+    assert_eq!(Span::new(&input, true).slice(input.len()..).col(), 8);
+}
+
+#[test]
+fn flags_are_a_single_column() {
+    let span = graphemes("🇫🇷🇩🇪!");
+
+    assert_eq!(span.slice(8..).col(), 2);
+    assert_eq!(span.slice(16..).col(), 3);
+    assert_eq!(span.slice(17..).col(), 4);
+}
+
+#[test]
+fn a_flag_consumed_one_char_at_a_time() {
+    let span = graphemes("🇫🇷!");
+
+    assert_eq!(span.slice(4..).col(), 1);
+    assert_eq!(take_chars_one_by_one(span).pos(), Position::new(1, 3, 9));
+}
+
+#[test]
+fn graphemes_across_lines() {
+    let span = graphemes("e\u{301}\na\u{308}b");
+
+    assert_eq!(span.slice(4..).pos(), Position::new(2, 1, 4));
+    assert_eq!(span.slice(7..).pos(), Position::new(2, 2, 7));
+    assert_eq!(span.slice(8..).pos(), Position::new(2, 3, 8));
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "greedyerror")]
+
+use common::tuple;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, multispace0, multispace1},
+    error::{context, ErrorKind},
+    sequence::{delimited, preceded},
+    Err, IResult,
+};
+use nom_greedyerror::{convert_error, error_position, GreedyError, GreedyErrorKind};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_greedyerror;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+type Error<'a> = GreedyError<Span<'a>, ErrorKind>;
+
+/// `let <name> = <number>;`
+fn binding(input: Span<'_>) -> IResult<Span<'_>, Span<'_>, Error<'_>> {
+    context(
+        "binding",
+        delimited(
+            tuple((tag("let"), multispace1, alpha1, multispace0, tag("="))),
+            preceded(multispace0, digit1),
+            tag(";"),
+        ),
+    )(input)
+}
+
+/// `<number>;`
+fn statement(input: Span<'_>) -> IResult<Span<'_>, Span<'_>, Error<'_>> {
+    alt((
+        binding,
+        context("number", delimited(multispace0, digit1, tag(";"))),
+    ))(input)
+}
+
+fn deepest_error(input: &str) -> Error<'_> {
+    match statement(Span::new(input, true)) {
+        Err(Err::Error(error)) => error,
+        other => panic!("expected an error, got {:?}", other),
+    }
+}
+
+#[test]
+fn the_error_of_the_deeper_branch_wins() {
+    let error = deepest_error("let ab\n  = x;");
+
+    let (span, kind) = &error.errors[0];
+    assert_eq!(kind, &GreedyErrorKind::Nom(ErrorKind::Digit));
+    assert_eq!((span.line(), span.col()), (2, 5));
+    assert_eq!(error_position(&error), Some(span.byte_offset()));
+}
+
+#[test]
+fn the_first_branch_wins_when_both_fail_at_the_start() {
+    let error = deepest_error("x");
+
+    let (span, _) = &error.errors[0];
+    assert_eq!((span.line(), span.col()), (1, 1));
+}
+
+#[test]
+fn errors_are_converted_to_a_trace() {
+    let input = "let a =\n  b;";
+    let error = deepest_error(input);
+
+    let trace = convert_error(Span::new(input, true), error);
+
+    assert!(trace.starts_with("0: at line 1, in Digit:"), "{}", trace);
+    assert!(trace.contains("in binding"), "{}", trace);
+}
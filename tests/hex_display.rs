@@ -0,0 +1,43 @@
+use common::Slice;
+use nom::HexDisplay;
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+const INPUT: &[u8] = b"\x00\x01binary\xffdata that spans a few chunks";
+
+#[test]
+fn dump_is_the_one_of_the_slice() {
+    let span = Spanned::new(INPUT, false);
+
+    assert_eq!(span.to_hex(8), INPUT.to_hex(8));
+}
+
+#[test]
+fn addresses_start_at_the_byte_offset() {
+    let span = Spanned::new(INPUT, false).slice(10..);
+
+    assert_eq!(span.to_hex(8), INPUT[10..].to_hex_from(8, 10));
+    assert!(span.to_hex(8).starts_with("0000000a\t"));
+}
+
+#[test]
+fn addresses_can_be_chosen() {
+    let span = Spanned::new(INPUT, false).slice(10..);
+
+    assert_eq!(span.to_hex_from(4, 0), INPUT[10..].to_hex(4));
+}
+
+#[test]
+fn str_spans_can_be_dumped() {
+    let input = "héllo\nwörld";
+    let span = Spanned::new(input, true).slice(7..);
+
+    assert_eq!(span.to_hex(4), input[7..].to_hex_from(4, 7));
+}
@@ -0,0 +1,64 @@
+use common::{InputIter, IterElements, IterIndices};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+fn indices(span: Span<'_>) -> IterIndices<Span<'_>> {
+    span.iter_indices()
+}
+
+fn chars<'a>(span: Span<'a>) -> impl Iterator<Item = char> + 'a {
+    span.iter_elements()
+}
+
+fn bytes(span: BytesSpan<'_>) -> IterElements<BytesSpan<'_>> {
+    span.iter_elements()
+}
+
+#[test]
+fn iterators_outlive_the_span() {
+    let input = String::from("hé🙌");
+
+    let mut iter = indices(Span::new(&input, true));
+    let stored: Vec<char> = chars(Span::new(&input, true)).collect();
+
+    assert_eq!(iter.nth(2), Some((3, '🙌')));
+    assert_eq!(stored, vec!['h', 'é', '🙌']);
+}
+
+#[test]
+fn iterators_outlive_the_byte_span() {
+    let input = vec![1u8, 2, 3];
+
+    let iter = bytes(BytesSpan::new(&input, false));
+
+    assert_eq!(iter.sum::<u8>(), 6);
+}
+
+#[test]
+fn iterators_can_be_stored_next_to_the_input() {
+    struct Lexer<'a> {
+        chars: IterIndices<Span<'a>>,
+    }
+
+    fn lexer(input: &str) -> Lexer<'_> {
+        let span = Span::new(input, true);
+
+        Lexer {
+            chars: span.iter_indices(),
+        }
+    }
+
+    let mut lexer = lexer("ab");
+
+    assert_eq!(lexer.chars.next(), Some((0, 'a')));
+}
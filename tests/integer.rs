@@ -0,0 +1,105 @@
+use nom::{
+    character::complete::{i128, i16, i32, i64, i8, u128, u16, u32, u64, u8},
+    error::{Error, ErrorKind},
+    Err, IResult, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+#[test]
+fn every_integer_parser_works_on_str_spans() {
+    let span = Span::new("42;", true);
+
+    assert_eq!(i8::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i16::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i32::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i64::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i128::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u8::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u16::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u32::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u64::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u128::<_, ()>(span).unwrap().1, 42);
+}
+
+#[test]
+fn every_integer_parser_works_on_byte_spans() {
+    let span = BytesSpan::new(b"42;", false);
+
+    assert_eq!(i8::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i16::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i32::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i64::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(i128::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u8::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u16::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u32::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u64::<_, ()>(span).unwrap().1, 42);
+    assert_eq!(u128::<_, ()>(span).unwrap().1, 42);
+}
+
+#[test]
+fn negative_numbers() {
+    let res: IResult<Span<'_>, i64> = i64(Span::new("-1234é", true));
+    let (rest, value) = res.unwrap();
+
+    assert_eq!(value, -1234);
+    assert_eq!(rest.pos(), Position::new(1, 6, 5));
+
+    let res: IResult<BytesSpan<'_>, i32> = i32(BytesSpan::new(b"-7\n", false));
+    assert_eq!(res.unwrap().1, -7);
+}
+
+#[test]
+fn unsigned_parsers_reject_the_sign() {
+    let res: IResult<Span<'_>, u32> = u32(Span::new("-1", true));
+
+    assert!(matches!(
+        res,
+        Err(Err::Error(Error {
+            code: ErrorKind::Digit,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn overflow_is_an_error_at_the_number() {
+    let span = Span::new("x 256", true);
+    let (span, _) = nom::bytes::complete::tag::<_, _, ()>("x ")
+        .parse(span)
+        .unwrap();
+
+    let res: IResult<Span<'_>, u8> = u8(span);
+
+    let Err(Err::Error(Error { input, code })) = res else {
+        panic!("expected an error");
+    };
+    assert_eq!(code, ErrorKind::Digit);
+    assert_eq!(input.col(), 3);
+
+    let res: IResult<Span<'_>, i8> = i8(Span::new("-129", true));
+    assert!(res.is_err());
+}
+
+#[test]
+fn remaining_input_after_the_number() {
+    let input = "12\n-345 rest";
+    let span = Span::new(input, true);
+
+    let (rest, _) = u16::<_, ()>(span).unwrap();
+    assert_eq!(rest.pos(), Position::new(1, 3, 2));
+
+    let (rest, _) = nom::character::complete::line_ending::<_, ()>(rest).unwrap();
+    let (rest, value) = i16::<_, ()>(rest).unwrap();
+    assert_eq!(value, -345);
+    assert_eq!(rest.pos(), Position::new(2, 5, 7));
+}
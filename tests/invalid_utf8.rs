@@ -0,0 +1,92 @@
+use common::{InputTake, Slice};
+use nom::{bytes::complete::take, Parser};
+use nom_span::{ColumnUnit, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+/// The columns `String::from_utf8_lossy` gives, every invalid sequence being one char
+fn lossy_col(bytes: &[u8]) -> usize {
+    String::from_utf8_lossy(bytes).chars().count() + 1
+}
+
+const CORRUPT: &[&[u8]] = &[
+    // Lone continuation bytes
+    b"a\x80\x80b",
+    // Invalid bytes
+    b"a\xff\xfeb",
+    // A truncated `€`
+    b"a\xe2\x82b",
+    // An overlong encoding and a surrogate
+    b"\xc0\xaf\xed\xa0\x80",
+    // A truncated 4 bytes sequence at the end
+    b"ab\xf0\x9f\x99",
+];
+
+#[test]
+fn invalid_sequences_are_one_column() {
+    for &bytes in CORRUPT {
+        let span = BytesSpan::new(bytes, true);
+        let end = span.slice(bytes.len()..);
+
+        assert_eq!(end.col(), lossy_col(bytes), "on {:?}", bytes);
+        assert_eq!(end.char_offset(), lossy_col(bytes) - 1, "on {:?}", bytes);
+    }
+}
+
+#[test]
+fn examples_of_columns() {
+    let end = |bytes: &'static [u8]| BytesSpan::new(bytes, true).slice(bytes.len()..).col();
+
+    assert_eq!(end(b"a\x80\x80b"), 5);
+    assert_eq!(end(b"a\xe2\x82b"), 4);
+    assert_eq!(end(b"a\xe2\x82\xacb"), 4);
+}
+
+#[test]
+fn chars_cut_by_a_slice_are_counted_once() {
+    let bytes = "aé€🙌b".as_bytes();
+    let one_shot = BytesSpan::new(bytes, true).slice(bytes.len()..);
+
+    let mut span = BytesSpan::new(bytes, true);
+    while !span.is_empty() {
+        span = span.take_split(1).0;
+    }
+
+    assert_eq!(span.pos(), one_shot.pos());
+    assert_eq!(span.pos(), Position::new(1, 6, bytes.len()));
+    assert_eq!(span.char_offset(), 5);
+}
+
+#[test]
+fn corrupt_input_consumed_in_pieces() {
+    let bytes: &[u8] = b"a\xff\xe2\x82b\n\x80c";
+
+    let (rest, _) = take::<_, _, ()>(3usize)
+        .parse(BytesSpan::new(bytes, true))
+        .unwrap();
+    assert_eq!(rest.col(), 4);
+
+    let rest = rest.slice(3..);
+    assert_eq!(rest.pos(), Position::new(2, 1, 6));
+
+    // The continuation byte doesn't end a cut char, so it's a replacement char of its own
+    assert_eq!(rest.slice(2..).col(), 3);
+}
+
+#[test]
+fn utf16_columns_count_invalid_sequences_once() {
+    let bytes: &[u8] = b"\xf0\x9f\x99\x8c\xff\xf0\x9f";
+    let span = Spanned::builder(bytes)
+        .column_unit(ColumnUnit::Utf16)
+        .build();
+
+    assert_eq!(span.slice(bytes.len()..).col(), 5);
+}
@@ -0,0 +1,83 @@
+use nom::{
+    bytes::complete::{tag, take},
+    multi::{length_count, length_data, length_value, many0},
+    number::complete::{be_u16, be_u8},
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a [u8]>;
+
+const INPUT: &[u8] = b"hdr\x00\x05ab\ncd\x02xy";
+
+fn header(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    tag("hdr").parse(input)
+}
+
+#[test]
+fn length_data_window_keeps_its_offset() {
+    let (input, _) = header(Span::new(INPUT, false)).unwrap();
+
+    let (rest, window) = length_data(be_u16::<_, ()>).parse(input).unwrap();
+
+    assert_eq!(*window, b"ab\ncd");
+    assert_eq!(window.byte_offset(), 5);
+    assert_eq!(window.col(), 6);
+    assert_eq!(rest.byte_offset(), 10);
+    assert_eq!(rest.line(), 2);
+}
+
+#[test]
+fn parsing_inside_the_window_keeps_the_global_offset() {
+    let (input, _) = header(Span::new(INPUT, false)).unwrap();
+    let (_, window) = length_data(be_u16::<_, ()>).parse(input).unwrap();
+
+    let (inner_rest, first) = take::<_, _, ()>(3usize).parse(window).unwrap();
+
+    assert_eq!(first.byte_offset(), 5);
+    assert_eq!(inner_rest.byte_offset(), 8);
+    assert_eq!(inner_rest.line(), 2);
+    assert_eq!(inner_rest.col(), 1);
+}
+
+#[test]
+fn length_value_parses_the_window() {
+    fn lines(input: Span<'_>) -> IResult<Span<'_>, Vec<Span<'_>>> {
+        many0(nom::sequence::terminated(
+            nom::character::complete::alpha1,
+            nom::combinator::opt(tag("\n")),
+        ))
+        .parse(input)
+    }
+
+    let (input, _) = header(Span::new(INPUT, false)).unwrap();
+
+    let (rest, words) = length_value(be_u16, lines).parse(input).unwrap();
+
+    assert_eq!(words.len(), 2);
+    assert_eq!(words[0].byte_offset(), 5);
+    assert_eq!(words[1].byte_offset(), 8);
+    assert_eq!(words[1].line(), 2);
+    assert_eq!(rest.byte_offset(), 10);
+}
+
+#[test]
+fn length_count_repeats_the_parser() {
+    let span = Span::new(INPUT, false);
+    let (input, _) = take::<_, _, ()>(10usize).parse(span).unwrap();
+
+    let res: IResult<_, _> = length_count(be_u8, take(1usize)).parse(input);
+    let (rest, items) = res.unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(*items[1], b"y");
+    assert_eq!(items[1].byte_offset(), 12);
+    assert_eq!(items[1].col(), 5);
+    assert_eq!(rest.byte_offset(), INPUT.len());
+}
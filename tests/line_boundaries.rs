@@ -0,0 +1,81 @@
+use common::Slice;
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn boundaries_of_the_first_line() {
+    let span = Span::new("  ab\ncd", true);
+
+    assert!(span.is_at_line_start());
+    assert_eq!((span.line_start_offset(), span.line_end_offset()), (0, 4));
+
+    let indented = span.slice(2..);
+    assert!(!indented.is_at_line_start());
+    assert_eq!(
+        (indented.line_start_offset(), indented.line_end_offset()),
+        (0, 4)
+    );
+}
+
+#[test]
+fn boundaries_after_multibyte_chars() {
+    let input = "é\n🙌 é x\r\nz";
+    let x = Span::new(input, true).slice(11..);
+
+    assert_eq!(*x.slice(..1), "x");
+    assert_eq!(x.col(), 5);
+    assert_eq!(x.line_start_offset(), 3);
+    assert_eq!(x.line_end_offset(), 12);
+    assert_eq!(&input[x.line_start_offset()..x.line_end_offset()], "🙌 é x");
+    assert!(Span::new(input, true).slice(3..).is_at_line_start());
+}
+
+#[test]
+fn boundaries_of_the_last_unterminated_line() {
+    let input = "ab\ncdé";
+    let span = Span::new(input, true).slice(4..);
+
+    assert_eq!(span.line_start_offset(), 3);
+    assert_eq!(span.line_end_offset(), input.len());
+
+    let end = span.slice(span.len()..);
+    assert_eq!(
+        (end.line_start_offset(), end.line_end_offset()),
+        (3, input.len())
+    );
+    assert!(!end.is_at_line_start());
+}
+
+#[test]
+fn a_cut_fragment_ends_its_line() {
+    let span = Span::new("abcd\n", true).slice(1..3);
+
+    assert_eq!(span.line_end_offset(), 3);
+}
+
+#[test]
+fn a_line_starting_before_the_input_starts_at_it() {
+    let span = Span::new_at("ab\ncd", 1, 10, 0, true);
+
+    assert!(!span.is_at_line_start());
+    assert_eq!(span.line_start_offset(), 0);
+    assert_eq!(span.current_line_text("ab\ncd"), "ab");
+    assert_eq!(span.slice(1..).line_start_offset(), 0);
+    assert_eq!(span.slice(3..).line_start_offset(), 3);
+
+    let built = Spanned::builder("ab")
+        .start_line(2)
+        .start_col(4)
+        .start_offset(1)
+        .build();
+    assert_eq!(built.line_start_offset(), 0);
+}
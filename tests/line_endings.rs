@@ -0,0 +1,88 @@
+use common::Slice;
+use nom::{
+    bytes::complete::{take, take_until},
+    Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn with_crlf(input: &str) -> Span<'_> {
+    Spanned::builder(input).crlf(true).build()
+}
+
+#[test]
+fn cr_is_a_column_by_default() {
+    let span = Span::new("ab\r\ncd\rx", true);
+
+    assert_eq!(span.slice(3..).pos(), Position::new(1, 4, 3));
+    assert_eq!(span.slice(4..).pos(), Position::new(2, 1, 4));
+    assert_eq!(span.slice(7..).pos(), Position::new(2, 4, 7));
+}
+
+#[test]
+fn crlf_is_a_single_line_ending() {
+    let span = with_crlf("ab\r\ncd\r\n\r\nx");
+
+    assert_eq!(span.slice(4..).pos(), Position::new(2, 1, 4));
+    assert_eq!(span.slice(8..).pos(), Position::new(3, 1, 8));
+    assert_eq!(span.slice(10..).pos(), Position::new(4, 1, 10));
+}
+
+#[test]
+fn slicing_between_cr_and_lf() {
+    let span = with_crlf("ab\r\ncd");
+
+    let between = span.slice(3..);
+
+    assert_eq!(between.pos(), Position::new(1, 3, 3));
+    assert_eq!(between.slice(1..).pos(), Position::new(2, 1, 4));
+    assert_eq!(between.slice(1..).pos(), span.slice(4..).pos());
+    assert_eq!(between.slice(2..).pos(), span.slice(5..).pos());
+}
+
+#[test]
+fn consuming_crlf_char_by_char_matches_consuming_it_at_once() {
+    let span = with_crlf("a\r\n\r\nb\rc");
+
+    let mut rest = span;
+    for i in 1..=span.len() {
+        rest = take::<_, _, ()>(1usize).parse(rest).unwrap().0;
+
+        assert_eq!(rest.pos(), span.slice(i..).pos());
+        assert_eq!(rest.char_offset(), span.slice(i..).char_offset());
+    }
+
+    assert_eq!(rest.pos(), Position::new(4, 2, 8));
+}
+
+#[test]
+fn lone_cr_is_a_line_ending() {
+    let span = with_crlf("ab\rcd\rx");
+
+    assert_eq!(span.slice(3..).pos(), Position::new(2, 1, 3));
+    assert_eq!(span.slice(6..).pos(), Position::new(3, 1, 6));
+    assert_eq!(span.end_position(), Position::new(3, 2, 7));
+}
+
+#[test]
+fn a_cr_ending_the_data_is_the_start_of_a_crlf() {
+    let span = with_crlf("ab\r\ncd\r");
+    let (rest, line) = take_until::<_, _, ()>("\n").parse(span).unwrap();
+
+    assert_eq!(line.end_position(), Position::new(1, 3, 3));
+    assert_eq!(line.end_position(), rest.pos());
+    assert_eq!(line.position_at(3), rest.pos());
+
+    // What comes after the input isn't known either
+    assert_eq!(span.end_position(), Position::new(2, 3, 7));
+    assert_eq!(span.slice(7..).pos(), span.end_position());
+}
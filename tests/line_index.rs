@@ -0,0 +1,89 @@
+use common::Slice;
+use nom_span::{LineIndex, Position, SourceFile, SourceId, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+const INPUT: &str = "let é = 1;\r\n\nlet 🦀 = \"ça\";\n\tend";
+
+fn cross_check(handle_utf8: bool) {
+    let index = LineIndex::new(INPUT, handle_utf8);
+    let span = Spanned::new(INPUT, handle_utf8);
+
+    for offset in (0..=INPUT.len()).filter(|&offset| INPUT.is_char_boundary(offset)) {
+        let position = span.slice(offset..).pos();
+
+        assert_eq!(index.position(offset), position, "at offset {}", offset);
+        assert_eq!(
+            index.offset(position.line, position.col),
+            Some(offset),
+            "at {}",
+            position
+        );
+    }
+}
+
+#[test]
+fn positions_are_the_ones_of_the_sliced_spans() {
+    cross_check(true);
+}
+
+#[test]
+fn positions_are_the_ones_of_the_sliced_spans_in_bytes() {
+    cross_check(false);
+}
+
+#[test]
+fn positions_past_the_end_are_clamped() {
+    let index = LineIndex::new("ab\ncd\n", true);
+
+    assert_eq!(index.line_count(), 3);
+    assert_eq!(index.position(6), Position::new(3, 1, 6));
+    assert_eq!(index.position(100), Position::new(3, 1, 6));
+}
+
+#[test]
+fn lines_are_without_their_line_ending() {
+    let index = LineIndex::new(INPUT, true);
+
+    assert_eq!(index.line(0), None);
+    assert_eq!(index.line(1), Some("let é = 1;"));
+    assert_eq!(index.line(2), Some(""));
+    assert_eq!(index.line(3), Some("let 🦀 = \"ça\";"));
+    assert_eq!(index.line(4), Some("\tend"));
+    assert_eq!(index.line(5), None);
+    assert_eq!(LineIndex::new("a\r", true).line(1), Some("a\r"));
+}
+
+#[test]
+fn offsets_outside_of_the_input_are_none() {
+    let index = LineIndex::new("ab\nçd", true);
+
+    assert_eq!(index.offset(1, 3), Some(2));
+    assert_eq!(index.offset(1, 4), None);
+    assert_eq!(index.offset(0, 1), None);
+    assert_eq!(index.offset(2, 0), None);
+    assert_eq!(index.offset(2, 3), Some(6));
+    assert_eq!(index.offset(3, 1), None);
+
+    let bytes = LineIndex::new("ab\nçd", false);
+    assert_eq!(bytes.offset(2, 2), None);
+    assert_eq!(bytes.offset(2, 3), Some(5));
+}
+
+#[test]
+fn positions_are_in_the_source_of_the_file() {
+    let file = SourceFile::new("main.txt", "let a;\nlet b;".into()).with_source_id(SourceId(4));
+    let index = file.line_index();
+
+    assert_eq!(
+        index.position(11),
+        Position::new(2, 5, 11).with_source(SourceId(4))
+    );
+    assert_eq!(index.position(11), file.span().slice(11..).pos());
+}
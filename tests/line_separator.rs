@@ -0,0 +1,61 @@
+use common::Slice;
+use nom::{bytes::complete::take_until, Parser};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn separated_by(input: &str, separator: u8) -> Span<'_> {
+    Spanned::builder(input).line_separator(separator).build()
+}
+
+#[test]
+fn nul_separates_the_lines() {
+    let span = separated_by("first\0second\0third", b'\0');
+
+    assert_eq!(span.slice(6..).pos(), Position::new(2, 1, 6));
+    assert_eq!(span.slice(13..).pos(), Position::new(3, 1, 13));
+    assert_eq!(span.end_position(), Position::new(3, 6, 18));
+}
+
+#[test]
+fn col_resets_after_every_separator() {
+    let span = separated_by("ab\0cde\0f", b'\0');
+
+    let (rest, _) = take_until::<_, _, ()>("\0").parse(span).unwrap();
+    assert_eq!(rest.pos(), Position::new(1, 3, 2));
+
+    let rest = rest.slice(1..);
+    assert_eq!(rest.pos(), Position::new(2, 1, 3));
+
+    let (rest, _) = take_until::<_, _, ()>("\0").parse(rest).unwrap();
+    assert_eq!(rest.pos(), Position::new(2, 4, 6));
+    assert_eq!(rest.slice(1..).pos(), Position::new(3, 1, 7));
+}
+
+#[test]
+fn newline_is_a_column_with_another_separator() {
+    let span = separated_by("{\"a\":\n1}\n\x1e{}", 0x1e);
+
+    assert_eq!(span.slice(9..).pos(), Position::new(1, 10, 9));
+    assert_eq!(span.slice(10..).pos(), Position::new(2, 1, 10));
+}
+
+#[test]
+fn crlf_ends_on_the_separator() {
+    let span = Spanned::builder("a\r\0b\rc")
+        .line_separator(b'\0')
+        .crlf(true)
+        .build();
+
+    assert_eq!(span.slice(2..).pos(), Position::new(1, 2, 2));
+    assert_eq!(span.slice(3..).pos(), Position::new(2, 1, 3));
+    assert_eq!(span.slice(5..).pos(), Position::new(3, 1, 5));
+}
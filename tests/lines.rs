@@ -0,0 +1,79 @@
+use nom_span::{Position, Spanned};
+
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn lines(input: &str) -> Vec<(&str, Position)> {
+    Span::new(input, true)
+        .lines()
+        .map(|line| (line.into_data(), line.pos()))
+        .collect()
+}
+
+#[test]
+fn every_line_starts_at_column_1() {
+    assert_eq!(
+        lines("é a\n\nbc\r\nd"),
+        [
+            ("é a", Position::new(1, 1, 0)),
+            ("", Position::new(2, 1, 5)),
+            ("bc", Position::new(3, 1, 6)),
+            ("d", Position::new(4, 1, 10)),
+        ]
+    );
+}
+
+#[test]
+fn a_final_newline_doesnt_make_a_line() {
+    assert_eq!(
+        lines("a\nb\n"),
+        [("a", Position::new(1, 1, 0)), ("b", Position::new(2, 1, 2))]
+    );
+    assert_eq!(lines(""), []);
+}
+
+#[test]
+fn only_newlines_make_empty_lines() {
+    assert_eq!(
+        lines("\n\r\n\n"),
+        [
+            ("", Position::new(1, 1, 0)),
+            ("", Position::new(2, 1, 1)),
+            ("", Position::new(3, 1, 3)),
+        ]
+    );
+}
+
+#[test]
+fn inclusive_lines_keep_the_terminator() {
+    let span = Span::new("a\r\nb\nc", true);
+
+    let lines: Vec<_> = span.lines_inclusive().map(Spanned::into_data).collect();
+    assert_eq!(lines, ["a\r\n", "b\n", "c"]);
+
+    let last = span.lines_inclusive().last().unwrap();
+    assert_eq!(last.end_position(), span.end_position());
+}
+
+#[test]
+fn lines_are_the_ones_of_str() {
+    for input in ["a\nb", "a\n\n", "\r\n", "a\rb\n", "\n\nx"] {
+        let lines: Vec<_> = Span::new(input, true)
+            .lines()
+            .map(Spanned::into_data)
+            .collect();
+
+        assert_eq!(lines, input.lines().collect::<Vec<_>>(), "on {:?}", input);
+    }
+}
+
+#[test]
+fn size_hint_bounds_the_count() {
+    let mut lines = Span::new("ab\n", true).lines();
+
+    assert_eq!(lines.size_hint(), (1, Some(3)));
+    lines.next();
+    assert_eq!(lines.size_hint(), (0, Some(0)));
+    assert_eq!(lines.next(), None);
+}
@@ -0,0 +1,74 @@
+use anyhow::Context;
+use common::Slice;
+use nom::{character::complete::digit1, IResult};
+use nom_span::{LocatedError, Position, Spanned, WithPosition};
+use std::{error::Error, num::ParseIntError};
+
+extern crate anyhow;
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+fn digits(input: Input<'_>) -> IResult<Input<'_>, Input<'_>> {
+    digit1(input)
+}
+
+/// Read the port of `host:port`
+fn port(source: &str) -> anyhow::Result<u16> {
+    let input = Input::new(source, true);
+    let colon = source.find(':').context("no port")?;
+    let (_, digits) = digits(input.slice(colon + 1..)).map_err(|e| anyhow::anyhow!("{e:?}"))?;
+
+    let port = digits
+        .data()
+        .parse::<u16>()
+        .at(&digits)
+        .context("invalid port")?;
+
+    Ok(port)
+}
+
+#[test]
+fn errors_are_displayed_with_their_position() {
+    let error = "x".parse::<u8>().with_position(Position::new(3, 14, 40));
+    let error = error.unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "at line 3, column 14: invalid digit found in string"
+    );
+    assert!(error.source().unwrap().is::<ParseIntError>());
+    assert_eq!(error.into_inner(), "x".parse::<u8>().unwrap_err());
+}
+
+#[test]
+fn positions_are_shown_in_anyhow_reports() {
+    assert_eq!(port("localhost:8080").unwrap(), 8080);
+
+    assert!(port("localhost:é").is_err());
+
+    let error = port("é.localhost:99999").unwrap_err();
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+
+    assert_eq!(
+        chain,
+        [
+            "invalid port",
+            "at line 1, column 13: number too large to fit in target type",
+            "number too large to fit in target type",
+        ]
+    );
+    assert_eq!(
+        format!("{error:#}"),
+        "invalid port: at line 1, column 13: number too large to fit in target type: number too large to fit in target type"
+    );
+
+    let located = error.downcast_ref::<LocatedError<ParseIntError>>().unwrap();
+    assert_eq!(located.position, Position::new(1, 13, 13));
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "lsp")]
+
+use common::Slice;
+use nom::character::complete::alpha1;
+use nom_span::{ColumnUnit, Position, Span, Spanned};
+
+extern crate lsp_types;
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+#[test]
+fn characters_are_counted_in_utf16_code_units() {
+    let source = "a = 1\nb = \"🙌é\" + cd";
+    let input = Spanned::new(source, true);
+    let (rest, name) = alpha1::<_, ()>(input.slice(21..)).unwrap();
+
+    // 🙌 is 4 bytes and 2 UTF-16 code units, é is 2 bytes and 1 code unit
+    assert_eq!((name.line(), name.col()), (2, 12));
+    assert_eq!(
+        name.to_lsp_position(source),
+        lsp_types::Position::new(1, 12)
+    );
+    assert_eq!(
+        name.to_lsp_range(&rest, source),
+        lsp_types::Range::new(
+            lsp_types::Position::new(1, 12),
+            lsp_types::Position::new(1, 14)
+        )
+    );
+    assert_eq!(
+        Span::of(&name).to_lsp_range(source),
+        name.to_lsp_range(&rest, source)
+    );
+    assert_eq!(
+        name.pos().to_lsp_position(source),
+        name.to_lsp_position(source)
+    );
+}
+
+#[test]
+fn characters_dont_depend_on_the_column_unit() {
+    let source = "🙌🙌 x";
+    let input = Spanned::builder(source)
+        .column_unit(ColumnUnit::Bytes)
+        .build();
+    let x = input.slice(9..);
+
+    assert_eq!(x.col(), 10);
+    assert_eq!(x.to_lsp_position(source), lsp_types::Position::new(0, 5));
+}
+
+#[test]
+fn lsp_positions_convert_back_to_positions() {
+    let source = "a = 1\r\nb = \"🙌é\" + cd\n";
+    let input = Spanned::new(source, true);
+    let name = input.slice(22..);
+
+    let position = Position::from_lsp_position(source, lsp_types::Position::new(1, 12));
+    assert_eq!(position, Some(name.pos()));
+
+    // In the middle of 🙌, at the start of it
+    let position = Position::from_lsp_position(source, lsp_types::Position::new(1, 6));
+    assert_eq!(position, Some(Position::new(2, 6, 12)));
+
+    // After the end of the line, at the end of it
+    let position = Position::from_lsp_position(source, lsp_types::Position::new(0, 40));
+    assert_eq!(position, Some(Position::new(1, 6, 5)));
+
+    let position = Position::from_lsp_position(source, lsp_types::Position::new(2, 0));
+    assert_eq!(position, Some(Position::new(3, 1, source.len())));
+    assert_eq!(
+        Position::from_lsp_position(source, lsp_types::Position::new(3, 0)),
+        None
+    );
+
+    let range = name.to_lsp_range(&input.slice(24..), source);
+    assert_eq!(
+        Span::from_lsp_range(source, range),
+        Some(Span::new(name.pos(), input.slice(24..).pos()))
+    );
+}
+
+#[test]
+fn positions_inside_a_char_are_at_the_start_of_the_char() {
+    let source = "a🙌b";
+    let span = Spanned::new(source.as_bytes(), false).slice(2..);
+
+    assert_eq!(span.to_lsp_position(source), lsp_types::Position::new(0, 1));
+    assert_eq!(
+        span.pos().to_lsp_position(source),
+        lsp_types::Position::new(0, 1)
+    );
+    assert_eq!(
+        Position::new(1, 9, 9).to_lsp_position(source),
+        lsp_types::Position::new(0, 4)
+    );
+}
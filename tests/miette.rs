@@ -0,0 +1,130 @@
+#![cfg(feature = "miette")]
+
+use miette::{Diagnostic, LabeledSpan, NarratableReportHandler, SourceCode, SourceSpan};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, line_ending, space0},
+    multi::many0,
+    sequence::{delimited, preceded, terminated},
+    IResult, Parser,
+};
+use nom_span::{Position, Span, Spanned};
+use std::{error::Error, fmt};
+
+extern crate miette;
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// An error labeling where a value was expected
+#[derive(Debug)]
+struct ExpectedValue<'a> {
+    source: Input<'a>,
+    at: SourceSpan,
+}
+
+impl fmt::Display for ExpectedValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid binding")
+    }
+}
+
+impl Error for ExpectedValue<'_> {}
+
+impl Diagnostic for ExpectedValue<'_> {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(std::iter::once(LabeledSpan::new_with_span(
+            Some("expected a number".into()),
+            self.at,
+        ))))
+    }
+}
+
+/// `<name> = <number>`, without newlines around it
+fn binding(input: Input<'_>) -> IResult<Input<'_>, Input<'_>> {
+    preceded(
+        delimited(space0, alpha1, delimited(space0, tag("="), space0)),
+        digit1,
+    )
+    .parse(input)
+}
+
+fn parse(source: &str) -> Result<Input<'_>, ExpectedValue<'_>> {
+    let input = Input::new(source, true);
+
+    preceded(many0(terminated(binding, line_ending)), binding)
+        .parse(input)
+        .map(|(_, value)| value)
+        .map_err(|error| match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => ExpectedValue {
+                source: input,
+                at: (&error.input.take_ceil_char_boundary(1)).into(),
+            },
+            nom::Err::Incomplete(_) => unreachable!(),
+        })
+}
+
+#[test]
+fn source_spans_have_the_offset_and_the_length() {
+    let value = parse("é\n  ab = 42").map(|_| ()).unwrap_err();
+    let input = Input::new("ab = 42;", true);
+    let (rest, number) = binding(input).unwrap();
+
+    assert_eq!(SourceSpan::from(&number), SourceSpan::from((5, 2)));
+    assert_eq!(SourceSpan::from(&rest), SourceSpan::from((7, 1)));
+    assert_eq!(
+        SourceSpan::from(Span::of(&number)),
+        SourceSpan::from((5, 2))
+    );
+    assert_eq!(
+        miette::SourceOffset::from(Position::new(1, 6, 5)),
+        miette::SourceOffset::from(5)
+    );
+    assert_eq!(value.at, SourceSpan::from((0, 2)));
+}
+
+#[test]
+fn read_span_gives_the_line_of_the_label() {
+    let error = parse("a = 1\nb = x\nc = 3").map(|_| ()).unwrap_err();
+    let contents = error.source.read_span(&error.at, 0, 0).unwrap();
+
+    assert_eq!(error.at, SourceSpan::from((10, 1)));
+    assert_eq!(contents.data(), b"x");
+    assert_eq!((contents.line(), contents.column()), (1, 4));
+
+    let contents = error.source.read_span(&error.at, 1, 1).unwrap();
+    assert_eq!(contents.data(), b"a = 1\nb = x\nc = 3");
+}
+
+#[test]
+fn labels_are_rendered_at_their_position() {
+    let error = parse("a = 1\nb = x").map(|_| ()).unwrap_err();
+
+    let mut rendered = String::new();
+    NarratableReportHandler::new()
+        .render_report(&mut rendered, &error)
+        .unwrap();
+
+    assert!(rendered.contains("invalid binding"), "{}", rendered);
+    assert!(
+        rendered.contains("label at line 2, column 5: expected a number"),
+        "{}",
+        rendered
+    );
+}
+
+#[test]
+fn sliced_spans_are_not_source_code() {
+    let input = Input::new("ab", true);
+    let (rest, _) = alpha1::<_, ()>(input).unwrap();
+
+    assert!(rest.read_span(&(0, 1).into(), 0, 0).is_err());
+}
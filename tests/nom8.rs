@@ -0,0 +1,235 @@
+#![cfg(feature = "nom8")]
+
+use nom8::{
+    bytes::{
+        complete::{tag, take_till},
+        streaming::take_while1,
+    },
+    character::complete::{alpha1, anychar, line_ending, not_line_ending},
+    combinator::recognize,
+    error::{Error, ErrorKind},
+    multi::{length_data, many1},
+    number::complete::{be_u16, double},
+    Err, IResult, Input, Parser,
+};
+use nom_span::{ArcStr, Position, RecordTracker, Spanned};
+use std::sync::Arc;
+
+extern crate nom8;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn utf8_vs_ascii() {
+    let utf8 = Span::new("🙌", true);
+    let ascii = Span::new("🙌", false);
+
+    let utf8_after: IResult<Span<'_>, Vec<char>> = many1(anychar).parse(utf8);
+    let ascii_after: IResult<Span<'_>, Vec<char>> = many1(anychar).parse(ascii);
+
+    let (utf8_after, _) = utf8_after.unwrap();
+    let (ascii_after, _) = ascii_after.unwrap();
+
+    assert_eq!(utf8_after.col(), 2);
+    assert_eq!(ascii_after.col(), 5);
+}
+
+#[test]
+fn can_compare_with_different_type() {
+    fn until_newline(input: Span<'_>) -> IResult<Span<'_>, &str> {
+        let (rest, comment_str) = not_line_ending(input)?;
+        Ok((rest, *comment_str))
+    }
+
+    let (after, comment) = until_newline(Span::new("test\n", true)).unwrap();
+
+    assert_eq!(comment, "test");
+    assert_eq!(*after, "\n");
+}
+
+#[test]
+fn lines_are_counted_across_parsers() {
+    let span = Span::new("hello\nwörld", true);
+
+    let (after, (word, _, next)) = (alpha1::<_, Error<_>>, line_ending, tag("wö"))
+        .parse(span)
+        .unwrap();
+
+    assert_eq!(word.pos(), Position::new(1, 1, 0));
+    assert_eq!(next.pos(), Position::new(2, 1, 6));
+    assert_eq!(after.pos(), Position::new(2, 3, 9));
+}
+
+#[test]
+fn take_keeps_the_position_and_take_from_advances() {
+    let span = Span::new("ab\ncd", true).take_from(1);
+
+    assert_eq!(span.take(2).pos(), span.pos());
+    assert_eq!(span.take_from(2).pos(), Position::new(2, 1, 3));
+
+    let (rest, consumed) = span.take_split(2);
+    assert_eq!(*consumed, "b\n");
+    assert_eq!(consumed.pos(), Position::new(1, 2, 1));
+    assert_eq!(rest.pos(), Position::new(2, 1, 3));
+}
+
+#[test]
+fn recognize_gets_the_consumed_span() {
+    let span = Span::new("ab cd", true);
+
+    let (_, word): (_, Span<'_>) = recognize((alpha1::<_, Error<_>>, tag(" ")))
+        .parse(span)
+        .unwrap();
+
+    assert_eq!(*word, "ab ");
+    assert_eq!(word.end_position(), Position::new(1, 4, 3));
+}
+
+#[test]
+fn streaming_take_while1_matches_str() {
+    fn word<I: Input<Item = char>>(input: I) -> IResult<I, I> {
+        take_while1(|c: char| c.is_alphabetic()).parse(input)
+    }
+
+    assert!(matches!(
+        word(Span::new("1ab", true)),
+        Err(Err::Error(Error {
+            code: ErrorKind::TakeWhile1,
+            ..
+        }))
+    ));
+    assert!(matches!(word("1ab"), Err(Err::Error(_))));
+
+    assert!(matches!(
+        word(Span::new("ab", true)),
+        Err(Err::Incomplete(_))
+    ));
+    assert!(matches!(word("ab"), Err(Err::Incomplete(_))));
+
+    let (rest, output) = word(Span::new("ab1", true)).unwrap();
+    assert_eq!((*rest, *output), word("ab1").unwrap());
+    assert_eq!(rest.col(), 3);
+}
+
+#[test]
+fn trackers_work_with_nom8() {
+    let records: &[u8] = b"\x00\x03abc\x00\x01d";
+    let span = Spanned::with_tracker(records, RecordTracker::new(2));
+
+    let (after, record): (_, Spanned<&[u8], (), RecordTracker>) =
+        length_data(be_u16::<_, Error<_>>).parse(span).unwrap();
+
+    assert_eq!(*record, b"abc");
+    assert_eq!(record.col(), 3);
+    assert_eq!(after.line(), 2);
+    assert_eq!(after.col(), 1);
+}
+
+#[test]
+fn extra_is_carried() {
+    let span = Spanned::new_extra("ab", true, "file.txt");
+
+    let (after, _) = tag::<_, _, Error<_>>("a").parse(span).unwrap();
+
+    assert_eq!(*after.extra(), "file.txt");
+    assert_eq!(after.col(), 2);
+}
+
+#[test]
+fn double_keeps_the_position() {
+    let res: IResult<Span<'_>, f64> = double(Span::new("1.5e-3;", true));
+    let (rest, value) = res.unwrap();
+
+    assert!((value - 1.5e-3).abs() < f64::EPSILON);
+    assert_eq!(*rest, ";");
+    assert_eq!(rest.col(), 7);
+}
+
+#[test]
+fn map_back_locates_derived_spans() {
+    let raw = Span::new("x = \"a\\tb\"", true).take_from(5);
+    let derived = raw.respan("a\tb");
+
+    let (inner, _) = tag::<_, _, Error<_>>("a\t").parse(derived).unwrap();
+    let origin = raw.map_back(&inner, |offset| offset + 1);
+
+    assert_eq!(*origin, "b\"");
+    assert_eq!(origin.col(), 9);
+}
+
+#[cfg(feature = "caseless")]
+#[test]
+fn tag_no_case_unicode_works_with_nom8() {
+    let res: IResult<Span<'_>, Span<'_>> =
+        nom_span::tag_no_case_unicode("été").parse(Span::new("ÉTÉ!", true));
+    let (rest, word) = res.unwrap();
+
+    assert_eq!(*word, "ÉTÉ");
+    assert_eq!(rest.col(), 4);
+}
+
+#[test]
+fn shared_strs_are_inputs() {
+    let span = Spanned::new(ArcStr::from(Arc::from("ré\nab")), true);
+
+    let (rest, (word, _)) = (take_till(|c| c == '\n'), line_ending::<_, Error<_>>)
+        .parse(span)
+        .unwrap();
+
+    assert_eq!(*word, "ré");
+    assert_eq!(rest.pos(), Position::new(2, 1, 4));
+    assert_eq!(rest.as_str(), "ab");
+}
+
+#[test]
+fn position_works_with_nom8() {
+    let res: IResult<Span<'_>, (Span<'_>, Span<'_>)> =
+        (tag("é"), nom_span::position).parse(Span::new("é;", true));
+    let (rest, (_, pos)) = res.unwrap();
+
+    assert_eq!(*pos, "");
+    assert_eq!(pos.pos(), Position::new(1, 2, 2));
+    assert_eq!(*rest, ";");
+}
+
+#[test]
+fn with_span_works_with_nom8() {
+    let res: IResult<Span<'_>, Vec<_>> =
+        many1(nom_span::with_span(anychar)).parse(Span::new("éa", true));
+    let (_, chars) = res.unwrap();
+
+    assert_eq!(chars[0].1, Position::new(1, 1, 0));
+    assert_eq!(chars[0].2, Position::new(1, 2, 2));
+    assert_eq!(chars[1].2, Position::new(1, 3, 3));
+}
+
+#[test]
+fn consumed_span_works_with_nom8() {
+    let res: IResult<Span<'_>, _> =
+        nom_span::consumed_span((anychar, anychar)).parse(Span::new("aé;", true));
+    let (rest, (consumed, _)) = res.unwrap();
+
+    assert_eq!(*consumed, "aé");
+    assert_eq!(consumed.pos(), Position::new(1, 1, 0));
+    assert_eq!(*rest, ";");
+}
+
+#[test]
+fn span_until_works_with_nom8() {
+    let before = Span::new("aé;", true);
+    let (after, _) = (anychar::<_, Error<_>>, anychar).parse(before).unwrap();
+
+    assert_eq!(*before.span_until(&after), "aé");
+    assert_eq!(before.span_until(&after).pos(), before.pos());
+}
+
+#[test]
+fn take_chars_works_with_nom8() {
+    let res: IResult<Spanned<&[u8]>, Spanned<&[u8]>> =
+        nom_span::take_chars(2).parse(Spanned::new("aéb".as_bytes(), true));
+    let (rest, field) = res.unwrap();
+
+    assert_eq!(*field, "aé".as_bytes());
+    assert_eq!(rest.col(), 3);
+}
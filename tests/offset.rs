@@ -0,0 +1,62 @@
+use common::Slice;
+use nom::Offset;
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn checked_offset_of_a_span_within() {
+    let span = Span::new("héllo\nwörld", true);
+    let rest = span.slice(7..);
+
+    assert_eq!(span.checked_offset(&rest), Some(7));
+    assert_eq!(span.checked_offset(&span), Some(0));
+    assert_eq!(span.checked_offset(&span.slice(span.len()..)), Some(13));
+    assert_eq!(span.offset(&rest), 7);
+}
+
+#[test]
+fn checked_offset_of_a_sub_span() {
+    let span = Span::new("héllo\nwörld", true);
+    let word = span.slice(1..6);
+
+    assert_eq!(span.checked_offset(&word), Some(1));
+    assert_eq!(word.checked_offset(&span.slice(3..)), None);
+}
+
+#[test]
+fn checked_offset_out_of_range() {
+    let span = Span::new("héllo\nwörld", true);
+    let rest = span.slice(7..);
+
+    assert_eq!(rest.checked_offset(&span), None);
+}
+
+#[test]
+fn checked_offset_of_different_inputs() {
+    let first = String::from("first input");
+    let second = String::from("second input");
+
+    let first = Span::new(&first, true);
+    let second = Span::new(&second, true);
+
+    assert_eq!(first.checked_offset(&second), None);
+    assert_eq!(second.checked_offset(&first), None);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the span at 2:1 (byte 7) doesn't contain the span at 1:1 (byte 0)")]
+fn offset_panics_on_unrelated_spans_in_debug() {
+    let span = Span::new("héllo\nwörld", true);
+
+    span.slice(7..).offset(&span);
+}
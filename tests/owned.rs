@@ -0,0 +1,75 @@
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::digit1,
+    error::Error,
+    Finish, IResult, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn number(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    digit1(input)
+}
+
+/// Parse an input that is dropped before returning, the error being kept
+fn parse_error(input: String) -> Error<Spanned<String>> {
+    let error = number(Span::new(&input, true)).finish().unwrap_err();
+
+    Error::new(error.input.into_owned(), error.code)
+}
+
+#[test]
+fn borrowed_owned_borrowed_round_trip() {
+    let span = Spanned::new_extra("héllo\nwörld", true, "file.txt");
+    let (rest, _) = take_until::<_, _, ()>("w").parse(span).unwrap();
+
+    let owned: Spanned<String, &str> = rest.into_owned();
+    assert_eq!(owned.data(), "wörld");
+    assert_eq!(owned.pos(), Position::new(2, 1, 7));
+    assert_eq!(*owned.extra(), "file.txt");
+
+    let borrowed = owned.as_deref();
+    assert_eq!(borrowed, rest);
+    assert_eq!(borrowed.into_owned(), owned);
+}
+
+#[test]
+fn owned_spans_are_parsed_again_through_a_borrow() {
+    let (rest, _) = tag::<_, _, ()>("a = ")
+        .parse(Span::new("a = 42;", true))
+        .unwrap();
+    let owned = rest.into_owned();
+
+    let (rest, digits) = number(owned.as_deref()).unwrap();
+
+    assert_eq!(*digits, "42");
+    assert_eq!(digits.col(), 5);
+    assert_eq!(rest.col(), 7);
+}
+
+#[test]
+fn owned_errors_outlive_the_input() {
+    let error = parse_error("x2".to_string());
+
+    assert_eq!(error.input.data(), "x2");
+    assert_eq!(error.input.pos(), Position::new(1, 1, 0));
+    assert_eq!(error.to_string(), "error Digit at: x2");
+}
+
+#[test]
+fn byte_spans_are_owned_as_vecs() {
+    let span = Spanned::new(&b"ab\ncd"[..], false);
+    let (rest, _) = tag::<_, _, ()>("ab\n").parse(span).unwrap();
+
+    let owned = rest.into_owned();
+    assert_eq!(owned.data(), b"cd");
+    assert_eq!(owned.pos(), Position::new(2, 1, 3));
+    assert_eq!(owned.as_deref(), rest);
+}
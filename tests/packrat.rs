@@ -0,0 +1,106 @@
+#![cfg(all(feature = "nom7", feature = "packrat"))]
+// `packrat_parser` expands to the traits of nom 7
+// `packrat_parser` traces the memoization when the crate using it has a `trace` feature
+#![allow(unexpected_cfgs)]
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{digit1, multispace0},
+    combinator::map,
+    sequence::{delimited, terminated},
+    IResult,
+};
+use nom_packrat::{init, packrat_parser, storage};
+use nom_span::{Position, Spanned};
+use std::cell::Cell;
+
+extern crate nom;
+extern crate nom_packrat;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+storage!(Position);
+
+thread_local!(static CALLS: Cell<usize> = const { Cell::new(0) });
+
+/// `nested = "(" nested ")" | "(" nested "]" | digit`, giving the position of the digit
+///
+/// When the brackets are closed by `]`, every level parses what's inside twice
+fn nested(input: Span<'_>) -> IResult<Span<'_>, Position> {
+    CALLS.with(|calls| calls.set(calls.get() + 1));
+
+    alt((
+        delimited(open, nested, tag(")")),
+        delimited(open, nested, tag("]")),
+        map(digit1, |digit: Span<'_>| digit.pos()),
+    ))(input)
+}
+
+#[packrat_parser]
+fn memoized(input: Span<'_>) -> IResult<Span<'_>, Position> {
+    CALLS.with(|calls| calls.set(calls.get() + 1));
+
+    alt((
+        delimited(open, memoized, tag(")")),
+        delimited(open, memoized, tag("]")),
+        map(digit1, |digit: Span<'_>| digit.pos()),
+    ))(input)
+}
+
+fn open(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    terminated(tag("("), multispace0)(input)
+}
+
+type Parser = for<'a> fn(Span<'a>) -> IResult<Span<'a>, Position>;
+
+/// Run `parser` on `input`, giving the position of the digit and of the rest, and the number of
+/// calls it took
+fn run(parser: Parser, input: &str) -> (Option<(Position, Position)>, usize) {
+    init!();
+    CALLS.with(|calls| calls.set(0));
+
+    let positions = parser(Span::new(input, true))
+        .ok()
+        .map(|(rest, digit)| (digit, rest.pos()));
+
+    (positions, CALLS.with(Cell::get))
+}
+
+#[test]
+fn memoized_results_are_the_same() {
+    for input in ["7", "(\n(é7)) x", "((\n 42]] x", "((7)]", "(x", "((7)"] {
+        assert_eq!(
+            run(memoized, input).0,
+            run(nested, input).0,
+            "on {:?}",
+            input
+        );
+    }
+
+    assert_eq!(
+        run(memoized, "((\n 42]] x").0,
+        Some((Position::new(2, 2, 4), Position::new(2, 6, 8))),
+    );
+}
+
+#[test]
+fn memoization_avoids_the_backtracking() {
+    let depth = 12;
+    let input = format!("{}1{}", "(".repeat(depth), "]".repeat(depth));
+
+    let (positions, calls) = run(nested, &input);
+    let (memoized_positions, memoized_calls) = run(memoized, &input);
+
+    assert_eq!(memoized_positions, positions);
+    assert_eq!(
+        positions,
+        Some((
+            Position::new(1, depth + 1, depth),
+            Position::new(1, 2 * depth + 2, input.len())
+        )),
+    );
+    assert!(calls >= 1 << depth, "{} calls", calls);
+    assert!(memoized_calls <= depth + 1, "{} calls", memoized_calls);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "nom7")]
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, multispace0},
+    multi::many0,
+    sequence::{delimited, preceded, separated_pair},
+    IResult, Parser,
+};
+use nom_span::{Position, Spanned, SpannedParserExt, WithSpan};
+
+extern crate nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[derive(Debug, PartialEq)]
+struct Binding<'a> {
+    name: WithSpan<&'a str>,
+    value: WithSpan<u32>,
+}
+
+fn binding(input: Span<'_>) -> IResult<Span<'_>, Binding<'_>> {
+    separated_pair(
+        alpha1
+            .spanned()
+            .map(|name| name.map(|name: Span<'_>| *name.data())),
+        delimited(multispace0, tag("="), multispace0),
+        digit1
+            .spanned()
+            .map(|value| value.map(|digits: Span<'_>| digits.parse().unwrap())),
+    )
+    .map(|(name, value)| Binding { name, value })
+    .parse(input)
+}
+
+#[test]
+fn spanned_outputs_have_both_ends() {
+    let (_, bindings) =
+        many0(preceded(multispace0, binding))(Span::new("ab = 1\n  cd =\n42", true)).unwrap();
+
+    assert_eq!(
+        bindings[1],
+        Binding {
+            name: WithSpan::new("cd", Position::new(2, 3, 9), Position::new(2, 5, 11)),
+            value: WithSpan::new(42, Position::new(3, 1, 14), Position::new(3, 3, 16)),
+        }
+    );
+    assert_eq!(bindings[0].name.start(), Position::new(1, 1, 0));
+    assert_eq!(*bindings[0].value.value(), 1);
+    assert_eq!(bindings[0].value.end(), Position::new(1, 7, 6));
+    assert_eq!(bindings[0].value.into_value(), 1);
+}
+
+#[test]
+fn map_with_span_gets_the_consumed_span() {
+    let mut word = preceded(multispace0, alpha1)
+        .map_with_span(|word: Span<'_>, span: Span<'_>| (*word.data(), span.pos(), span.len()));
+
+    let res: IResult<Span<'_>, _> = word(Span::new("\n ab;", true));
+    let (rest, (word, start, len)) = res.unwrap();
+
+    assert_eq!(word, "ab");
+    assert_eq!(start, Position::new(1, 1, 0));
+    assert_eq!(len, 4);
+    assert_eq!(*rest, ";");
+}
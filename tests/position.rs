@@ -0,0 +1,199 @@
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::{Position, SourceId, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn pos_matches_the_accessors() {
+    let span = Span::new("ab\ncdé\nf", true);
+
+    let (rest, _): (_, Span) = take::<_, _, ()>(6usize).parse(span).unwrap();
+
+    assert_eq!(span.pos(), Position::new(1, 1, 0));
+    assert_eq!(
+        rest.pos(),
+        Position::new(rest.line(), rest.col(), rest.byte_offset())
+    );
+    assert_eq!(rest.pos(), Position::new(2, 4, 7));
+}
+
+#[test]
+fn positions_are_ordered_by_offset() {
+    let span = Span::new("first\nsecond\nthird", false);
+
+    let start = span.pos();
+    let middle = span.slice(8..).pos();
+    let end = span.slice(15..).pos();
+
+    assert!(start < middle);
+    assert!(middle < end);
+    assert_eq!(start.max(end), end);
+
+    let mut positions = vec![end, start, middle];
+    positions.sort();
+
+    assert_eq!(positions, vec![start, middle, end]);
+}
+
+#[test]
+fn positions_can_be_built_and_compared() {
+    let stored = Position {
+        source: SourceId::default(),
+        line: 2,
+        col: 3,
+        offset: 8,
+    };
+
+    assert_eq!(Span::new("first\nsecond", true).slice(8..).pos(), stored);
+    assert_ne!(stored, Position::new(2, 4, 9));
+
+    let mut set = std::collections::HashSet::new();
+    set.insert(stored);
+
+    assert!(set.contains(&Position::new(2, 3, 8)));
+}
+
+#[test]
+fn end_position_of_a_recognized_token() {
+    use nom::{bytes::complete::tag, combinator::recognize, sequence::pair, Parser};
+
+    let span = Span::new("let héllo = 1", true);
+
+    let (_, token) = recognize(pair(tag::<_, _, ()>("let "), tag("héllo")))
+        .parse(span)
+        .unwrap();
+
+    assert_eq!(token.pos(), Position::new(1, 1, 0));
+    assert_eq!(token.end_position(), Position::new(1, 10, 10));
+    assert_eq!(token.end_line(), 1);
+    assert_eq!(token.end_col(), 10);
+}
+
+#[test]
+fn end_position_respects_the_encoding() {
+    let utf8 = Span::new("a\nbé🙌", true).slice(2..);
+    let ascii = Span::new("a\nbé🙌", false).slice(2..);
+
+    assert_eq!(utf8.end_position(), Position::new(2, 4, 9));
+    assert_eq!(ascii.end_position(), Position::new(2, 8, 9));
+}
+
+#[test]
+fn end_position_of_an_empty_span_is_its_start() {
+    let span = Span::new("abc\ndef", true).slice(5..5);
+
+    assert_eq!(span.end_position(), span.pos());
+}
+
+#[test]
+fn end_position_after_a_trailing_newline() {
+    let span = Span::new("abc\ndef\n", true).slice(4..);
+
+    assert_eq!(span.end_position(), Position::new(3, 1, 8));
+}
+
+#[test]
+fn position_relative_to_on_the_first_line_of_the_fragment() {
+    let host = Span::new("<p>{{ a + }}</p>", true);
+    let (block, _): (_, Span) = take::<_, _, ()>(6usize).parse(host).unwrap();
+
+    let inner = Span::new("a + ", true).slice(4..);
+
+    assert_eq!(inner.pos(), Position::new(1, 5, 4));
+    assert_eq!(
+        inner.position_relative_to(block.pos()),
+        Position::new(1, 11, 10)
+    );
+}
+
+#[test]
+fn position_relative_to_on_a_later_line_of_the_fragment() {
+    let host = Span::new("line 1\n  {{ a +\n  b * }}", true);
+    let block = host.slice(11..);
+
+    let inner = Span::new(" a +\n  b * ", true).slice(11..);
+
+    assert_eq!(inner.pos(), Position::new(2, 7, 11));
+    assert_eq!(block.pos(), Position::new(2, 5, 11));
+    assert_eq!(
+        inner.position_relative_to(block.pos()),
+        Position::new(3, 7, 22)
+    );
+    assert_eq!(
+        inner.position_relative_to(block.pos()),
+        host.slice(22..).pos()
+    );
+}
+
+#[test]
+fn char_offset_diverges_from_byte_offset_on_multibyte_input() {
+    let span = Span::new("é🙌\nça va", true);
+
+    let (first, _): (_, Span) = take::<_, _, ()>(2usize).parse(span).unwrap();
+    let (second, _): (_, Span) = take::<_, _, ()>(3usize).parse(first).unwrap();
+    let (third, _): (_, Span) = take::<_, _, ()>(3usize).parse(second).unwrap();
+
+    assert_eq!((first.byte_offset(), first.char_offset()), (6, 2));
+    assert_eq!((second.byte_offset(), second.char_offset()), (10, 5));
+    assert_eq!((third.byte_offset(), third.char_offset()), (13, 8));
+    assert_eq!((third.line(), third.col()), (2, 6));
+}
+
+#[test]
+fn char_offset_is_the_byte_offset_without_utf8() {
+    let span = Span::new("é🙌\nça va", false);
+
+    let first = span.slice(6..);
+    let second = first.slice(4..);
+
+    assert_eq!(first.char_offset(), first.byte_offset());
+    assert_eq!(second.char_offset(), second.byte_offset());
+    assert_eq!(second.char_offset(), 10);
+}
+
+#[test]
+fn zero_based_counters_across_new_lines() {
+    let span = Span::new("ab\ncd\n\né", true);
+
+    assert_eq!((span.line0(), span.col0()), (0, 0));
+    assert_eq!((span.slice(2..).line0(), span.slice(2..).col0()), (0, 2));
+    assert_eq!((span.slice(3..).line0(), span.slice(3..).col0()), (1, 0));
+    assert_eq!((span.slice(7..).line0(), span.slice(7..).col0()), (3, 0));
+}
+
+#[test]
+fn zero_based_counters_at_the_end_of_the_input() {
+    let span = Span::new("ab\ncdé", true);
+    let end = span.slice(span.len()..);
+
+    assert_eq!((end.line0(), end.col0()), (1, 3));
+    assert_eq!(end.pos().line0(), end.line0());
+    assert_eq!(end.pos().col0(), end.col0());
+    assert_eq!(
+        (span.end_position().line0(), span.end_position().col0()),
+        (1, 3)
+    );
+}
+
+#[test]
+fn zero_based_counters_of_positions_starting_at_0() {
+    assert_eq!(Position::new(0, 0, 0).line0(), 0);
+    assert_eq!(Position::new(0, 0, 0).col0(), 0);
+}
+
+#[test]
+fn positions_display_as_line_and_column() {
+    let pos = Position::new(2, 3, 7);
+
+    assert_eq!(pos.to_string(), "2:3");
+    assert_eq!(format!("{:#}", pos), "line 2, column 3");
+}
@@ -0,0 +1,67 @@
+use common::Slice;
+use nom::FindSubstring;
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+const INPUT: &str = "fn é() {\n\n    🦀\n}";
+
+#[test]
+fn positions_are_the_ones_of_the_sliced_spans() {
+    for handle_utf8 in [true, false] {
+        let span = Span::new(INPUT, handle_utf8).slice(3..);
+
+        for rel_offset in (0..=span.len()).filter(|&i| span.is_char_boundary(i)) {
+            let position = span.slice(rel_offset..).pos();
+
+            assert_eq!(span.position_at(rel_offset), position);
+            assert_eq!(
+                span.offset_of(position.line, position.col),
+                Some(rel_offset)
+            );
+        }
+    }
+}
+
+#[test]
+fn offsets_on_newlines_are_at_the_end_of_their_line() {
+    let span = Span::new(INPUT, true);
+
+    // The newline ending the first line, then the one of the empty line
+    assert_eq!(span.position_at(9), Position::new(1, 9, 9));
+    assert_eq!(span.position_at(10), Position::new(2, 1, 10));
+    assert_eq!(span.position_at(11), Position::new(3, 1, 11));
+    assert_eq!(span.offset_of(1, 9), Some(9));
+    assert_eq!(span.offset_of(2, 1), Some(10));
+    assert_eq!(span.offset_of(2, 2), None);
+}
+
+#[test]
+fn offsets_found_in_the_data_are_located() {
+    let span = Span::new(INPUT, true).slice(10..);
+    let index = span.find_substring("🦀").unwrap();
+
+    assert_eq!(span.position_at(index), Position::new(3, 5, 15));
+    assert_eq!(span.position_at(100), span.end_position());
+}
+
+#[test]
+fn lines_outside_of_the_data_have_no_offset() {
+    let span = Span::new(INPUT, true).slice(10..);
+
+    assert_eq!(span.offset_of(1, 1), None);
+    assert_eq!(span.offset_of(2, 2), None);
+    assert_eq!(span.offset_of(4, 1), Some(10));
+    assert_eq!(span.offset_of(4, 2), Some(11));
+    assert_eq!(span.offset_of(4, 3), None);
+    assert_eq!(span.offset_of(5, 1), None);
+    assert_eq!(span.offset_of(3, 0), None);
+}
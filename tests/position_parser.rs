@@ -0,0 +1,51 @@
+use common::tuple;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, multispace0},
+    error::Error,
+    sequence::preceded,
+    IResult, Parser,
+};
+use nom_span::{position, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn position_does_not_consume_the_input() {
+    let span = Span::new("ab\nc", true);
+
+    let (rest, pos) = position::<_, _, _, Error<Span<'_>>>(span).unwrap();
+
+    assert_eq!(rest, span);
+    assert_eq!(*pos, "");
+    assert_eq!(pos.pos(), span.pos());
+}
+
+#[test]
+fn position_gives_the_start_of_the_next_token() {
+    fn binding(input: Span<'_>) -> IResult<Span<'_>, (Span<'_>, Span<'_>, Span<'_>)> {
+        tuple((
+            preceded(multispace0, position),
+            tag("let"),
+            preceded(multispace0, alpha1),
+        ))
+        .parse(input)
+    }
+
+    let (rest, (start, keyword, name)) = binding(Span::new("\n  let x;", true)).unwrap();
+
+    assert_eq!(start.pos(), Position::new(2, 3, 3));
+    assert_eq!(*start, "");
+    assert_eq!(start.end_position(), start.pos());
+    assert_eq!(keyword.pos(), Position::new(2, 3, 3));
+    assert_eq!(name.pos(), Position::new(2, 7, 7));
+    assert_eq!(*rest, ";");
+}
@@ -0,0 +1,71 @@
+use common::Slice;
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn chars_come_with_their_position() {
+    let chars: Vec<_> = Span::new("a\né\r\nb", true).positioned_chars().collect();
+
+    assert_eq!(
+        chars,
+        [
+            (Position::new(1, 1, 0), 'a'),
+            (Position::new(1, 2, 1), '\n'),
+            (Position::new(2, 1, 2), 'é'),
+            (Position::new(2, 2, 4), '\r'),
+            (Position::new(2, 3, 5), '\n'),
+            (Position::new(3, 1, 6), 'b'),
+        ]
+    );
+}
+
+#[test]
+fn the_positions_are_the_ones_of_the_slices() {
+    for handle_utf8 in [true, false] {
+        let span = Span::new("ab\n🙌é\tc\n\nd", handle_utf8).slice(1..);
+
+        let mut chars = span.positioned_chars();
+        for (i, _) in span.data().char_indices() {
+            assert_eq!(chars.next().unwrap().0, span.slice(i..).pos());
+        }
+
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.current_position(), span.slice(span.len()..).pos());
+        assert_eq!(chars.current_position(), span.end_position());
+    }
+}
+
+#[test]
+fn ascii_columns_count_the_bytes() {
+    let chars: Vec<_> = Span::new("éa", false)
+        .positioned_chars()
+        .map(|(pos, _)| pos.col)
+        .collect();
+
+    assert_eq!(chars, [1, 3]);
+}
+
+#[test]
+fn the_rest_is_what_remains() {
+    let mut chars = Span::new("ab\nc", true).positioned_chars();
+    chars.nth(2);
+
+    assert_eq!(*chars.rest().data(), "c");
+    assert_eq!(chars.current_position(), Position::new(2, 1, 3));
+}
+
+#[test]
+fn the_index_of_a_char_is_found_with_the_iterator() {
+    let mut chars = Span::new("aé\nx", true).positioned_chars();
+
+    assert_eq!(chars.position(|(_, c)| c == 'x'), Some(3));
+}
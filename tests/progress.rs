@@ -0,0 +1,48 @@
+use nom::{
+    character::complete::{alpha1, multispace0},
+    sequence::preceded,
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn progress_never_goes_back() {
+    let input = "ab  cafe\n xyz\n\n\telan end";
+    let mut span = Span::new(input, true);
+    let mut last = span.progress();
+
+    assert_eq!(last, 0.0);
+    loop {
+        let res: IResult<Span<'_>, Span<'_>, ()> = preceded(multispace0, alpha1).parse(span);
+        let Ok((rest, _)) = res else {
+            break;
+        };
+
+        assert!(rest.progress() >= last);
+        assert_eq!(rest.bytes_consumed() + rest.bytes_remaining(), input.len());
+        assert_eq!(rest.bytes_consumed(), rest.byte_offset());
+        last = rest.progress();
+        span = rest;
+    }
+
+    assert_eq!(span.bytes_remaining(), 0);
+    assert_eq!(span.progress(), 1.0);
+}
+
+#[test]
+fn progress_is_the_fraction_consumed() {
+    let span = Span::new("abcd", true);
+    let (rest, _): (_, Span<'_>) = alpha1::<_, ()>(Span::new("ab  ", true)).unwrap();
+
+    assert_eq!(rest.progress(), 0.5);
+    assert_eq!(Span::new("", true).progress(), 1.0);
+    assert_eq!(span.bytes_remaining(), 4);
+}
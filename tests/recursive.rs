@@ -0,0 +1,75 @@
+#![cfg(feature = "recursive")]
+// `recursive_parser` traces the recursion when the crate using it has a `trace` feature
+#![allow(unexpected_cfgs)]
+
+use common::tuple;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{digit1, multispace0},
+    combinator::map,
+    sequence::delimited,
+    IResult, Parser,
+};
+use nom_recursive::{recursive_parser, RecursiveInfo};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_recursive;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str, RecursiveInfo>;
+
+#[derive(Debug)]
+enum Expr<'a> {
+    Number(Span<'a>),
+    Add(Box<Expr<'a>>, Span<'a>, Span<'a>),
+}
+
+/// `expr = expr "+" term | term`
+fn expr(input: Span<'_>) -> IResult<Span<'_>, Expr<'_>> {
+    alt((add, map(term, Expr::Number))).parse(input)
+}
+
+#[recursive_parser]
+fn add(input: Span<'_>) -> IResult<Span<'_>, Expr<'_>> {
+    map(tuple((expr, tag("+"), term)), |(left, plus, right)| {
+        Expr::Add(Box::new(left), plus, right)
+    })
+    .parse(input)
+}
+
+fn term(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    delimited(multispace0, digit1, multispace0).parse(input)
+}
+
+#[test]
+fn left_recursion_is_parsed() {
+    let (rest, parsed) = expr(Spanned::new_extra("12 +\n 3", true, RecursiveInfo::new())).unwrap();
+    assert!(rest.is_empty());
+
+    let Expr::Add(left, plus, right) = parsed else {
+        panic!("expected an addition, got {:?}", parsed);
+    };
+    let Expr::Number(left) = *left else {
+        panic!("expected a number, got {:?}", left);
+    };
+
+    assert_eq!(*left, "12");
+    assert_eq!(left.pos(), Position::new(1, 1, 0));
+    assert_eq!(plus.pos(), Position::new(1, 4, 3));
+    assert_eq!(*right, "3");
+    assert_eq!(right.pos(), Position::new(2, 2, 6));
+}
+
+#[test]
+fn terms_alone_are_expressions() {
+    let (_, parsed) = expr(Spanned::new_extra(" 7", true, RecursiveInfo::new())).unwrap();
+
+    assert!(matches!(parsed, Expr::Number(number) if number.col() == 2));
+}
@@ -0,0 +1,49 @@
+use common::Slice;
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn empty_fragments_have_no_lines_left() {
+    let span = Span::new("a\nb", true).slice(2..2);
+
+    assert_eq!(span.remaining_lines(), 0);
+    assert_eq!(span.last_line_number(), 2);
+}
+
+#[test]
+fn an_unterminated_line_is_the_last_one() {
+    let span = Span::new("é b", true);
+
+    assert_eq!(span.remaining_lines(), 0);
+    assert_eq!(span.last_line_number(), 1);
+}
+
+#[test]
+fn a_final_newline_ends_on_the_next_line() {
+    let span = Span::new("a\nb\nc\n", true).slice(2..);
+
+    assert_eq!(span.remaining_lines(), 2);
+    assert_eq!(span.last_line_number(), 4);
+    assert_eq!(span.last_line_number(), span.end_line());
+}
+
+#[test]
+fn crlf_counts_once() {
+    let span = Spanned::builder("a\r\nb\r\n\r\nc").crlf(true).build();
+
+    assert_eq!(span.remaining_lines(), 3);
+    assert_eq!(span.last_line_number(), span.end_line());
+
+    let bytes = Spanned::new(&b"x\r\ny"[..], false);
+    assert_eq!(bytes.remaining_lines(), 1);
+    assert_eq!(bytes.last_line_number(), 2);
+}
@@ -0,0 +1,116 @@
+use nom::{
+    bytes::complete::{escaped, tag, take_while},
+    character::complete::{none_of, one_of},
+    combinator::all_consuming,
+    error::Error,
+    sequence::delimited,
+    Err, IResult, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+const SOURCE: &str = "let x = 1;\nlet s = \"ab\\tc+\";";
+
+fn raw_literal(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    delimited(
+        tag("\""),
+        escaped(none_of("\\\""), '\\', one_of("\\\"t")),
+        tag("\""),
+    )
+    .parse(input)
+}
+
+fn literal_at(source: &str) -> Span<'_> {
+    let span = Span::new(source, true);
+    let (span, _) = take_while::<_, _, ()>(|c| c != '"').parse(span).unwrap();
+
+    raw_literal(span).unwrap().1
+}
+
+/// Unescape `raw`, with the offset in `raw` of every byte of the result and of its end
+fn unescape(raw: &str) -> (String, Vec<usize>) {
+    let mut output = String::new();
+    let mut offsets = Vec::new();
+    let mut chars = raw.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        let c = if c == '\\' {
+            match chars.next() {
+                Some((_, 't')) => '\t',
+                Some((_, c)) => c,
+                None => break,
+            }
+        } else {
+            c
+        };
+
+        output.push(c);
+        offsets.extend(std::iter::repeat_n(i, c.len_utf8()));
+    }
+    offsets.push(raw.len());
+
+    (output, offsets)
+}
+
+fn no_plus(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    all_consuming(take_while(|c| c != '+')).parse(input)
+}
+
+#[test]
+fn respan_starts_at_the_position_of_the_span() {
+    let raw = literal_at(SOURCE);
+    let (unescaped, _) = unescape(&raw);
+
+    let derived = raw.respan(unescaped.as_str());
+
+    assert_eq!(*derived, "ab\tc+");
+    assert_eq!(derived.pos(), raw.pos());
+    assert_eq!(derived.pos(), Position::new(2, 10, 20));
+}
+
+#[test]
+fn inner_errors_are_located_in_the_host_file() {
+    let raw = literal_at(SOURCE);
+    let (unescaped, offsets) = unescape(&raw);
+    let derived = raw.respan(unescaped.as_str());
+
+    let Err(Err::Error(Error { input, .. })) = no_plus(derived) else {
+        panic!("expected an error");
+    };
+
+    // Without mapping the offsets, the escape is counted as a single column
+    assert_eq!(input.line(), 2);
+    assert_eq!(input.col(), 14);
+
+    let origin = raw.map_back(&input, |offset| offsets[offset]);
+    assert_eq!(*origin, "+");
+    assert_eq!(origin.pos(), Position::new(2, 15, 25));
+}
+
+#[test]
+fn map_back_is_capped_to_the_data() {
+    let raw = literal_at(SOURCE);
+    let derived = raw.respan("ab\tc+");
+
+    let origin = raw.map_back(&derived, |offset| offset + 100);
+
+    assert_eq!(*origin, "");
+    assert_eq!(origin.pos(), raw.end_position());
+}
+
+#[test]
+fn extra_is_kept_by_respan() {
+    let span = Spanned::new_extra("a\\nb", true, "file.txt");
+
+    let derived = span.respan(String::from("a\nb"));
+
+    assert_eq!(*derived.extra(), "file.txt");
+    assert_eq!(derived.data(), "a\nb");
+}
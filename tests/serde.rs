@@ -0,0 +1,27 @@
+#![cfg(feature = "serde")]
+
+use nom_span::{Position, SourceId, Span};
+
+extern crate nom_span;
+extern crate serde_json;
+
+#[test]
+fn spans_round_trip() {
+    let span = Span::new(Position::new(1, 2, 1), Position::new(2, 1, 4));
+
+    let json = serde_json::to_string(&span).unwrap();
+    assert_eq!(
+        json,
+        r#"{"start":{"offset":1,"line":1,"col":2},"end":{"offset":4,"line":2,"col":1}}"#
+    );
+    assert_eq!(serde_json::from_str::<Span>(&json).unwrap(), span);
+}
+
+#[test]
+fn sources_are_serialized_when_given() {
+    let position = Position::new(1, 2, 1).with_source(SourceId(3));
+
+    let json = serde_json::to_string(&position).unwrap();
+    assert_eq!(json, r#"{"source":3,"offset":1,"line":1,"col":2}"#);
+    assert_eq!(serde_json::from_str::<Position>(&json).unwrap(), position);
+}
@@ -0,0 +1,105 @@
+use common::Slice;
+use nom::{
+    bytes::complete::{tag, take_until, take_while1},
+    character::complete::{anychar, digit1, line_ending, space0},
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::{separated_pair, terminated},
+    IResult, Parser,
+};
+use nom_span::{ArcStr, Position, RcStr, Spanned};
+use std::{rc::Rc, sync::Arc, thread};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span = Spanned<ArcStr>;
+
+const INPUT: &str = "é = 12\nbé = 345\n";
+
+fn assignment(input: Span) -> IResult<Span, (Span, u32)> {
+    separated_pair(
+        take_while1(char::is_alphabetic),
+        terminated(tag(" ="), space0),
+        map_res(digit1, |digits: Span| digits.parse::<u32>()),
+    )
+    .parse(input)
+}
+
+fn assignments(input: Span) -> IResult<Span, Vec<(Span, u32)>> {
+    separated_list1(line_ending, assignment).parse(input)
+}
+
+/// Parse an input that is dropped before returning, only the results being left
+fn parse_names(input: &str) -> Vec<Span> {
+    let span = Span::new(ArcStr::from(Arc::from(input)), true);
+    let (_, assignments) = assignments(span).unwrap();
+
+    assignments.into_iter().map(|(name, _)| name).collect()
+}
+
+#[test]
+fn positions_are_the_ones_of_str_spans() {
+    let (rest, assignments) = assignments(Span::new(ArcStr::from(Arc::from(INPUT)), true)).unwrap();
+
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(*assignments[1].0, "bé");
+    assert_eq!(assignments[1].0.pos(), Position::new(2, 1, 8));
+    assert_eq!(assignments[1].1, 345);
+    assert_eq!(*rest, "\n");
+
+    let end = Spanned::new(INPUT, true).slice(INPUT.len() - 1..);
+    assert_eq!(rest.pos(), end.pos());
+}
+
+#[test]
+fn results_outlive_the_original_span() {
+    let names = parse_names(INPUT);
+    let name = names[1].clone();
+    drop(names);
+
+    assert_eq!(name.as_str(), "bé");
+    assert_eq!(name.data().source().len(), INPUT.len());
+    assert_eq!(name.pos(), Position::new(2, 1, 8));
+    assert_eq!(name.end_position(), Position::new(2, 3, 11));
+}
+
+#[test]
+fn spans_are_sent_to_other_threads() {
+    let names = parse_names(INPUT);
+
+    let handle = thread::spawn(move || {
+        names
+            .iter()
+            .map(|name| (name.to_string(), name.line()))
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(
+        handle.join().unwrap(),
+        [("é".to_string(), 1), ("bé".to_string(), 2)],
+    );
+}
+
+#[test]
+fn rc_str_spans_parse_on_one_thread() {
+    let span = Spanned::new(RcStr::from(Rc::from("aé;b")), true);
+
+    let res: IResult<_, _> = take_until(";").parse(span);
+    let (rest, head) = res.unwrap();
+
+    assert_eq!(*head, "aé");
+    assert_eq!(rest.col(), 3);
+    assert_eq!(rest.byte_offset(), 3);
+
+    let res: IResult<_, _> = anychar(rest.slice(1..));
+    let (rest, c) = res.unwrap();
+    assert_eq!(c, 'b');
+    assert_eq!(rest.col(), 5);
+    assert!(rest.is_empty());
+}
@@ -0,0 +1,51 @@
+use common::Slice;
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn skipping_from_the_middle_of_a_line() {
+    let span = Span::new("let é = ?;\r\nlet b = 2;", true).slice(9..);
+
+    let next = span.skip_to_next_line();
+
+    assert_eq!(*next, "let b = 2;");
+    assert_eq!(next.pos(), Position::new(2, 1, 13));
+    assert_eq!(next, span.slice(4..));
+}
+
+#[test]
+fn skipping_from_the_start_of_a_line_goes_to_the_next_one() {
+    let span = Span::new("a\n\nb", true).slice(2..);
+
+    let next = span.skip_to_next_line();
+
+    assert_eq!(*next, "b");
+    assert_eq!(next.pos(), Position::new(3, 1, 3));
+}
+
+#[test]
+fn skipping_on_the_last_line_goes_to_the_end() {
+    let span = Span::new("a\nbé", true).slice(2..);
+
+    let end = span.skip_to_next_line();
+
+    assert_eq!(*end, "");
+    assert_eq!(end.pos(), span.end_position());
+    assert_eq!(end.skip_to_next_line(), end);
+}
+
+#[test]
+fn byte_spans_skip_lines_too() {
+    let span = Spanned::new(&b"ab\ncd"[..], false);
+
+    assert_eq!(span.skip_to_next_line(), span.slice(3..));
+}
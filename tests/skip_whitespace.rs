@@ -0,0 +1,77 @@
+use common::Slice;
+use nom::{character::complete::multispace0, IResult};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn multispace(span: Span<'_>) -> Span<'_> {
+    let res: IResult<Span<'_>, Span<'_>, ()> = multispace0(span);
+
+    res.unwrap().0
+}
+
+#[test]
+fn skipping_counts_the_newlines() {
+    let span = Span::new("a \n\t\r\n\n  é", true).slice(1..);
+
+    let rest = span.skip_ascii_whitespace();
+
+    assert_eq!(*rest, "é");
+    assert_eq!(rest.pos(), Position::new(4, 3, 9));
+    assert_eq!(rest, multispace(span));
+    assert_eq!(span.skip_whitespace(), rest);
+}
+
+#[test]
+fn skipping_is_like_multispace0() {
+    for input in [
+        "",
+        "x",
+        "   ",
+        "\n\n\n",
+        "\r\n \r\n\tx \n",
+        " \u{a0}x",
+        "\n\u{2028}\n",
+    ] {
+        let span = Span::new(input, true);
+
+        assert_eq!(
+            span.skip_ascii_whitespace(),
+            multispace(span),
+            "on {:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn unicode_whitespace_is_skipped_too() {
+    let span = Spanned::builder("\u{a0}\u{2028} \u{3000}x")
+        .unicode_newlines(true)
+        .build();
+
+    let rest = span.skip_whitespace();
+
+    assert_eq!(*rest, "x");
+    assert_eq!(rest.pos(), Position::new(2, 3, 9));
+    assert_eq!(span.skip_ascii_whitespace(), span);
+}
+
+#[test]
+fn byte_spans_skip_ascii_whitespace() {
+    let span = Spanned::new(&b"\n \r\nab"[..], false);
+
+    let rest = span.skip_ascii_whitespace();
+
+    assert_eq!(*rest, b"ab");
+    assert_eq!(rest, span.slice(4..));
+    assert_eq!((rest.line(), rest.col()), (3, 1));
+}
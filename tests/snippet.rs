@@ -0,0 +1,103 @@
+#![cfg(not(feature = "color"))]
+
+use common::Slice;
+use nom::FindSubstring;
+use nom_span::{Position, Span, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// The padding before what comes after the emoji, that takes 2 cells in a terminal
+#[cfg(feature = "unicode-width")]
+const AFTER_EMOJI: &str = "           ";
+#[cfg(not(feature = "unicode-width"))]
+const AFTER_EMOJI: &str = "          ";
+
+#[test]
+fn carets_are_placed_after_emoji() {
+    let source = "let x = 🙌 + y;\nlet z = 1;";
+    let input = Input::new(source, true);
+    let plus = input.slice(source.find_substring("+").unwrap()..);
+
+    assert_eq!(
+        plus.render_snippet(source, "unexpected operator"),
+        format!(
+            "1:11: unexpected operator\n  |\n1 | let x = 🙌 + y;\n  | {}^\n",
+            AFTER_EMOJI
+        )
+    );
+}
+
+#[test]
+fn ranges_are_underlined_over_several_lines() {
+    let source = "a = (1,\n\tbé,\n  3)\nb = 2";
+    let input = Input::new(source, true);
+    let tuple = input.slice(4..18);
+
+    assert_eq!(
+        Span::of(&tuple).render_snippet(source, "unclosed tuple"),
+        "1:5: unclosed tuple\n  |\n1 | a = (1,\n  |     ^~~\n2 | \tbé,\n  | ~~~~\n3 |   3)\n  | ~~~~\n"
+    );
+}
+
+#[test]
+fn tabs_are_kept_before_the_caret() {
+    let source = "\tx = ?";
+    let question = Input::new(source, true).slice(5..);
+
+    assert_eq!(
+        question.pos().render_snippet(source, "expected a value"),
+        "1:6: expected a value\n  |\n1 | \tx = ?\n  | \t    ^\n"
+    );
+}
+
+#[test]
+fn carets_at_the_end_of_a_line() {
+    let source = "ab\r\ncd";
+    let input = Input::new(source, true);
+
+    assert_eq!(
+        input.slice(2..).render_snippet(source, "expected `;`"),
+        "1:3: expected `;`\n  |\n1 | ab\n  |   ^\n"
+    );
+    assert_eq!(
+        input
+            .slice(source.len()..)
+            .render_snippet(source, "unexpected end"),
+        "2:3: unexpected end\n  |\n2 | cd\n  |   ^\n"
+    );
+}
+
+#[test]
+fn the_gutter_fits_the_last_line_number() {
+    let source = "\n".repeat(9) + "ab\ncd";
+    let input = Input::new(&source, true);
+    let range = Span::between(&input.slice(10..), &input.slice(13..));
+
+    assert_eq!(
+        range.render_snippet(&source, "here"),
+        "10:2: here\n   |\n10 | ab\n   |  ^\n11 | cd\n   | ~\n"
+    );
+}
+
+#[test]
+fn offsets_are_clamped_to_a_char_boundary() {
+    let source = "a🙌b";
+    let cut = Spanned::new(source.as_bytes(), false).slice(2..);
+
+    assert_eq!(
+        cut.render_snippet(source, "here"),
+        "1:3: here\n  |\n1 | a🙌b\n  |  ^\n"
+    );
+    assert_eq!(
+        Position::new(1, 5, 4).render_snippet("ab", "unexpected end"),
+        "1:5: unexpected end\n  |\n1 | ab\n  |   ^\n"
+    );
+}
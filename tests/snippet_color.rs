@@ -0,0 +1,23 @@
+#![cfg(feature = "color")]
+
+use common::Slice;
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+#[test]
+fn carets_are_colored() {
+    let source = "a ?";
+    let question = Spanned::new(source, true).slice(2..);
+
+    assert_eq!(
+        question.render_snippet(source, "expected a value"),
+        "1:3: expected a value\n  |\n1 | a ?\n  |   \x1b[1;31m^\x1b[0m\n"
+    );
+}
@@ -0,0 +1,80 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, multispace0},
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+use nom_span::{FinishWithPosition, Position, SourceFile, SourceId, Span, Spanned};
+use std::{env, fs, process};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Input<'a> = Spanned<&'a str>;
+
+fn assignment(input: Input<'_>) -> IResult<Input<'_>, (Input<'_>, Input<'_>)> {
+    let (input, name) = preceded(tag("let "), alpha1).parse(input)?;
+    let (input, value) = delimited(tag(" = "), digit1, tag(";")).parse(input)?;
+    let (input, _) = multispace0(input)?;
+
+    Ok((input, (name, value)))
+}
+
+#[test]
+fn an_opened_file_is_named_after_its_path() {
+    let path = env::temp_dir().join(format!("nom-span-source-file-{}.txt", process::id()));
+    fs::write(&path, "let a = 1;\nlet b = x;\n").unwrap();
+
+    let file = SourceFile::open(&path).unwrap().with_source_id(SourceId(1));
+    fs::remove_file(&path).unwrap();
+
+    let (rest, (name, _)) = assignment(file.span()).unwrap();
+    assert_eq!(name.pos(), Position::new(1, 5, 4).with_source(SourceId(1)));
+
+    let failure = assignment(rest).finish_with_position().unwrap_err();
+    let position = failure.position().unwrap();
+    let rendered = file.render_snippet(Span::new(position, position), "expected a number");
+
+    assert!(rendered.starts_with(&format!("{}:2:9: expected a number\n", path.display())));
+    assert!(rendered.contains("2 | let b = x;\n"));
+    assert_eq!(file.name(), path.display().to_string());
+}
+
+#[test]
+fn opening_a_missing_file_fails() {
+    let path = env::temp_dir().join("nom-span-source-file-missing.txt");
+
+    assert!(SourceFile::open(path).is_err());
+}
+
+#[test]
+fn lines_are_looked_up_by_number() {
+    let file = SourceFile::new("main.txt", "let a = 1;\r\nlet b = 2;\n\nlet c".into());
+
+    assert_eq!(file.name(), "main.txt");
+    assert_eq!(file.contents(), "let a = 1;\r\nlet b = 2;\n\nlet c");
+    assert_eq!(file.source_id(), SourceId::default());
+    assert_eq!(file.line_count(), 4);
+    assert_eq!(file.line(0), None);
+    assert_eq!(file.line(1), Some("let a = 1;"));
+    assert_eq!(file.line(3), Some(""));
+    assert_eq!(file.line(4), Some("let c"));
+    assert_eq!(file.line(5), None);
+    assert_eq!(file.line_at(Position::new(2, 3, 14)), Some("let b = 2;"));
+}
+
+#[test]
+fn lines_are_the_ones_of_the_line_index() {
+    let file = SourceFile::new("main.txt", "let a;\r\nlet b;\n".into());
+    let index = file.line_index();
+
+    assert_eq!(file.line_count(), 3);
+    assert_eq!(file.line_count(), index.line_count());
+    assert_eq!(file.line(2), Some("let b;"));
+    assert_eq!(file.line(3), Some(""));
+    assert_eq!(file.line(4), None);
+    assert_eq!(file.line_at(index.position(12)), Some("let b;"));
+}
@@ -0,0 +1,68 @@
+use common::Slice;
+use nom::{bytes::complete::tag, character::complete::alpha1, IResult, Parser};
+use nom_span::{Position, SourceId, Span, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Input<'a> = Spanned<&'a str>;
+
+fn name(input: Input<'_>) -> IResult<Input<'_>, Input<'_>> {
+    let (rest, _) = tag("let ").parse(input)?;
+
+    alpha1(rest)
+}
+
+#[test]
+fn sources_are_copied_through_slicing() {
+    let input = Input::new("let a", true).with_source_id(SourceId(2));
+    let (rest, name) = name(input).unwrap();
+
+    assert_eq!(Input::new("", true).source_id(), SourceId::default());
+    assert_eq!(name.source_id(), SourceId(2));
+    assert_eq!(rest.slice(..).source_id(), SourceId(2));
+    assert_eq!(name.pos(), Position::new(1, 5, 4).with_source(SourceId(2)));
+    assert_eq!(name.end_position().source, SourceId(2));
+    assert_eq!(Span::of(&name).source(), SourceId(2));
+
+    let built = Spanned::builder("x").source_id(SourceId(7)).build();
+    assert_eq!(built.pos().source, SourceId(7));
+}
+
+#[test]
+fn spans_of_different_sources_are_different() {
+    let first = Input::new("let a", true).with_source_id(SourceId(1));
+    let second = Input::new("let a", true).with_source_id(SourceId(2));
+
+    assert_ne!(first, second);
+    assert_ne!(first.pos(), second.pos());
+    assert_eq!(first, Input::new("let a", true).with_source_id(SourceId(1)));
+
+    // Ordered by source first
+    assert!(second.pos() > first.slice(4..).pos());
+    assert!(Span::of(&first.slice(4..)) < Span::of(&second));
+
+    let span = Span::of(&first);
+    assert!(span.contains(first.slice(1..).pos()));
+    assert!(!span.contains(second.slice(1..).pos()));
+    assert!(span.intersects(&Span::of(&first.slice(2..))));
+    assert!(!span.intersects(&Span::of(&second.slice(2..))));
+}
+
+#[test]
+fn sources_are_shown_in_diagnostics() {
+    let input = Input::new("let a", true).with_source_id(SourceId(3));
+    let name = input.slice(4..);
+
+    assert_eq!(name.pos().to_string(), "#3:1:5");
+    assert_eq!(format!("{:#}", name.pos()), "line 1, column 5 in #3");
+    assert_eq!(Input::new("a", true).pos().to_string(), "1:1");
+    assert!(name
+        .render_snippet("let a", "unused variable")
+        .starts_with("#3:1:5: unused variable\n"));
+}
@@ -0,0 +1,92 @@
+use nom::{
+    bytes::complete::take,
+    character::complete::{alpha1, multispace0},
+    sequence::preceded,
+    IResult, Parser,
+};
+use nom_span::{Position, Span, Spanned};
+use std::collections::BTreeSet;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Input<'a> = Spanned<&'a str>;
+
+fn span(start: usize, end: usize) -> Span {
+    Span::new(
+        Position::new(1, start + 1, start),
+        Position::new(1, end + 1, end),
+    )
+}
+
+#[test]
+fn spans_are_made_from_spanned_values() {
+    let input = Input::new("\n é ab;", true);
+
+    let (rest, word): (_, Input<'_>) = preceded(multispace0::<_, ()>, take(2usize))
+        .parse(input)
+        .unwrap();
+
+    assert_eq!(
+        Span::between(&input, &rest),
+        Span::new(Position::new(1, 1, 0), Position::new(2, 4, 5))
+    );
+    assert_eq!(
+        Span::of(&word),
+        Span::new(Position::new(2, 2, 2), Position::new(2, 4, 5))
+    );
+    assert_eq!(Span::of(&word).len_bytes(), 3);
+}
+
+#[test]
+fn empty_fragments_give_empty_spans() {
+    let input = Input::new("ab", true);
+
+    let res: IResult<Input<'_>, Input<'_>> = multispace0(input);
+    let (rest, spaces) = res.unwrap();
+
+    assert_eq!(Span::of(&spaces), Span::between(&input, &rest));
+    assert_eq!(Span::of(&spaces).len_bytes(), 0);
+    assert!(!Span::of(&spaces).contains(input.pos()));
+
+    let res: IResult<Input<'_>, Input<'_>> = alpha1(input);
+    assert_eq!(Span::of(&res.unwrap().1), span(0, 2));
+}
+
+#[test]
+fn union_covers_both_spans() {
+    assert_eq!(span(4, 6).union(span(1, 2)), span(1, 6));
+    assert_eq!(span(1, 6).union(span(2, 3)), span(1, 6));
+}
+
+#[test]
+fn contains_excludes_the_end() {
+    let span = span(2, 4);
+
+    assert!(!span.contains(Position::new(1, 2, 1)));
+    assert!(span.contains(Position::new(1, 3, 2)));
+    assert!(span.contains(Position::new(1, 4, 3)));
+    assert!(!span.contains(Position::new(1, 5, 4)));
+}
+
+#[test]
+fn intersects_needs_a_byte_in_common() {
+    assert!(span(1, 3).intersects(&span(2, 5)));
+    assert!(span(2, 5).intersects(&span(1, 3)));
+    assert!(span(1, 5).intersects(&span(2, 3)));
+    assert!(!span(1, 3).intersects(&span(3, 5)));
+    assert!(!span(1, 3).intersects(&span(2, 2)));
+}
+
+#[test]
+fn spans_are_ordered_by_start_then_end() {
+    let spans: BTreeSet<_> = [span(2, 3), span(1, 4), span(1, 2), span(2, 3)].into();
+
+    assert_eq!(
+        spans.into_iter().collect::<Vec<_>>(),
+        [span(1, 2), span(1, 4), span(2, 3)]
+    );
+}
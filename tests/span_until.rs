@@ -0,0 +1,64 @@
+use common::tuple;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, multispace0},
+    combinator::recognize,
+    sequence::delimited,
+    IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn call(input: Span<'_>) -> IResult<Span<'_>, (Span<'_>, Span<'_>)> {
+    let (input, name) = alpha1(input)?;
+    let (input, arg) = delimited(
+        tag("("),
+        delimited(multispace0, digit1, multispace0),
+        tag(")"),
+    )
+    .parse(input)?;
+
+    Ok((input, (name, arg)))
+}
+
+#[test]
+fn span_until_gives_what_recognize_gives() {
+    let input = Span::new("é\nf(\n 1 );", true);
+    let (before, _) = tuple((tag("é"), multispace0::<_, ()>))
+        .parse(input)
+        .unwrap();
+
+    let (after, _) = call(before).unwrap();
+    let (recognized_rest, recognized) = recognize(call).parse(before).unwrap();
+
+    let consumed = before.span_until(&after);
+    assert_eq!(consumed, recognized);
+    assert_eq!(after, recognized_rest);
+    assert_eq!(*consumed, "f(\n 1 )");
+    assert_eq!(consumed.pos(), before.pos());
+    assert_eq!(consumed.end_position(), after.pos());
+}
+
+#[test]
+fn span_until_itself_is_empty() {
+    let span = Span::new("ab", true);
+
+    assert_eq!(*span.span_until(&span), "");
+    assert_eq!(span.span_until(&span).pos(), span.pos());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic]
+fn span_until_an_unrelated_span_panics() {
+    let _ = Span::new("ab", true).span_until(&Span::new("cd", true));
+}
@@ -0,0 +1,72 @@
+use common::Slice;
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn split_in_the_middle_of_a_line() {
+    let span = Span::new("a\nbéc\nd", true);
+
+    let (head, tail) = span.split_at(5);
+
+    assert_eq!((*head, *tail), ("a\nbé", "c\nd"));
+    assert_eq!(head.pos(), span.pos());
+    assert_eq!(tail, span.slice(5..));
+    assert_eq!((tail.line(), tail.col(), tail.byte_offset()), (2, 3, 5));
+}
+
+#[test]
+fn split_at_a_newline() {
+    let span = Span::new("ab\ncd", true);
+
+    let (head, tail) = span.split_at(2);
+    assert_eq!((*head, *tail), ("ab", "\ncd"));
+    assert_eq!((tail.line(), tail.col()), (1, 3));
+
+    let (head, tail) = span.split_at(3);
+    assert_eq!((*head, *tail), ("ab\n", "cd"));
+    assert_eq!((tail.line(), tail.col()), (2, 1));
+    assert_eq!(tail, span.slice(3..));
+}
+
+#[test]
+fn split_at_the_ends() {
+    let span = Span::new("ab\ncd", true).slice(1..);
+
+    let (head, tail) = span.split_at(0);
+    assert_eq!(*head, "");
+    assert_eq!(tail, span);
+
+    let (head, tail) = span.split_at(span.len());
+    assert_eq!(head, span);
+    assert_eq!(*tail, "");
+    assert_eq!(tail.pos(), span.end_position());
+}
+
+#[test]
+fn split_at_checked_refuses_bad_indices() {
+    let span = Span::new("é", true);
+
+    assert_eq!(span.split_at_checked(1), None);
+    assert_eq!(span.split_at_checked(3), None);
+    assert_eq!(span.split_at_checked(2), Some(span.split_at(2)));
+
+    let bytes = Spanned::new(&b"a\nb"[..], false);
+    let (_, tail) = bytes.split_at_checked(2).unwrap();
+    assert_eq!((tail.line(), tail.col()), (2, 1));
+    assert_eq!(bytes.split_at_checked(4), None);
+}
+
+#[test]
+#[should_panic]
+fn split_at_panics_in_a_char() {
+    let _ = Span::new("é", true).split_at(1);
+}
@@ -0,0 +1,94 @@
+use nom_span::{Position, Spanned};
+use std::thread;
+
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn input() -> String {
+    (0..200)
+        .map(|i| format!("{}é{}\n", i, "x".repeat(i % 7)))
+        .collect()
+}
+
+fn parse(span: Span<'_>) -> Vec<(Position, &str)> {
+    span.lines()
+        .map(|line| (line.pos(), line.into_data()))
+        .collect()
+}
+
+#[test]
+fn parallel_parses_are_the_sequential_one() {
+    let input = input();
+    let span = Span::new(&input, true);
+
+    for chunks in [1, 2, 3, 8, 17] {
+        let pieces = span.split_for_parallel(chunks);
+        assert_eq!(pieces.len(), chunks);
+
+        let results: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = pieces
+                .iter()
+                .map(|&piece| scope.spawn(move || parse(piece)))
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        assert_eq!(results, parse(span), "with {} chunks", chunks);
+    }
+}
+
+#[test]
+fn pieces_start_at_the_start_of_a_line() {
+    let input = input();
+    let span = Span::new(&input, true);
+
+    let pieces = span.split_for_parallel(5);
+
+    assert_eq!(pieces[0].pos(), span.pos());
+    for window in pieces.windows(2) {
+        assert!(window[0].ends_with('\n'));
+        assert_eq!(window[1].col(), 1);
+        assert_eq!(window[1].pos(), window[0].end_position());
+    }
+    assert_eq!(
+        pieces.iter().map(|piece| piece.len()).sum::<usize>(),
+        input.len()
+    );
+    assert_eq!(pieces.last().unwrap().end_position(), span.end_position());
+}
+
+#[test]
+fn long_lines_give_less_pieces() {
+    let span = Span::new("abcdefgh\nij", true);
+
+    let pieces: Vec<_> = span
+        .split_for_parallel(4)
+        .into_iter()
+        .map(Spanned::into_data)
+        .collect();
+    assert_eq!(pieces, ["abcdefgh\n", "ij"]);
+
+    assert_eq!(
+        Span::new("abc", true).split_for_parallel(3),
+        [Span::new("abc", true)]
+    );
+    assert_eq!(
+        Span::new("", true).split_for_parallel(0),
+        [Span::new("", true)]
+    );
+}
+
+#[test]
+fn byte_spans_are_split_too() {
+    let span = Spanned::new(&b"ab\ncd\nef\n"[..], false);
+
+    let pieces = span.split_for_parallel(3);
+
+    assert_eq!(pieces.len(), 3);
+    assert_eq!(*pieces[1], b"cd\n");
+    assert_eq!((pieces[2].line(), pieces[2].byte_offset()), (3, 6));
+}
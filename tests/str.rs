@@ -1,11 +1,14 @@
 use nom::{
     character::complete::{anychar, not_line_ending},
     multi::many1,
-    IResult,
+    IResult, Parser,
 };
 use nom_span::Spanned;
 
+#[cfg(feature = "nom7")]
 extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
 extern crate nom_span;
 
 type Span<'a> = Spanned<&'a str>;
@@ -15,8 +18,8 @@ fn utf8_vs_ascii() {
     let utf8 = Span::new("🙌", true);
     let ascii = Span::new("🙌", false);
 
-    let utf8_after: IResult<Span<'_>, Vec<char>> = many1(anychar)(utf8);
-    let ascii_after: IResult<Span<'_>, Vec<char>> = many1(anychar)(ascii);
+    let utf8_after: IResult<Span<'_>, Vec<char>> = many1(anychar).parse(utf8);
+    let ascii_after: IResult<Span<'_>, Vec<char>> = many1(anychar).parse(ascii);
 
     let (utf8_after, _) = utf8_after.unwrap();
     let (ascii_after, _) = ascii_after.unwrap();
@@ -30,7 +33,7 @@ fn utf8_vs_ascii() {
 // `not_line_ending` requires that `T: Compare<&'static str>`, and it wasn't working before
 #[test]
 fn can_compare_with_different_type() {
-    pub fn until_newline(input: Span) -> IResult<Span, &str> {
+    pub fn until_newline(input: Span<'_>) -> IResult<Span<'_>, &str> {
         let (rest, comment_str) = not_line_ending(input)?;
         Ok((rest, *comment_str))
     }
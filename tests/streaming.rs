@@ -0,0 +1,278 @@
+use nom::{
+    bytes::{complete, streaming},
+    character::{complete as char_complete, streaming as char_streaming},
+    error::{Error, ErrorKind},
+    number::streaming::be_u16,
+    AsChar, Err, IResult, Needed, Parser,
+};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+/// What a parser returned, without the positions
+#[derive(Debug, PartialEq)]
+enum Outcome<I> {
+    Done(I, I),
+    Incomplete(Needed),
+    Error(I, ErrorKind),
+    Failure(I, ErrorKind),
+}
+
+fn outcome<I, O, F>(res: IResult<I, I, Error<I>>, data: F) -> Outcome<O>
+where
+    F: Fn(I) -> O,
+{
+    match res {
+        Ok((rest, output)) => Outcome::Done(data(rest), data(output)),
+        Err(Err::Incomplete(needed)) => Outcome::Incomplete(needed),
+        Err(Err::Error(e)) => Outcome::Error(data(e.input), e.code),
+        Err(Err::Failure(e)) => Outcome::Failure(data(e.input), e.code),
+    }
+}
+
+/// Run the parser over `&str` and over `Spanned<&str>` and check they return the same outcome
+macro_rules! assert_same_on_str {
+    ($parser:expr, $($input:expr),+) => {
+        $(
+            let on_str = outcome($parser($input), |s: &str| s);
+            let on_span = outcome($parser(Span::new($input, true)), Span::into_data);
+
+            assert_eq!(on_str, on_span, "on {:?}", $input);
+        )+
+    };
+}
+
+/// Same as `assert_same_on_str` over `&[u8]` and `Spanned<&[u8]>`
+macro_rules! assert_same_on_bytes {
+    ($parser:expr, $($input:expr),+) => {
+        $(
+            let input: &[u8] = $input;
+            let on_bytes = outcome($parser(input), |s: &[u8]| s);
+            let on_span = outcome($parser(BytesSpan::new(input, false)), BytesSpan::into_data);
+
+            assert_eq!(on_bytes, on_span, "on {:?}", input);
+        )+
+    };
+}
+
+#[test]
+fn streaming_take_while_matches_str() {
+    assert_same_on_str!(
+        streaming::take_while(|c: char| c.is_alphabetic()),
+        "",
+        "abc",
+        "ab1",
+        "1ab"
+    );
+    assert_same_on_str!(
+        streaming::take_while1(|c: char| c.is_alphabetic()),
+        "",
+        "abc",
+        "ab1",
+        "1ab"
+    );
+    assert_same_on_str!(
+        streaming::take_till(|c: char| c == 'é'),
+        "",
+        "abc",
+        "abé",
+        "éab"
+    );
+    assert_same_on_str!(
+        streaming::take_till1(|c: char| c == 'é'),
+        "",
+        "abc",
+        "abé",
+        "éab"
+    );
+    assert_same_on_str!(streaming::is_not("é1"), "", "abc", "ab1", "1ab");
+    assert_same_on_str!(streaming::is_a("ab"), "", "abc", "ab", "cab");
+}
+
+#[test]
+fn complete_take_while_matches_str() {
+    assert_same_on_str!(
+        complete::take_while(|c: char| c.is_alphabetic()),
+        "",
+        "abc",
+        "ab1",
+        "1ab"
+    );
+    assert_same_on_str!(
+        complete::take_while1(|c: char| c.is_alphabetic()),
+        "",
+        "abc",
+        "ab1",
+        "1ab"
+    );
+    assert_same_on_str!(
+        complete::take_till(|c: char| c == 'é'),
+        "",
+        "abc",
+        "abé",
+        "éab"
+    );
+    assert_same_on_str!(
+        complete::take_till1(|c: char| c == 'é'),
+        "",
+        "abc",
+        "abé",
+        "éab"
+    );
+    assert_same_on_str!(complete::is_not("é1"), "", "abc", "ab1", "1ab");
+    assert_same_on_str!(complete::is_a("ab"), "", "abc", "ab", "cab");
+}
+
+#[test]
+fn character_parsers_match_str() {
+    assert_same_on_str!(char_streaming::alpha0, "", "abc", "ab1", "1ab");
+    assert_same_on_str!(char_streaming::alpha1, "", "abc", "ab1", "1ab");
+    assert_same_on_str!(char_streaming::multispace0, "", "  ", " \na", "a");
+    assert_same_on_str!(char_streaming::digit1, "", "12", "12a", "a12");
+    assert_same_on_str!(char_complete::alpha0, "", "abc", "ab1", "1ab");
+    assert_same_on_str!(char_complete::alpha1, "", "abc", "ab1", "1ab");
+    assert_same_on_str!(char_complete::multispace1, "", "  ", " \na", "a");
+    assert_same_on_str!(char_complete::digit1, "", "12", "12a", "a12");
+}
+
+#[test]
+fn byte_parsers_match_bytes() {
+    assert_same_on_bytes!(
+        streaming::take_while(|b: u8| b.is_alpha()),
+        b"",
+        b"abc",
+        b"ab1",
+        b"1ab"
+    );
+    assert_same_on_bytes!(
+        streaming::take_while1(|b: u8| b.is_alpha()),
+        b"",
+        b"abc",
+        b"ab1",
+        b"1ab"
+    );
+    assert_same_on_bytes!(
+        complete::take_while1(|b: u8| b.is_alpha()),
+        b"",
+        b"abc",
+        b"ab1",
+        b"1ab"
+    );
+    assert_same_on_bytes!(char_streaming::digit1, b"", b"12", b"12a", b"a12");
+    assert_same_on_bytes!(char_complete::digit1, b"", b"12", b"12a", b"a12");
+    assert_same_on_bytes!(char_streaming::space0, b"", b"  ", b" a", b"a");
+}
+
+#[test]
+fn needed_matches_bytes() {
+    assert_same_on_bytes!(streaming::tag("abc"), b"", b"a", b"ab", b"abc", b"abd");
+    assert_same_on_bytes!(streaming::take(4usize), b"", b"ab", b"abcd");
+    assert_same_on_bytes!(streaming::take_until("\r\n"), b"ab", b"ab\r", b"ab\r\n");
+
+    assert_eq!(
+        be_u16::<_, Error<_>>(&b"\x00"[..]),
+        Err(Err::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+        be_u16::<_, Error<_>>(BytesSpan::new(b"\x00", false))
+            .map_err(|e| e.map_input(BytesSpan::into_data)),
+        Err(Err::Incomplete(Needed::new(1)))
+    );
+}
+
+/// Key-value pairs ended by a newline, or a binary frame starting with `#` and its length
+fn message(input: BytesSpan<'_>) -> IResult<BytesSpan<'_>, BytesSpan<'_>> {
+    nom::branch::alt((
+        nom::sequence::preceded(
+            streaming::tag("#"),
+            nom::multi::length_data(nom::number::streaming::be_u8),
+        ),
+        nom::sequence::terminated(
+            nom::combinator::recognize(nom::sequence::separated_pair(
+                char_streaming::alpha1,
+                streaming::tag("="),
+                streaming::is_not("\n"),
+            )),
+            streaming::tag("\n"),
+        ),
+    ))
+    .parse(input)
+}
+
+const MESSAGES: &[u8] =
+    b"key=value\n#\x03a\nbk=\xc3\xa9t\xc3\xa9\n#\x00x=1\n#\x05\n\n\n\n\nlast=1\n";
+
+/// Parse `MESSAGES` fed in chunks of the given sizes, resuming over the longer buffer after every
+/// `Incomplete`
+fn parse_in_chunks(mut sizes: impl FnMut() -> usize) -> Vec<(Vec<u8>, Position)> {
+    let mut buffer = Vec::new();
+    let mut messages = Vec::new();
+    // The position to resume from, without any data
+    let mut resume = BytesSpan::new(b"", true).respan(());
+
+    loop {
+        let span = resume.respan(&buffer[resume.byte_offset()..]);
+
+        match message(span) {
+            Ok((rest, output)) => {
+                messages.push((output.to_vec(), output.pos()));
+                resume = rest.respan(());
+            }
+            Err(Err::Incomplete(_)) if buffer.len() < MESSAGES.len() => {
+                let end = (buffer.len() + sizes()).min(MESSAGES.len());
+                buffer.extend_from_slice(&MESSAGES[buffer.len()..end]);
+            }
+            Err(Err::Incomplete(_)) => return messages,
+            Err(e) => panic!("unexpected error {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn positions_after_a_retry_match_a_one_shot_parse() {
+    let one_shot = parse_in_chunks(|| MESSAGES.len());
+    assert_eq!(one_shot.len(), 7);
+    assert_eq!(one_shot[2].1, Position::new(3, 2, 15));
+    assert_eq!(one_shot[6].1, Position::new(10, 1, 36));
+
+    for size in 1..8 {
+        assert_eq!(parse_in_chunks(|| size), one_shot, "in chunks of {}", size);
+    }
+
+    // A xorshift, so the chunk sizes are random but the test is reproducible
+    let mut state: u32 = 0x1234_5678;
+    for _ in 0..100 {
+        let chunks = parse_in_chunks(|| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+
+            state as usize % 6
+        });
+
+        assert_eq!(chunks, one_shot);
+    }
+}
+
+#[test]
+fn incomplete_leaves_the_span_untouched() {
+    let span = BytesSpan::new(b"key=val", true);
+    let copy = span;
+
+    assert!(matches!(message(span), Err(Err::Incomplete(_))));
+    assert_eq!(span, copy);
+
+    // Resuming over a longer buffer gives the same span as parsing it from the start
+    let longer = span.respan(&b"key=value\n"[..]);
+    assert_eq!(
+        message(longer),
+        message(BytesSpan::new(b"key=value\n", true))
+    );
+}
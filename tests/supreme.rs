@@ -0,0 +1,111 @@
+#![cfg(feature = "supreme")]
+
+use nom::{
+    branch::alt,
+    character::complete::{alpha1, digit1, line_ending, space0},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+use nom_span::{locate_error_tree, Position, Spanned};
+use nom_supreme::{
+    error::{BaseErrorKind, ErrorTree, Expectation, GenericErrorTree},
+    final_parser::{final_parser, Location},
+    tag::complete::tag,
+    ParserExt,
+};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+extern crate nom_supreme;
+
+type Span<'a> = Spanned<&'a str>;
+type Error<'a> = ErrorTree<Span<'a>>;
+
+/// `key = value` lines, the value being a number or `true`
+fn entries(input: Span<'_>) -> IResult<Span<'_>, Vec<(Span<'_>, Span<'_>)>, Error<'_>> {
+    separated_list1(
+        line_ending,
+        separated_pair(
+            alpha1.context("key"),
+            delimited(space0, tag("="), space0),
+            alt((digit1, tag("true"))).context("value").cut(),
+        )
+        .context("entry"),
+    )(input)
+}
+
+fn failure(input: &str) -> Error<'_> {
+    match entries(Span::new(input, true)) {
+        Err(nom::Err::Failure(tree)) => tree,
+        other => panic!("expected a failure, got {:?}", other),
+    }
+}
+
+#[test]
+fn rendered_trees_have_the_position_of_every_expectation() {
+    let rendered = locate_error_tree(failure("a = 1\nbc =\tfalse")).to_string();
+
+    assert!(
+        rendered.contains("in section \"entry\" at line 2, column 1"),
+        "{}",
+        rendered
+    );
+    assert!(
+        rendered.contains("in section \"value\" at line 2, column 6"),
+        "{}",
+        rendered
+    );
+    assert!(
+        rendered.contains("expected an ascii digit at line 2, column 6"),
+        "{}",
+        rendered
+    );
+    assert!(
+        rendered.contains("expected \"true\" at line 2, column 6"),
+        "{}",
+        rendered
+    );
+}
+
+#[test]
+fn tag_errors_keep_the_expected_tag() {
+    let tree = failure("a = truth");
+
+    let GenericErrorTree::Stack { base, contexts } = locate_error_tree(tree) else {
+        panic!("expected a stack of contexts");
+    };
+    assert_eq!(contexts.last().unwrap().0, Position::new(1, 1, 0));
+
+    let GenericErrorTree::Alt(siblings) = *base else {
+        panic!("expected alternatives");
+    };
+    assert!(siblings.iter().any(|sibling| matches!(
+        sibling,
+        GenericErrorTree::Base {
+            location,
+            kind: BaseErrorKind::Expected(Expectation::Tag("true")),
+        } if *location == Position::new(1, 5, 4)
+    )));
+}
+
+#[test]
+fn final_parsers_locate_the_error_without_recounting() {
+    let input = "a = 1\nb = true\ncd = ?";
+
+    let error: ErrorTree<Position> = final_parser(entries)(Span::new(input, true)).unwrap_err();
+    let rendered = error.to_string();
+    assert!(
+        rendered.contains("expected an ascii digit at line 3, column 6"),
+        "{}",
+        rendered
+    );
+
+    let error: ErrorTree<Location> = final_parser(entries)(Span::new(input, true)).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("expected an ascii digit at line 3, column 6"));
+}
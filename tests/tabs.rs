@@ -0,0 +1,79 @@
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn with_tabs(input: &str, tab_width: u8, handle_utf8: bool) -> Span<'_> {
+    Spanned::builder(input)
+        .utf8(handle_utf8)
+        .tab_width(tab_width)
+        .build()
+}
+
+#[test]
+fn tabs_count_as_one_column_by_default() {
+    let span = Span::new("\tx", true);
+
+    assert_eq!(span.slice(1..).col(), 2);
+}
+
+#[test]
+fn tab_at_the_start_of_a_line() {
+    let span = with_tabs("\tx\n\ty", 4, true);
+
+    assert_eq!(span.slice(1..).col(), 5);
+    assert_eq!(span.slice(2..).col(), 6);
+    assert_eq!(span.slice(4..).col(), 5);
+    assert_eq!(span.slice(4..).line(), 2);
+}
+
+#[test]
+fn consecutive_tabs() {
+    let span = with_tabs("ab\t\t\tc", 4, true);
+
+    assert_eq!(span.slice(3..).col(), 5);
+    assert_eq!(span.slice(4..).col(), 9);
+    assert_eq!(span.slice(5..).col(), 13);
+    assert_eq!(span.slice(6..).col(), 14);
+}
+
+#[test]
+fn consecutive_tabs_consumed_one_by_one() {
+    let span = with_tabs("ab\t\t\tc", 8, true);
+
+    let mut rest = span;
+    for _ in 0..5 {
+        rest = take::<_, _, ()>(1usize).parse(rest).unwrap().0;
+    }
+
+    assert_eq!(rest.col(), span.slice(5..).col());
+    assert_eq!(rest.col(), 25);
+}
+
+#[test]
+fn tabs_after_multibyte_chars() {
+    let utf8 = with_tabs("é🙌\tx", 4, true);
+    let ascii = with_tabs("é🙌\tx", 4, false);
+
+    assert_eq!(utf8.slice(6..).col(), 3);
+    assert_eq!(utf8.slice(7..).col(), 5);
+    assert_eq!(ascii.slice(6..).col(), 7);
+    assert_eq!(ascii.slice(7..).col(), 9);
+}
+
+#[test]
+fn tab_width_is_kept_when_switching_the_encoding() {
+    let span = with_tabs("é\tx", 4, false).with_utf8(true);
+
+    assert_eq!(span.slice(3..).col(), 5);
+    assert_eq!(span.slice(3..).char_offset(), 2);
+}
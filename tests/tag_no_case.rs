@@ -0,0 +1,52 @@
+use nom::{bytes::complete::tag_no_case, IResult, Parser};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+#[test]
+fn ascii_letters_are_matched_ignoring_the_case() {
+    let res: IResult<_, _> = tag_no_case("select").parse(Span::new("SeLeCt *", true));
+    let (rest, keyword) = res.unwrap();
+
+    assert_eq!(*keyword, "SeLeCt");
+    assert_eq!(rest.col(), 7);
+
+    let res: IResult<_, _> = tag_no_case("select").parse(BytesSpan::new(b"SELECT *", false));
+    let (rest, keyword) = res.unwrap();
+
+    assert_eq!(*keyword, b"SELECT");
+    assert_eq!(rest.col(), 7);
+}
+
+#[test]
+fn str_spans_lowercase_the_chars_one_by_one() {
+    let res: IResult<_, _> = tag_no_case("été").parse(Span::new("ÉTÉ!", true));
+    let (rest, keyword) = res.unwrap();
+
+    assert_eq!(*keyword, "ÉTÉ");
+    assert_eq!(rest.col(), 4);
+    assert_eq!(rest.byte_offset(), 5);
+
+    // The input is cut at the length of the tag, so the case folding can't change it
+    let res: IResult<_, _> = tag_no_case("strasse").parse(Span::new("STRAßE", true));
+    assert!(res.is_err());
+}
+
+#[test]
+fn byte_spans_only_ignore_the_ascii_case() {
+    let res: IResult<_, _> = tag_no_case("été").parse(BytesSpan::new("ÉTÉ".as_bytes(), true));
+    assert!(res.is_err());
+
+    // The non-ASCII letters still match when they have the same case
+    let res: IResult<_, _> = tag_no_case("été").parse(BytesSpan::new("éTé!".as_bytes(), true));
+    let (rest, _) = res.unwrap();
+    assert_eq!(rest.col(), 4);
+    assert_eq!(rest.byte_offset(), 5);
+}
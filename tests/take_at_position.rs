@@ -0,0 +1,83 @@
+use nom::{
+    bytes::streaming::take_while1,
+    error::{Error, ErrorKind},
+    multi::many1,
+    AsChar, Err, IResult, Parser,
+};
+use nom_span::Spanned;
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+fn word(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    take_while1(|c: char| c.is_alphabetic()).parse(input)
+}
+
+fn bytes_word(input: BytesSpan<'_>) -> IResult<BytesSpan<'_>, BytesSpan<'_>> {
+    take_while1(|b: u8| b.is_alpha()).parse(input)
+}
+
+#[test]
+fn take_while1_fails_on_an_empty_match() {
+    let span = Span::new("1ab", true);
+
+    match word(span) {
+        Err(Err::Error(Error { input, code })) => {
+            assert_eq!(code, ErrorKind::TakeWhile1);
+            assert_eq!(input.byte_offset(), 0);
+        }
+        res => panic!("expected an error, got {:?}", res),
+    }
+}
+
+#[test]
+fn take_while1_fails_on_an_empty_match_over_bytes() {
+    let span = BytesSpan::new(b"1ab", false);
+
+    let res = bytes_word(span);
+
+    assert!(matches!(
+        res,
+        Err(Err::Error(Error {
+            code: ErrorKind::TakeWhile1,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn many1_stops_on_an_empty_match() {
+    let span = Span::new("ab1", true);
+
+    let (rest, words) = many1(word).parse(span).unwrap();
+
+    assert_eq!(words.len(), 1);
+    assert_eq!(*words[0], "ab");
+    assert_eq!(rest.col(), 3);
+}
+
+#[test]
+fn many1_stops_on_an_empty_match_over_bytes() {
+    let span = BytesSpan::new(b"12a", false);
+
+    let (rest, numbers) = many1(take_while1::<_, _, Error<_>>(|b: u8| b.is_dec_digit()))
+        .parse(span)
+        .unwrap();
+
+    assert_eq!(numbers.len(), 1);
+    assert_eq!(*numbers[0], b"12");
+    assert_eq!(rest.col(), 3);
+}
+
+#[test]
+fn take_while1_is_incomplete_without_a_match() {
+    let span = Span::new("ab", true);
+
+    assert!(matches!(word(span), Err(Err::Incomplete(_))));
+}
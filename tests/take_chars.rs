@@ -0,0 +1,83 @@
+use common::Slice;
+use nom::{
+    error::{Error, ErrorKind},
+    multi::count,
+    Err, IResult, Needed, Parser,
+};
+use nom_span::{take_chars, take_chars_streaming, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+type Bytes<'a> = Spanned<&'a [u8]>;
+
+#[test]
+fn fixed_width_fields_count_chars() {
+    let res: IResult<Span<'_>, Vec<Span<'_>>> =
+        count(take_chars(3), 3).parse(Span::new("aébçdé🙌xyz", true));
+    let (rest, fields) = res.unwrap();
+
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|field| (*field.data(), field.col()))
+        .collect();
+    assert_eq!(fields, [("aéb", 1), ("çdé", 4), ("🙌xy", 7)]);
+    assert_eq!(*rest, "z");
+    assert_eq!(rest.col(), 10);
+}
+
+#[test]
+fn byte_spans_are_decoded_when_utf8_is_handled() {
+    let input = "é🙌a".as_bytes();
+
+    let res: IResult<Bytes<'_>, Bytes<'_>> = take_chars(2).parse(Bytes::new(input, true));
+    let (rest, field) = res.unwrap();
+    assert_eq!(*field, "é🙌".as_bytes());
+    assert_eq!(rest, Bytes::new(input, true).slice(6..));
+    assert_eq!(rest.col(), 3);
+
+    let res: IResult<Bytes<'_>, Bytes<'_>> = take_chars(2).parse(Bytes::new(input, false));
+    assert_eq!(*res.unwrap().1, &input[..2]);
+}
+
+#[test]
+fn str_spans_are_not_split_inside_a_char_when_utf8_is_not_handled() {
+    let res: IResult<Span<'_>, Span<'_>> = take_chars(1).parse(Span::new("éa", false));
+    let (rest, field) = res.unwrap();
+
+    assert_eq!(*field, "é");
+    assert_eq!(*rest, "a");
+    assert_eq!(rest.col(), 3);
+}
+
+#[test]
+fn invalid_utf8_counts_as_one_char() {
+    let input = b"a\xF0\x9F\x99b\xFFc";
+
+    let res: IResult<Bytes<'_>, Bytes<'_>> = take_chars(4).parse(Bytes::new(input, true));
+    let (rest, field) = res.unwrap();
+
+    assert_eq!(*field, &input[..6]);
+    assert_eq!(*rest, b"c");
+    assert_eq!(rest.col(), 5);
+}
+
+#[test]
+fn taking_more_chars_than_left_fails() {
+    let span = Span::new("aé", true);
+
+    let res: IResult<Span<'_>, Span<'_>> = take_chars(3).parse(span);
+    assert_eq!(res, Err(Err::Error(Error::new(span, ErrorKind::Eof))));
+
+    let res: IResult<Span<'_>, Span<'_>> = take_chars_streaming(3).parse(span);
+    assert_eq!(res, Err(Err::Incomplete(Needed::Unknown)));
+
+    let res: IResult<Span<'_>, Span<'_>> = take_chars_streaming(2).parse(span);
+    assert_eq!(*res.unwrap().0, "");
+}
@@ -0,0 +1,82 @@
+use common::Slice;
+use nom_span::{LineEnding, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+#[test]
+fn lines_are_taken_like_slices() {
+    let span = Span::new("é a\nb", true);
+
+    let (rest, line, ending) = span.take_line();
+
+    assert_eq!((*line, *rest, ending), ("é a", "b", LineEnding::Lf));
+    assert_eq!(line, span.slice(..4));
+    assert_eq!(rest, span.slice(5..));
+    assert_eq!(rest.pos(), Position::new(2, 1, 5));
+}
+
+#[test]
+fn crlf_files_give_the_lines_without_cr() {
+    let mut span = Span::new("a\r\n\r\nbc\r\n", true);
+    let mut lines = Vec::new();
+
+    while !span.is_empty() {
+        let (rest, line, ending) = span.take_line();
+        lines.push((*line.data(), line.pos(), ending));
+        span = rest;
+    }
+
+    assert_eq!(
+        lines,
+        [
+            ("a", Position::new(1, 1, 0), LineEnding::CrLf),
+            ("", Position::new(2, 1, 3), LineEnding::CrLf),
+            ("bc", Position::new(3, 1, 5), LineEnding::CrLf),
+        ]
+    );
+    assert_eq!(span.pos(), Position::new(4, 1, 9));
+}
+
+#[test]
+fn the_last_line_can_have_no_terminator() {
+    let span = Span::new("a\nbé", true).slice(2..);
+
+    let (rest, line, ending) = span.take_line();
+
+    assert_eq!(ending, LineEnding::Eof);
+    assert_eq!(line, span);
+    assert_eq!(*rest, "");
+    assert_eq!(rest, span.slice(span.len()..));
+
+    let (rest, line, ending) = rest.take_line();
+    assert_eq!((*rest, *line, ending), ("", "", LineEnding::Eof));
+}
+
+#[test]
+fn a_lone_cr_is_part_of_the_line() {
+    let (_, line, ending) = Span::new("a\rb\n", true).take_line();
+
+    assert_eq!((*line, ending), ("a\rb", LineEnding::Lf));
+}
+
+#[test]
+fn byte_spans_have_lines_too() {
+    let span = Spanned::new(&b"ab\r\ncd"[..], false);
+
+    let (rest, line, ending) = span.take_line();
+
+    assert_eq!(
+        (*line, *rest, ending),
+        (&b"ab"[..], &b"cd"[..], LineEnding::CrLf)
+    );
+    assert_eq!(rest, span.slice(4..));
+    assert_eq!(ending.as_str(), "\r\n");
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "nom7")]
+// nom 8 only takes `&[u8]` and `&str` as inputs, so a span over tokens needs nom 7
+
+use common::{tuple, InputTake, Slice};
+use nom::{
+    error::{Error, ErrorKind, ParseError},
+    multi::{many0, separated_list1},
+    sequence::delimited,
+    Err, IResult, Parser,
+};
+use nom_span::{ElementTracker, Spanned, TokenPosition, TokenTracker};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Ident(String),
+    Number(u32),
+    Punct(char),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Token {
+    kind: Kind,
+    line: usize,
+    col: usize,
+}
+
+impl TokenPosition for Token {
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn col(&self) -> usize {
+        self.col
+    }
+}
+
+type Tokens<'a> = Spanned<&'a [Token], (), TokenTracker>;
+
+/// Split the source on whitespace, every char that isn't alphanumeric being a punctuation
+fn lex(source: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let mut chars = line.chars().enumerate().peekable();
+
+        while let Some((col, c)) = chars.next() {
+            let mut word = c.to_string();
+            if c.is_alphanumeric() {
+                while let Some((_, next)) = chars.peek().filter(|(_, c)| c.is_alphanumeric()) {
+                    word.push(*next);
+                    chars.next();
+                }
+            }
+
+            let kind = match word.parse() {
+                Ok(n) => Kind::Number(n),
+                Err(_) if c.is_alphabetic() => Kind::Ident(word),
+                Err(_) if c.is_whitespace() => continue,
+                Err(_) => Kind::Punct(c),
+            };
+
+            tokens.push(Token {
+                kind,
+                line: i + 1,
+                col: col + 1,
+            });
+        }
+    }
+
+    tokens
+}
+
+fn token<'a, F>(
+    expected: F,
+) -> impl Fn(Tokens<'a>) -> IResult<Tokens<'a>, &'a Token, Error<Tokens<'a>>>
+where
+    F: Fn(&Kind) -> bool,
+{
+    move |input: Tokens<'a>| match input.data().first() {
+        Some(token) if expected(&token.kind) => Ok((input.slice(1..), token)),
+        _ => Err(Err::Error(Error::from_error_kind(input, ErrorKind::Tag))),
+    }
+}
+
+fn punct<'a>(c: char) -> impl Fn(Tokens<'a>) -> IResult<Tokens<'a>, &'a Token, Error<Tokens<'a>>> {
+    token(move |kind| *kind == Kind::Punct(c))
+}
+
+/// `ident = number, number, ...;`
+fn assignment(input: Tokens<'_>) -> IResult<Tokens<'_>, (&Token, Vec<&Token>), Error<Tokens<'_>>> {
+    let (input, (name, _, values)) = tuple((
+        token(|kind| matches!(kind, Kind::Ident(_))),
+        punct('='),
+        separated_list1(punct(','), token(|kind| matches!(kind, Kind::Number(_)))),
+    ))
+    .parse(input)?;
+    let (input, _) = punct(';').parse(input)?;
+
+    Ok((input, (name, values)))
+}
+
+const SOURCE: &str = "a = 1, 2;\n  bc =\n    3;\nd = 4 5;";
+
+#[test]
+fn spans_start_at_the_first_token() {
+    let tokens = lex("\n\n   x");
+    let span = Spanned::from_tokens(&tokens[..]);
+
+    assert_eq!(span.line(), 3);
+    assert_eq!(span.col(), 4);
+    assert_eq!(span.byte_offset(), 0);
+
+    let empty = Spanned::from_tokens(&[] as &[Token]);
+    assert_eq!((empty.line(), empty.col()), (1, 1));
+}
+
+#[test]
+fn slicing_moves_to_the_next_token() {
+    let tokens = lex(SOURCE);
+    let span = Spanned::from_tokens(&tokens[..]);
+
+    let (rest, (name, values)) = assignment(span).unwrap();
+
+    assert_eq!(name.kind, Kind::Ident("a".into()));
+    assert_eq!(values.len(), 2);
+    assert_eq!((rest.line(), rest.col()), (2, 3));
+    assert_eq!(rest.byte_offset(), 6);
+
+    let (rest, (_, values)) = assignment(rest).unwrap();
+    assert_eq!(values[0].line, 3);
+    assert_eq!((rest.line(), rest.col()), (4, 1));
+}
+
+#[test]
+fn errors_are_located_in_the_source() {
+    let tokens = lex(SOURCE);
+    let span = Spanned::from_tokens(&tokens[..]);
+
+    let (rest, assignments) = many0(assignment).parse(span).unwrap();
+    assert_eq!(assignments.len(), 2);
+
+    // A `,` or a `;` was expected instead of the `5`
+    let Err(Err::Error(Error { input, code })) = assignment(rest) else {
+        panic!("expected an error");
+    };
+    assert_eq!(code, ErrorKind::Tag);
+    assert_eq!((input.line(), input.col()), (4, 7));
+
+    let Err(Err::Error(Error { input, .. })) =
+        delimited(punct('{'), assignment, punct('}')).parse(rest)
+    else {
+        panic!("expected an error");
+    };
+    assert_eq!((input.line(), input.col()), (4, 1));
+}
+
+#[test]
+fn the_position_stays_on_the_last_token() {
+    let tokens = lex("a\n  b");
+    let span = Spanned::from_tokens(&tokens[..]);
+
+    let end = span.take_split(2).0;
+
+    assert!(end.is_empty());
+    assert_eq!((end.line(), end.col()), (2, 3));
+    assert_eq!(end.byte_offset(), 2);
+}
+
+#[test]
+fn tokens_without_position_count_elements() {
+    let kinds = [Kind::Number(1), Kind::Punct('+'), Kind::Number(2)];
+    let span = Spanned::with_tracker(&kinds[..], ElementTracker);
+
+    let rest = span.slice(2..);
+
+    assert_eq!(rest.line(), 1);
+    assert_eq!(rest.col(), 3);
+    assert_eq!(rest.byte_offset(), 2);
+}
+
+#[test]
+fn tokens_counted_from_0_stay_at_0() {
+    let tokens = [Token {
+        kind: Kind::Punct(';'),
+        line: 0,
+        col: 0,
+    }];
+    let span = Spanned::from_tokens(&tokens[..]);
+
+    assert_eq!((span.line0(), span.col0()), (0, 0));
+    assert_eq!((span.pos().line0(), span.pos().col0()), (0, 0));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "tracable")]
+// `tracable_parser` only traces when the crate using it has a `trace` feature
+#![allow(unexpected_cfgs)]
+// The errors hold the span and so its `TracableInfo`
+#![allow(clippy::result_large_err)]
+
+use nom::{
+    character::complete::{char, digit1},
+    sequence::separated_pair,
+    IResult,
+};
+use nom_span::{Position, Spanned};
+use nom_tracable::{
+    backward_trace, forward_trace, tracable_parser, HasTracableInfo, Tracable, TracableInfo,
+};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+extern crate nom_tracable;
+
+type Span<'a> = Spanned<&'a str, TracableInfo>;
+
+#[tracable_parser]
+fn number(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+    digit1(input)
+}
+
+#[tracable_parser]
+fn pair(input: Span<'_>) -> IResult<Span<'_>, (Span<'_>, Span<'_>)> {
+    separated_pair(number, char(','), number)(input)
+}
+
+/// What `tracable_parser` expands to with the `trace` feature
+fn traced_pair(input: Span<'_>) -> IResult<Span<'_>, (Span<'_>, Span<'_>)> {
+    let (info, input) = forward_trace(input, "pair");
+    let result = pair(input);
+
+    backward_trace(result, "pair", info)
+}
+
+#[test]
+fn annotated_parsers_keep_the_position() {
+    let info = TracableInfo::new().forward(true).backward(true);
+    let (rest, (left, right)) = pair(Spanned::new_extra("12,3é", true, info)).unwrap();
+
+    assert_eq!(*left, "12");
+    assert_eq!(right.pos(), Position::new(1, 4, 3));
+    assert_eq!(*rest, "é");
+}
+
+#[test]
+fn traces_show_the_depth_and_the_position() {
+    let info = TracableInfo::new().forward(true).backward(true);
+    let span = Spanned::new_extra("1,2;", true, info);
+
+    let deeper = span.inc_depth();
+    assert_eq!(deeper.get_tracable_info().depth, 1);
+    assert_eq!(deeper.pos(), span.pos());
+    assert_eq!(deeper.dec_depth().get_tracable_info().depth, 0);
+
+    let (rest, _) = traced_pair(span).unwrap();
+    assert_eq!(rest.get_tracable_info().depth, 0);
+    assert_eq!(
+        rest.format().split_whitespace().collect::<Vec<_>>(),
+        ["1:4", ":", ";"]
+    );
+    assert!(rest.header().starts_with("line:col"));
+}
@@ -0,0 +1,125 @@
+use common::{InputTake, Slice};
+use nom::{
+    bytes::complete::{tag, take},
+    multi::many0,
+    number::complete::be_u16,
+    IResult, Parser,
+};
+use nom_span::{CounterStorage, Counters, PositionTracker, RecordTracker, Spanned, Utf8};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Records<'a> = Spanned<&'a [u8], (), RecordTracker>;
+
+const RECORDS: &[u8] = b"\x00\x03abc\x00\x01d\x00\x00\x00\x02ef";
+
+fn record(input: Records<'_>) -> IResult<Records<'_>, Records<'_>> {
+    nom::multi::length_data(be_u16).parse(input)
+}
+
+#[test]
+fn lines_are_records() {
+    let span = Spanned::with_tracker(RECORDS, RecordTracker::new(2));
+
+    let (after, records) = many0(record).parse(span).unwrap();
+
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[1].line(), 2);
+    assert_eq!(records[1].col(), 3);
+    assert_eq!(*records[1].data(), b"d");
+    assert_eq!(after.line(), 5);
+    assert_eq!(after.col(), 1);
+    assert_eq!(after.byte_offset(), RECORDS.len());
+}
+
+#[test]
+fn columns_are_bytes_in_the_record() {
+    let span = Spanned::with_tracker(RECORDS, RecordTracker::new(2));
+
+    let (after, _) = take::<_, _, ()>(3usize).parse(span).unwrap();
+
+    assert_eq!(after.line(), 1);
+    assert_eq!(after.col(), 4);
+    assert_eq!(after.char_offset(), 3);
+}
+
+#[test]
+fn length_prefix_can_be_consumed_in_pieces() {
+    let span = Spanned::with_tracker(RECORDS, RecordTracker::new(2));
+
+    let span = span.take_split(1).0;
+    assert_eq!(span.col(), 2);
+
+    let span = span.take_split(1).0;
+    assert_eq!(span.col(), 3);
+
+    let span = span.take_split(3).0;
+    assert_eq!(span.line(), 2);
+    assert_eq!(span.col(), 1);
+}
+
+#[test]
+fn empty_records_are_counted() {
+    let span = Spanned::with_tracker(RECORDS, RecordTracker::new(2));
+
+    let after_empty = span.slice(10..);
+
+    assert_eq!(after_empty.line(), 4);
+    assert_eq!(after_empty.col(), 1);
+    assert_eq!(after_empty.end_position().line, 5);
+}
+
+#[test]
+fn default_trackers_count_lines() {
+    let input = "ab\ncd";
+
+    let tracked = Spanned::with_tracker(input, Utf8);
+    let utf8 = Spanned::utf8(input);
+
+    assert_eq!(tracked.slice(4..).pos(), utf8.slice(4..).pos());
+    assert_eq!(tracked.slice(4..).pos().line, 2);
+}
+
+/// Counts the statements ended by a `;`, ignoring the newlines
+#[derive(Clone, Debug, Copy)]
+struct Statements;
+
+impl CounterStorage for Statements {}
+
+impl PositionTracker for Statements {
+    fn advance(&mut self, counters: &mut Counters, consumed: &[u8], _rest: &[u8]) {
+        for &byte in consumed {
+            counters.char_offset += 1;
+
+            if byte == b';' {
+                counters.line += 1;
+                counters.col = 1;
+            } else {
+                counters.col += 1;
+            }
+        }
+    }
+}
+
+#[test]
+fn trackers_can_be_implemented_outside_of_the_crate() {
+    type Span<'a> = Spanned<&'a str, (), Statements>;
+
+    fn statement(input: Span<'_>) -> IResult<Span<'_>, Span<'_>> {
+        tag("let a = 1;\n").parse(input)
+    }
+
+    let span = Spanned::with_tracker("let a = 1;\nlet b", Statements);
+
+    let (after, _) = statement(span).unwrap();
+
+    assert_eq!(after.line(), 2);
+    assert_eq!(after.col(), 2);
+    assert_eq!(after.byte_offset(), 11);
+}
@@ -0,0 +1,61 @@
+use common::Slice;
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+#[test]
+fn positions_are_the_ones_of_slice() {
+    let span = Span::new("ab\ncéd", true);
+
+    assert_eq!(span.try_slice(3..), Some(span.slice(3..)));
+    assert_eq!(span.try_slice(4..6), Some(span.slice(4..6)));
+    assert_eq!(span.try_slice(..2), Some(span.slice(..2)));
+    assert_eq!(span.try_slice(..), Some(span));
+
+    let sliced = span.try_slice(6..=6).unwrap();
+    assert_eq!(*sliced, "d");
+    assert_eq!(sliced.pos(), Position::new(2, 3, 6));
+
+    let bytes = BytesSpan::new(b"ab\ncd", false);
+    assert_eq!(bytes.try_slice(3..4), Some(bytes.slice(3..4)));
+    assert_eq!(bytes.try_slice(5..), Some(bytes.slice(5..)));
+}
+
+#[test]
+fn out_of_range_is_none() {
+    let span = Span::new("abc", true);
+
+    assert_eq!(span.try_slice(4..), None);
+    assert_eq!(span.try_slice(..4), None);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = 2..1;
+    assert_eq!(span.try_slice(reversed), None);
+    assert_eq!(span.try_slice(..=3), None);
+
+    let bytes = BytesSpan::new(b"abc", false);
+    assert_eq!(bytes.try_slice(1..5), None);
+    assert_eq!(bytes.try_slice(..=usize::MAX), None);
+}
+
+#[test]
+fn ranges_inside_a_char_are_none() {
+    let span = Span::new("aé🙌", true);
+
+    assert_eq!(span.try_slice(2..), None);
+    assert_eq!(span.try_slice(..2), None);
+    assert_eq!(span.try_slice(3..5), None);
+    assert_eq!(span.try_slice(3..7), Some(span.slice(3..7)));
+
+    // Byte data has no char boundaries
+    let bytes = BytesSpan::new("aé".as_bytes(), true);
+    assert_eq!(bytes.try_slice(2..), Some(bytes.slice(2..)));
+}
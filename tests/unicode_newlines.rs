@@ -0,0 +1,101 @@
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::{Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn with_unicode_newlines(input: &str, handle_utf8: bool) -> Span<'_> {
+    Spanned::builder(input)
+        .utf8(handle_utf8)
+        .unicode_newlines(true)
+        .build()
+}
+
+#[test]
+fn unicode_newlines_are_columns_by_default() {
+    let span = Span::new("a\u{2028}b\u{0C}c", true);
+
+    assert_eq!(span.slice(5..).pos(), Position::new(1, 4, 5));
+    assert_eq!(span.slice(6..).pos(), Position::new(1, 5, 6));
+}
+
+#[test]
+fn columns_reset_after_each_terminator_kind() {
+    let span = with_unicode_newlines("ab\u{2028}cd\u{2029}e\u{0C}fé\ng", true);
+
+    assert_eq!(span.slice(5..).pos(), Position::new(2, 1, 5));
+    assert_eq!(span.slice(6..).pos(), Position::new(2, 2, 6));
+    assert_eq!(span.slice(10..).pos(), Position::new(3, 1, 10));
+    assert_eq!(span.slice(12..).pos(), Position::new(4, 1, 12));
+    assert_eq!(span.slice(15..).pos(), Position::new(4, 3, 15));
+    assert_eq!(span.slice(16..).pos(), Position::new(5, 1, 16));
+}
+
+#[test]
+fn other_chars_starting_like_separators_are_columns() {
+    // `€` is encoded as E2 82 AC and `…` as E2 80 A6
+    let span = with_unicode_newlines("€…x", true);
+
+    assert_eq!(span.slice(6..).pos(), Position::new(1, 3, 6));
+}
+
+#[test]
+fn consuming_char_by_char_matches_consuming_at_once() {
+    let span = with_unicode_newlines("a\u{2028}\u{2029}b\u{0C}\nc", true);
+
+    let mut rest = span;
+    while !rest.is_empty() {
+        rest = take::<_, _, ()>(1usize).parse(rest).unwrap().0;
+
+        assert_eq!(rest.pos(), span.slice(rest.byte_offset()..).pos());
+    }
+
+    assert_eq!(rest.pos(), Position::new(5, 2, 11));
+}
+
+#[test]
+fn unicode_newlines_with_crlf() {
+    let span = Spanned::builder("a\r\nb\u{2028}c\rd")
+        .crlf(true)
+        .unicode_newlines(true)
+        .build();
+
+    assert_eq!(span.slice(3..).pos(), Position::new(2, 1, 3));
+    assert_eq!(span.slice(7..).pos(), Position::new(3, 1, 7));
+    assert_eq!(span.slice(9..).pos(), Position::new(4, 1, 9));
+}
+
+#[test]
+fn unicode_newlines_are_ignored_without_utf8() {
+    let span = with_unicode_newlines("a\u{2028}b\u{0C}c", false);
+
+    assert_eq!(span.slice(6..).pos(), Position::new(1, 7, 6));
+}
+
+#[test]
+fn separator_split_by_byte_slices() {
+    let input = "a\u{2028}b".as_bytes();
+    let span = Spanned::builder(input).unicode_newlines(true).build();
+
+    assert_eq!(span.slice(2..).slice(2..).pos(), Position::new(2, 1, 4));
+    assert_eq!(span.slice(4..).pos(), Position::new(2, 1, 4));
+
+    for start in 1..input.len() {
+        for end in start..=input.len() {
+            let stepwise = span.slice(start..).slice(end - start..);
+            let direct = span.slice(end..);
+
+            assert_eq!(stepwise.pos(), direct.pos());
+            assert_eq!(stepwise.byte_col(), direct.byte_col());
+        }
+    }
+    assert_eq!(span.slice(2..).pos(), Position::new(2, 1, 2));
+}
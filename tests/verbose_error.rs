@@ -0,0 +1,150 @@
+#![cfg(feature = "nom7")]
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, char, digit1, multispace0},
+    combinator::cut,
+    error::{context, VerboseError},
+    sequence::{delimited, preceded, separated_pair},
+    Err, IResult,
+};
+use nom_span::{convert_spanned_error, Spanned};
+
+extern crate nom;
+extern crate nom_span;
+
+type Input<'a> = Spanned<&'a str>;
+
+/// `{ <key>: <number> }`
+fn object(input: Input<'_>) -> IResult<Input<'_>, (Input<'_>, Input<'_>), VerboseError<Input<'_>>> {
+    context(
+        "object",
+        delimited(
+            char('{'),
+            preceded(
+                multispace0,
+                separated_pair(
+                    context("key", alpha1),
+                    cut(preceded(multispace0, char(':'))),
+                    cut(preceded(multispace0, context("number", digit1))),
+                ),
+            ),
+            cut(preceded(multispace0, char('}'))),
+        ),
+    )(input)
+}
+
+fn error(input: Input<'_>) -> VerboseError<Input<'_>> {
+    match object(input) {
+        Err(Err::Error(error) | Err::Failure(error)) => error,
+        result => panic!("the object should be invalid: {:?}", result),
+    }
+}
+
+#[test]
+fn frames_are_located_with_the_position_of_their_span() {
+    let source = "{\n  é: 12\n  🙌}";
+    let input = Input::new(source, true);
+    let error = error(input);
+
+    let positions: Vec<_> = error
+        .errors
+        .iter()
+        .map(|(span, _)| (span.line(), span.col()))
+        .collect();
+    assert_eq!(positions, [(2, 3), (2, 3), (1, 1)]);
+
+    assert_eq!(
+        convert_spanned_error(input, error),
+        "0: at line 2, column 3, in Alpha:
+  é: 12
+  ^
+
+1: at line 2, column 3, in key:
+  é: 12
+  ^
+
+2: at line 1, column 1, in object:
+{
+^
+
+"
+    );
+}
+
+#[test]
+fn chars_are_expected_under_their_position() {
+    let source = "{ \tab: 12 🙌";
+    let input = Input::new(source, true);
+    let error = error(input);
+
+    assert_eq!((error.errors[0].0.line(), error.errors[0].0.col()), (1, 11));
+    assert_eq!(
+        convert_spanned_error(input, error),
+        "0: at line 1, column 11:
+{ \tab: 12 🙌
+  \t       ^
+expected '}', found 🙌
+
+1: at line 1, column 1, in object:
+{ \tab: 12 🙌
+^
+
+"
+    );
+}
+
+#[test]
+fn the_end_of_the_input_is_expected() {
+    let source = "x = {a:";
+    let input = Input::new(source, true);
+    let error = error(nom::Slice::slice(&input, 4..));
+
+    assert_eq!(
+        convert_spanned_error(input, error),
+        "0: at line 1, column 8, in Digit:
+x = {a:
+       ^
+
+1: at line 1, column 8, in number:
+x = {a:
+       ^
+
+2: at line 1, column 5, in object:
+x = {a:
+    ^
+
+"
+    );
+}
+
+#[test]
+fn errors_on_trailing_whitespace_are_rendered() {
+    let source = "abc   \r\nz";
+    let input = Input::new(source, true);
+    let error = match preceded(tag("abc "), char::<_, VerboseError<Input<'_>>>('x'))(input) {
+        Err(Err::Error(error)) => error,
+        result => panic!("the char should be missing: {:?}", result),
+    };
+
+    assert_eq!(
+        convert_spanned_error(input, error),
+        "0: at line 1, column 5:
+abc
+    ^
+expected 'x', found  \n\n"
+    );
+
+    let error = match preceded(tag("abc   "), char::<_, VerboseError<Input<'_>>>('x'))(input) {
+        Err(Err::Error(error)) => error,
+        result => panic!("the char should be missing: {:?}", result),
+    };
+
+    assert_eq!(
+        convert_spanned_error(input, error),
+        "0: at line 1, column 7:
+abc
+      ^
+expected 'x', found \r\n\n"
+    );
+}
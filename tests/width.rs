@@ -0,0 +1,84 @@
+#![cfg(feature = "unicode-width")]
+
+use common::Slice;
+use nom::{bytes::complete::take, Parser};
+use nom_span::{ColumnUnit, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+
+fn width(input: &str) -> Span<'_> {
+    Spanned::builder(input)
+        .column_unit(ColumnUnit::Width)
+        .build()
+}
+
+#[test]
+fn wide_chars_take_two_columns() {
+    let span = width("漢字x");
+
+    assert_eq!(span.slice(3..).col(), 3);
+    assert_eq!(span.slice(6..).col(), 5);
+    assert_eq!(span.slice(7..).col(), 6);
+    assert_eq!(span.slice(7..).char_offset(), 3);
+}
+
+#[test]
+fn zero_width_chars_take_no_column() {
+    // U+200B ZERO WIDTH SPACE and U+0301 COMBINING ACUTE ACCENT
+    let span = width("a\u{200B}e\u{301}b");
+
+    assert_eq!(span.slice(4..).col(), 2);
+    assert_eq!(span.slice(7..).col(), 3);
+    assert_eq!(span.slice(8..).col(), 4);
+}
+
+#[test]
+fn mixed_ascii_and_cjk_line() {
+    let span = width("let 名前 = \"值\";\nx");
+
+    let mut rest = span;
+    while !rest.is_empty() {
+        rest = take::<_, _, ()>(1usize).parse(rest).unwrap().0;
+
+        assert_eq!(rest.pos(), span.slice(rest.byte_offset()..).pos());
+    }
+
+    assert_eq!(span.slice(10..).col(), 9);
+    assert_eq!(span.slice(19..).pos(), Position::new(1, 17, 19));
+    assert_eq!(rest.pos(), Position::new(2, 2, 21));
+}
+
+#[test]
+fn width_with_tab_expansion() {
+    let span = Spanned::builder("漢\tx")
+        .column_unit(ColumnUnit::Width)
+        .tab_width(4)
+        .build();
+
+    assert_eq!(span.slice(3..).col(), 3);
+    assert_eq!(span.slice(4..).col(), 5);
+    assert_eq!(span.slice(5..).col(), 6);
+}
+
+#[test]
+fn wide_char_cut_by_byte_slices() {
+    let span = Spanned::builder("中x".as_bytes())
+        .column_unit(ColumnUnit::Width)
+        .build();
+
+    assert_eq!(span.slice(1..).col(), 3);
+    assert_eq!(span.slice(2..).slice(1..).col(), span.slice(3..).col());
+    assert_eq!(
+        span.slice(1..).slice(1..).slice(1..).pos(),
+        span.slice(3..).pos()
+    );
+    assert_eq!(span.slice(3..).col(), 3);
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "winnow")]
+
+use nom_span::{Position, Spanned};
+use winnow::{
+    ascii::{alpha1, dec_uint, line_ending, space0},
+    combinator::{alt, repeat, separated_pair},
+    error::ContextError,
+    stream::{Location, Offset, Stream},
+    token::{any, literal, take_until},
+    ModalResult, Parser,
+};
+
+extern crate nom_span;
+extern crate winnow;
+
+type Span<'a> = Spanned<&'a str>;
+type BytesSpan<'a> = Spanned<&'a [u8]>;
+
+fn assignment<'a>(input: &mut Span<'a>) -> ModalResult<(Span<'a>, u32)> {
+    separated_pair(alpha1, (space0, literal("="), space0), dec_uint).parse_next(input)
+}
+
+#[test]
+fn columns_are_counted_after_consumption() {
+    let mut input = Span::new("héllo wörld", true);
+
+    let _: char = any::<_, ContextError>.parse_next(&mut input).unwrap();
+    assert_eq!(input.col(), 2);
+
+    let _: char = any::<_, ContextError>.parse_next(&mut input).unwrap();
+    assert_eq!(input.col(), 3);
+    assert_eq!(input.byte_offset(), 3);
+
+    let taken: Span<'_> = take_until::<_, _, ContextError>(.., "w")
+        .parse_next(&mut input)
+        .unwrap();
+    assert_eq!(*taken, "llo ");
+    assert_eq!(taken.pos(), Position::new(1, 3, 3));
+    assert_eq!(input.pos(), Position::new(1, 7, 7));
+}
+
+#[test]
+fn lines_are_counted_by_parsers() {
+    let mut input = Span::new("a = 1\nbc = 42\n", true);
+
+    let assignments: Vec<(Span<'_>, u32)> = repeat(1.., (assignment, line_ending).map(|(a, _)| a))
+        .parse_next(&mut input)
+        .unwrap();
+
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(assignments[1].0.pos(), Position::new(2, 1, 6));
+    assert_eq!(assignments[1].1, 42);
+    assert_eq!(input.pos(), Position::new(3, 1, 14));
+}
+
+#[test]
+fn backtracking_restores_the_position() {
+    let mut input = Span::new("ab\ncd", true);
+
+    let output: ModalResult<Span<'_>> =
+        alt(((literal("ab\n"), literal("x")).take(), literal("ab\nc"))).parse_next(&mut input);
+
+    let output = output.unwrap();
+    assert_eq!(*output, "ab\nc");
+    assert_eq!(input.pos(), Position::new(2, 2, 4));
+}
+
+#[test]
+fn location_is_the_byte_offset() {
+    let mut input = Span::new("🙌 x", true);
+
+    let checkpoint = input.checkpoint();
+    let _: char = any::<_, ContextError>.parse_next(&mut input).unwrap();
+
+    assert_eq!(input.current_token_start(), 4);
+    assert_eq!(input.previous_token_end(), 4);
+    assert_eq!(input.col(), 2);
+
+    input.reset(&checkpoint);
+    assert_eq!(input.pos(), Position::new(1, 1, 0));
+}
+
+#[test]
+fn byte_spans_are_streams() {
+    let mut input = BytesSpan::new(b"GET /\r\nHost", false);
+
+    let (method, _, _): (BytesSpan<'_>, _, BytesSpan<'_>) = (
+        literal::<_, _, ContextError>("GET"),
+        literal(" "),
+        take_until(.., "\r\n"),
+    )
+        .parse_next(&mut input)
+        .unwrap();
+    let _: BytesSpan<'_> = literal::<_, _, ContextError>("\r\n")
+        .parse_next(&mut input)
+        .unwrap();
+
+    assert_eq!(*method, b"GET");
+    assert_eq!(input.pos(), Position::new(2, 1, 7));
+}
+
+#[test]
+fn offsets_are_counted_from_the_start() {
+    let start = Span::new("ab cd", true);
+    let mut input = start;
+    let _: Span = alpha1::<_, ContextError>.parse_next(&mut input).unwrap();
+
+    assert_eq!(input.offset_from(&start), 2);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "they may come from different inputs")]
+fn offsets_between_unrelated_spans_panic() {
+    let _ = Span::new("ab", true).offset_from(&Span::new("cd", true));
+}
@@ -0,0 +1,65 @@
+use common::Slice;
+use nom::{
+    bytes::complete::{tag, take},
+    character::complete::{alpha1, digit0, space0},
+    multi::many0,
+    sequence::terminated,
+    IResult, Parser,
+};
+use nom_span::{with_span, Position, Spanned};
+
+#[cfg(feature = "nom7")]
+extern crate nom;
+#[cfg(feature = "nom8")]
+extern crate nom8 as nom;
+extern crate nom_span;
+
+mod common;
+
+type Span<'a> = Spanned<&'a str>;
+type Bytes<'a> = Spanned<&'a [u8]>;
+
+#[test]
+fn the_output_comes_with_the_start_and_the_end() {
+    let res: IResult<Span<'_>, _> = with_span(tag("é\nab")).parse(Span::new("é\nabc", true));
+    let (rest, (matched, start, end)) = res.unwrap();
+
+    assert_eq!(*matched, "é\nab");
+    assert_eq!(start, Position::new(1, 1, 0));
+    assert_eq!(end, Position::new(2, 3, 5));
+    assert_eq!(end, rest.pos());
+}
+
+#[test]
+fn consuming_nothing_gives_an_empty_range() {
+    let res: IResult<Span<'_>, _> = with_span(digit0).parse(Span::new("ab", true).slice(1..));
+    let (_, (digits, start, end)) = res.unwrap();
+
+    assert_eq!(*digits, "");
+    assert_eq!(start, end);
+    assert_eq!(start, Position::new(1, 2, 1));
+}
+
+#[test]
+fn byte_spans_are_supported() {
+    let res: IResult<Bytes<'_>, _> = with_span(take(3usize)).parse(Bytes::new(b"a\nbcd", false));
+    let (_, (taken, start, end)) = res.unwrap();
+
+    assert_eq!(*taken, b"a\nb");
+    assert_eq!(start, Position::new(1, 1, 0));
+    assert_eq!(end, Position::new(2, 2, 3));
+}
+
+#[test]
+fn every_item_of_many0_gets_its_range() {
+    let words: IResult<Span<'_>, _> =
+        many0(with_span(terminated(alpha1, space0))).parse(Span::new("ab  cd\te 1", true));
+    let (rest, words) = words.unwrap();
+
+    let ranges: Vec<_> = words
+        .iter()
+        .map(|(word, start, end)| (*word.data(), start.col, end.col))
+        .collect();
+    assert_eq!(ranges, [("ab", 1, 5), ("cd", 5, 8), ("e", 8, 10)]);
+    assert_eq!(*rest, "1");
+}